@@ -4,7 +4,7 @@
 
 use criterion::{
     black_box, criterion_group, criterion_main, AxisScale, BenchmarkId, Criterion,
-    PlotConfiguration,
+    PlotConfiguration, Throughput,
 };
 use ibig::{modular::ModuloRing, ops::DivRem, ubig, UBig};
 use rand::prelude::*;
@@ -69,6 +69,82 @@ fn bench_mul(criterion: &mut Criterion) {
     group.finish();
 }
 
+/// Multiplication and squaring around the crossover points between multiplication algorithms.
+///
+/// The sizes straddle the default thresholds in `thresholds.rs`: Karatsuba to Toom-3 at 192 words,
+/// Toom-3 to Toom-4 at 7000 words and Toom-4 to number-theoretic multiplication at 10000 words,
+/// for 64-bit words. Time per bit changes slope at the crossovers.
+fn bench_mul_tiers(criterion: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = criterion.benchmark_group("mul_tiers");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+    group.sample_size(10);
+
+    for words in &[150, 192, 250, 5000, 7000, 8500, 10000, 12000] {
+        let bits = 64 * words;
+        let a = random_ubig(bits, &mut rng);
+        let b = random_ubig(bits, &mut rng);
+        group.throughput(Throughput::Elements(bits as u64));
+        group.bench_with_input(BenchmarkId::new("mul", bits), &bits, |bencher, _| {
+            bencher.iter(|| black_box(&a) * black_box(&b))
+        });
+        group.bench_with_input(BenchmarkId::new("sqr", bits), &bits, |bencher, _| {
+            bencher.iter(|| black_box(&a) * black_box(&a))
+        });
+    }
+
+    group.finish();
+}
+
+/// Multiplication and squaring of 10^5 to 10^8 bits, crossing over from Toom-3 to
+/// number-theoretic multiplication.
+fn bench_mul_ntt(criterion: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = criterion.benchmark_group("mul_ntt");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+    group.sample_size(10);
+
+    for bits in &[100_000, 1_000_000, 10_000_000, 100_000_000] {
+        let a = random_ubig(*bits, &mut rng);
+        let b = random_ubig(*bits, &mut rng);
+        group.throughput(Throughput::Elements(*bits as u64));
+        group.bench_with_input(BenchmarkId::new("mul", bits), bits, |bencher, _| {
+            bencher.iter(|| black_box(&a) * black_box(&b))
+        });
+        group.bench_with_input(BenchmarkId::new("sqr", bits), bits, |bencher, _| {
+            bencher.iter(|| black_box(&a) * black_box(&a))
+        });
+    }
+
+    group.finish();
+}
+
+/// Arithmetic on 128 to 256-bit numbers, which can be stored inline with the `inline-2` and
+/// `inline-4` features.
+fn bench_small_arith(criterion: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = criterion.benchmark_group("small_arith");
+
+    for bits in &[128, 192, 256] {
+        let a = random_ubig(*bits, &mut rng);
+        let b = random_ubig(*bits, &mut rng);
+        group.bench_with_input(BenchmarkId::new("add", bits), bits, |bencher, _| {
+            bencher.iter(|| black_box(&a) + black_box(&b))
+        });
+        group.bench_with_input(BenchmarkId::new("mul", bits), bits, |bencher, _| {
+            bencher.iter(|| black_box(&a) * black_box(&b))
+        });
+        group.bench_with_input(BenchmarkId::new("shl", bits), bits, |bencher, _| {
+            bencher.iter(|| black_box(&a) << black_box(3))
+        });
+        group.bench_with_input(BenchmarkId::new("clone", bits), bits, |bencher, _| {
+            bencher.iter(|| black_box(&a).clone())
+        });
+    }
+
+    group.finish();
+}
+
 fn bench_div(criterion: &mut Criterion) {
     let mut rng = StdRng::seed_from_u64(1);
     let mut group = criterion.benchmark_group("div");
@@ -86,6 +162,30 @@ fn bench_div(criterion: &mut Criterion) {
     group.finish();
 }
 
+fn bench_div_large(criterion: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = criterion.benchmark_group("div_large");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+    group.sample_size(10);
+
+    // Dividends of 10^6 to 10^8 bits. Divisions with divisors and quotients longer than
+    // `div_newton` use Newton's method. With 64-bit words and the default thresholds, these are
+    // the balanced divisions from 2*10^7 bits and the divisions with a long quotient from
+    // 5*10^7 bits.
+    for bits in &[1_000_000, 10_000_000, 20_000_000, 50_000_000, 100_000_000] {
+        let a = random_ubig(*bits, &mut rng);
+        // Balanced (2:1) and with a long quotient (8:1).
+        for (shape, ratio) in [("balanced", 2), ("long_quotient", 8)].iter() {
+            let b = random_ubig(bits / ratio, &mut rng);
+            group.bench_with_input(BenchmarkId::new(*shape, bits), bits, |bencher, _| {
+                bencher.iter(|| black_box(&a).div_rem(black_box(&b)))
+            });
+        }
+    }
+
+    group.finish();
+}
+
 fn bench_to_hex(criterion: &mut Criterion) {
     let mut rng = StdRng::seed_from_u64(1);
     let mut group = criterion.benchmark_group("to_hex");
@@ -216,12 +316,52 @@ fn bench_modulo_pow(criterion: &mut Criterion) {
 
     group.finish();
 }
+
+fn bench_fibonacci(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("fibonacci");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for log_n in 1..=6 {
+        let n = 10usize.pow(log_n);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |bencher, n| {
+            bencher.iter(|| UBig::fibonacci(black_box(*n)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_modulo_fibonacci(criterion: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = criterion.benchmark_group("modulo_fibonacci");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for log_bits in 1..=4 {
+        if log_bits == 4 {
+            group.sample_size(10);
+        }
+        let bits = 10usize.pow(log_bits);
+        let m = random_ubig(bits, &mut rng);
+        let ring = ModuloRing::new(&m);
+        let n = random_ubig(bits, &mut rng);
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |bencher, _| {
+            bencher.iter(|| ring.fibonacci(black_box(&n)))
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_add,
     bench_sub,
     bench_mul,
+    bench_mul_tiers,
+    bench_mul_ntt,
+    bench_small_arith,
     bench_div,
+    bench_div_large,
     bench_to_hex,
     bench_to_dec,
     bench_from_hex,
@@ -229,6 +369,8 @@ criterion_group!(
     bench_pow,
     bench_modulo_mul,
     bench_modulo_pow,
+    bench_fibonacci,
+    bench_modulo_fibonacci,
 );
 
 criterion_main!(benches);
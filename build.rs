@@ -0,0 +1,55 @@
+//! Build script.
+//!
+//! Reads algorithm thresholds from the file named by the `IBIG_THRESHOLDS` environment variable,
+//! and writes the initializer of the built-in `ThresholdConfig` to `$OUT_DIR/thresholds.rs`.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    // Configuration options for testing: the `Word` size and the number-theoretic transform.
+    println!("cargo:rustc-check-cfg=cfg(force_bits, values(\"16\", \"32\", \"64\"))");
+    println!("cargo:rustc-check-cfg=cfg(force_ntt, values(\"u32\", \"word\"))");
+
+    println!("cargo:rerun-if-env-changed=IBIG_THRESHOLDS");
+
+    let mut code = String::from(
+        "{\n    #[allow(unused_mut)]\n    let mut config = ThresholdConfig::DEFAULT;\n",
+    );
+    if let Some(path) = env::var_os("IBIG_THRESHOLDS") {
+        let path = Path::new(&path);
+        println!("cargo:rerun-if-changed={}", path.display());
+        let contents = fs::read_to_string(path).unwrap_or_else(|err| {
+            panic!("IBIG_THRESHOLDS: can't read {}: {}", path.display(), err)
+        });
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // Unknown names are reported by the compiler.
+            let (name, value) = parse_line(line).unwrap_or_else(|| {
+                panic!(
+                    "IBIG_THRESHOLDS: {}:{}: expected `name = value`",
+                    path.display(),
+                    index + 1
+                )
+            });
+            code += &format!("    config.{} = {};\n", name, value);
+        }
+    }
+    code += "    config\n}\n";
+
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("thresholds.rs"), code).unwrap();
+}
+
+/// Parse `name = value`.
+fn parse_line(line: &str) -> Option<(&str, usize)> {
+    let mut parts = line.splitn(2, '=');
+    let name = parts.next()?.trim();
+    let value = parts.next()?.trim().parse().ok()?;
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    Some((name, value))
+}
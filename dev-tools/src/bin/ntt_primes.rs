@@ -97,14 +97,16 @@ fn is_prime(mult: &UBig, order: usize) -> bool {
     }
 }
 
-/// Find 2^order-th root modulo a prime p.
+/// Find the smallest 2^order-th primitive root of unity modulo a prime p.
 fn find_root(prime: &UBig, order: usize) -> UBig {
     assert!(order >= 1);
 
     let ring = ModuloRing::new(prime);
+    // The target order is a power of 2, so factorizing the group order is not needed.
+    let factors = [(ubig!(2), order)];
+    let target_order = ubig!(1) << order;
     let mut root = ubig!(1);
-    let exponent = ubig!(1) << (order - 1);
-    while ring.from(&root).pow(&exponent) != ring.from(-1) {
+    while ring.from(&root).order_with_factors(&factors) != Some(target_order.clone()) {
         root += ubig!(1);
     }
     root
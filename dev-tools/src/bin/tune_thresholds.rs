@@ -0,0 +1,255 @@
+//! Measure the algorithm thresholds for the host CPU and write them to a thresholds file.
+//!
+//! Usage: `cargo run --release -p dev-tools --bin tune_thresholds -- <output file>`
+//!
+//! Build with `IBIG_THRESHOLDS=<absolute path of the output file>` to use the thresholds.
+
+use ibig::{ubig, ThresholdConfig, UBig, Word};
+use rand::{thread_rng, Rng};
+use std::{
+    env, fs,
+    time::{Duration, Instant},
+};
+
+/// Ratio between consecutive lengths measured.
+const GRID_RATIO: f64 = 1.1;
+
+/// Total time of a measurement.
+const MEASUREMENT_TIME: Duration = Duration::from_millis(30);
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 2 {
+        eprintln!("Usage: {} <output file>", args[0]);
+        std::process::exit(1);
+    }
+
+    let mut config = ThresholdConfig::DEFAULT;
+    config.set_current_for_tuning();
+
+    tune_mul(&mut config, "mul_simple", 8, 200, 0, |c| &mut c.mul_simple);
+    let lower = config.mul_simple;
+    tune_mul(&mut config, "mul_karatsuba", 48, 800, lower, |c| {
+        &mut c.mul_karatsuba
+    });
+    let lower = config.mul_karatsuba;
+    tune_mul(&mut config, "mul_toom_3", 200, 20000, lower, |c| {
+        &mut c.mul_toom_3
+    });
+    let lower = config.mul_toom_3;
+    tune_mul(&mut config, "mul_toom_4", 1000, 40000, lower, |c| {
+        &mut c.mul_toom_4
+    });
+    let lower = config.mul_toom_4;
+    tune_mul(&mut config, "mul_toom_8", 4000, 100000, lower, |c| {
+        &mut c.mul_toom_8
+    });
+    tune_div(&mut config, "div_simple", 16, 400, 0, |c| &mut c.div_simple);
+    // Newton's method computes reciprocals of up to 64 words by division.
+    let lower = config.div_simple.max(64);
+    tune_div(&mut config, "div_newton", 16000, 200000, lower, |c| {
+        &mut c.div_newton
+    });
+    tune_parse(&mut config);
+    tune_fmt(&mut config);
+
+    let contents = format!("# Generated by tune_thresholds.\n{}", config);
+    fs::write(&args[1], &contents).expect("can't write the output file");
+    println!();
+    print!("{}", contents);
+    println!();
+    println!(
+        "Build with IBIG_THRESHOLDS=<absolute path of {}> to use these thresholds.",
+        args[1]
+    );
+}
+
+/// Tune a multiplication threshold by multiplying n-word numbers with the threshold at n (the
+/// lower algorithm) and at n - 1 (the higher algorithm).
+///
+/// The threshold stays above `lower`, the previous threshold. The higher thresholds are raised as
+/// needed to keep the configuration valid.
+fn tune_mul(
+    config: &mut ThresholdConfig,
+    name: &str,
+    min_len: usize,
+    max_len: usize,
+    lower: usize,
+    threshold: fn(&mut ThresholdConfig) -> &mut usize,
+) {
+    let min_len = min_len.max(lower + 2);
+    let max_len = max_len.max(min_len);
+    let base = *config;
+    let with_threshold = |value: usize| {
+        let mut c = base;
+        *threshold(&mut c) = value;
+        raise_mul_thresholds(&mut c);
+        c
+    };
+    let value = find_crossover(name, min_len, max_len, with_threshold, |n| {
+        let a = random_ubig(n);
+        let b = random_ubig(n);
+        Box::new(move || {
+            let _ = &a * &b;
+        })
+    });
+    *config = with_threshold(value);
+    config.set_current_for_tuning();
+}
+
+/// Make the multiplication thresholds non-decreasing by raising the higher ones.
+fn raise_mul_thresholds(config: &mut ThresholdConfig) {
+    config.mul_karatsuba = config.mul_karatsuba.max(config.mul_simple);
+    config.mul_toom_3 = config.mul_toom_3.max(config.mul_karatsuba);
+    config.mul_toom_4 = config.mul_toom_4.max(config.mul_toom_3);
+    config.mul_toom_8 = config.mul_toom_8.max(config.mul_toom_4);
+}
+
+/// Tune a division threshold by dividing 2n-word numbers by n-word numbers with the threshold
+/// at n (the lower algorithm) and at n - 1 (the higher algorithm).
+///
+/// The threshold stays at least `lower`. `div_newton` is raised as needed to keep the
+/// configuration valid.
+fn tune_div(
+    config: &mut ThresholdConfig,
+    name: &str,
+    min_len: usize,
+    max_len: usize,
+    lower: usize,
+    threshold: fn(&mut ThresholdConfig) -> &mut usize,
+) {
+    let min_len = min_len.max(lower + 1);
+    let max_len = max_len.max(min_len);
+    let base = *config;
+    let with_threshold = |value: usize| {
+        let mut c = base;
+        *threshold(&mut c) = value;
+        c.div_newton = c.div_newton.max(c.div_simple);
+        c
+    };
+    let value = find_crossover(name, min_len, max_len, with_threshold, |n| {
+        let a = random_ubig(2 * n);
+        let b = random_ubig(n);
+        Box::new(move || {
+            let _ = &a / &b;
+        })
+    });
+    *config = with_threshold(value);
+    config.set_current_for_tuning();
+}
+
+/// Tune the chunk length for parsing by parsing a long decimal string.
+fn tune_parse(config: &mut ThresholdConfig) {
+    let text = random_ubig(20000).to_string();
+    let base = *config;
+    config.parse_chunk = fastest("parse_chunk", &[32, 64, 128, 256, 512, 1024], |value| {
+        let mut c = base;
+        c.parse_chunk = value;
+        c.set_current_for_tuning();
+        measure(|| {
+            let _ = UBig::from_str_radix(&text, 10).unwrap();
+        })
+    });
+    config.set_current_for_tuning();
+}
+
+/// Tune the chunk length for formatting by formatting a long number in decimal.
+fn tune_fmt(config: &mut ThresholdConfig) {
+    let number = random_ubig(20000);
+    let base = *config;
+    config.fmt_chunk = fastest("fmt_chunk", &[4, 8, 16, 32, 64], |value| {
+        let mut c = base;
+        c.fmt_chunk = value;
+        c.set_current_for_tuning();
+        measure(|| {
+            let _ = number.to_string();
+        })
+    });
+    config.set_current_for_tuning();
+}
+
+/// Find the largest length for which the lower algorithm is faster.
+///
+/// Measures lengths on a geometric grid from min_len to max_len. The crossover is where the
+/// higher algorithm is faster at two consecutive lengths. Returns max_len if there is none.
+fn find_crossover<C, B>(
+    name: &str,
+    min_len: usize,
+    max_len: usize,
+    with_threshold: C,
+    benchmark: B,
+) -> usize
+where
+    C: Fn(usize) -> ThresholdConfig,
+    B: Fn(usize) -> Box<dyn FnMut()>,
+{
+    let mut prev_len = None;
+    for n in grid(min_len, max_len) {
+        let mut f = benchmark(n);
+        with_threshold(n).set_current_for_tuning();
+        let lower = measure(&mut f);
+        with_threshold(n - 1).set_current_for_tuning();
+        let higher = measure(&mut f);
+        println!("{} n = {}: lower {:?}, higher {:?}", name, n, lower, higher);
+        if higher < lower {
+            if let Some(prev_len) = prev_len {
+                println!("{} = {}", name, prev_len - 1);
+                return prev_len - 1;
+            }
+            prev_len = Some(n);
+        } else {
+            prev_len = None;
+        }
+    }
+    println!("{} = {}", name, max_len);
+    max_len
+}
+
+/// Choose the fastest value.
+fn fastest<F>(name: &str, values: &[usize], mut time: F) -> usize
+where
+    F: FnMut(usize) -> Duration,
+{
+    let mut best = (Duration::MAX, values[0]);
+    for &value in values {
+        let t = time(value);
+        println!("{} = {}: {:?}", name, value, t);
+        if t < best.0 {
+            best = (t, value);
+        }
+    }
+    let (_, value) = best;
+    println!("{} = {}", name, value);
+    value
+}
+
+/// Lengths from min_len to max_len, increasing by GRID_RATIO.
+fn grid(min_len: usize, max_len: usize) -> Vec<usize> {
+    let mut lens = vec![];
+    let mut n = min_len;
+    while n <= max_len {
+        lens.push(n);
+        n = ((n as f64 * GRID_RATIO) as usize).max(n + 1);
+    }
+    lens
+}
+
+/// Minimum time of one run.
+fn measure<F: FnMut()>(mut f: F) -> Duration {
+    let start = Instant::now();
+    let mut best = Duration::MAX;
+    let mut runs = 0;
+    while runs < 3 || start.elapsed() < MEASUREMENT_TIME {
+        let run_start = Instant::now();
+        f();
+        best = best.min(run_start.elapsed());
+        runs += 1;
+    }
+    best
+}
+
+/// Random number of exactly n words.
+fn random_ubig(n: usize) -> UBig {
+    let bits = n * Word::MAX.count_ones() as usize;
+    thread_rng().gen_range((ubig!(1) << (bits - 1))..(ubig!(1) << bits))
+}
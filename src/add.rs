@@ -40,6 +40,14 @@ pub(crate) fn sub_one_in_place(words: &mut [Word]) -> bool {
     true
 }
 
+/// words = -words in two's complement.
+pub(crate) fn negate_in_place(words: &mut [Word]) {
+    for word in words.iter_mut() {
+        *word = !*word;
+    }
+    let _ = add_one_in_place(words);
+}
+
 /// Add a word to a non-empty word sequence.
 ///
 /// Returns overflow.
@@ -10,7 +10,7 @@ use crate::{
 };
 use core::{
     convert::TryFrom,
-    mem,
+    iter::Sum,
     ops::{Add, AddAssign, Sub, SubAssign},
 };
 
@@ -64,13 +64,17 @@ impl Add<&UBig> for &UBig {
     fn add(self, rhs: &UBig) -> UBig {
         match (self.repr(), rhs.repr()) {
             (Small(word0), Small(word1)) => UBig::add_word(*word0, *word1),
-            (Small(word0), Large(buffer1)) => UBig::add_large_word(buffer1.clone(), *word0),
-            (Large(buffer0), Small(word1)) => UBig::add_large_word(buffer0.clone(), *word1),
+            (Small(word0), Large(buffer1)) => {
+                UBig::add_large_word(UBig::copy_for_add(buffer1), *word0)
+            }
+            (Large(buffer0), Small(word1)) => {
+                UBig::add_large_word(UBig::copy_for_add(buffer0), *word1)
+            }
             (Large(buffer0), Large(buffer1)) => {
                 if buffer0.len() >= buffer1.len() {
-                    UBig::add_large(buffer0.clone(), buffer1)
+                    UBig::add_large(UBig::copy_for_add(buffer0), buffer1)
                 } else {
-                    UBig::add_large(buffer1.clone(), buffer0)
+                    UBig::add_large(UBig::copy_for_add(buffer1), buffer0)
                 }
             }
         }
@@ -79,13 +83,13 @@ impl Add<&UBig> for &UBig {
 
 impl AddAssign<UBig> for UBig {
     fn add_assign(&mut self, rhs: UBig) {
-        *self = mem::take(self) + rhs;
+        self.update(|x| x + rhs);
     }
 }
 
 impl AddAssign<&UBig> for UBig {
     fn add_assign(&mut self, rhs: &UBig) {
-        *self = mem::take(self) + rhs;
+        self.update(|x| x + rhs);
     }
 }
 
@@ -123,13 +127,13 @@ impl Sub<&UBig> for &UBig {
 
 impl SubAssign<UBig> for UBig {
     fn sub_assign(&mut self, rhs: UBig) {
-        *self = mem::take(self) - rhs;
+        self.update(|x| x - rhs);
     }
 }
 
 impl SubAssign<&UBig> for UBig {
     fn sub_assign(&mut self, rhs: &UBig) {
-        *self = mem::take(self) - rhs;
+        self.update(|x| x - rhs);
     }
 }
 
@@ -188,13 +192,13 @@ impl Add<&IBig> for &IBig {
 
 impl AddAssign<IBig> for IBig {
     fn add_assign(&mut self, rhs: IBig) {
-        *self = mem::take(self) + rhs;
+        self.update(|x| x + rhs);
     }
 }
 
 impl AddAssign<&IBig> for IBig {
     fn add_assign(&mut self, rhs: &IBig) {
-        *self = mem::take(self) + rhs;
+        self.update(|x| x + rhs);
     }
 }
 
@@ -239,19 +243,100 @@ impl Sub<&IBig> for &IBig {
 
 impl SubAssign<IBig> for IBig {
     fn sub_assign(&mut self, rhs: IBig) {
-        *self = mem::take(self) - rhs;
+        self.update(|x| x - rhs);
     }
 }
 
 impl SubAssign<&IBig> for IBig {
     fn sub_assign(&mut self, rhs: &IBig) {
-        *self = mem::take(self) - rhs;
+        self.update(|x| x - rhs);
+    }
+}
+
+impl Sum<UBig> for UBig {
+    fn sum<I: Iterator<Item = UBig>>(iter: I) -> UBig {
+        iter.fold(UBig::from_word(0), |sum, x| sum + x)
+    }
+}
+
+impl<'a> Sum<&'a UBig> for UBig {
+    fn sum<I: Iterator<Item = &'a UBig>>(iter: I) -> UBig {
+        iter.fold(UBig::from_word(0), |sum, x| sum + x)
+    }
+}
+
+impl Sum<IBig> for IBig {
+    fn sum<I: Iterator<Item = IBig>>(iter: I) -> IBig {
+        iter.fold(IBig::default(), |sum, x| sum + x)
+    }
+}
+
+impl<'a> Sum<&'a IBig> for IBig {
+    fn sum<I: Iterator<Item = &'a IBig>>(iter: I) -> IBig {
+        iter.fold(IBig::default(), |sum, x| sum + x)
     }
 }
 
 impl UBig {
+    /// Addition into an existing number: `*out = a + b`.
+    ///
+    /// Reuses the memory of `out` when it has enough capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// let mut out = UBig::default();
+    /// UBig::add_into(&mut out, &ubig!(2), &ubig!(3));
+    /// assert_eq!(out, ubig!(5));
+    /// ```
+    pub fn add_into(out: &mut UBig, a: &UBig, b: &UBig) {
+        let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+        match long.repr() {
+            Small(_) => out.assign(a + b),
+            Large(words) => {
+                let mut buffer = out.take_buffer(words.len() + 1);
+                buffer.extend_from_slice(&words[..]);
+                if add::add_in_place(&mut buffer, short.as_words()) {
+                    buffer.push(1);
+                }
+                *out = UBig::from_reused_buffer(buffer);
+            }
+        }
+    }
+
+    /// Subtraction into an existing number: `*out = a - b`.
+    ///
+    /// Reuses the memory of `out` when it has enough capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// let mut out = UBig::default();
+    /// UBig::sub_into(&mut out, &ubig!(5), &ubig!(3));
+    /// assert_eq!(out, ubig!(2));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a < b`.
+    pub fn sub_into(out: &mut UBig, a: &UBig, b: &UBig) {
+        match a.repr() {
+            Large(words) if words.len() >= b.len() => {
+                let mut buffer = out.take_buffer(words.len());
+                buffer.extend_from_slice(&words[..]);
+                if add::sub_in_place(&mut buffer, b.as_words()) {
+                    panic!("UBig subtraction overflow");
+                }
+                *out = UBig::from_reused_buffer(buffer);
+            }
+            _ => out.assign(a - b),
+        }
+    }
+
     /// Add two `Word`s.
-    fn add_word(a: Word, b: Word) -> UBig {
+    pub(crate) fn add_word(a: Word, b: Word) -> UBig {
         let (res, overflow) = a.overflowing_add(b);
         if overflow {
             let mut buffer = Buffer::allocate(2);
@@ -264,7 +349,7 @@ impl UBig {
     }
 
     /// Add a large number to a `Word`.
-    fn add_large_word(mut buffer: Buffer, rhs: Word) -> UBig {
+    pub(crate) fn add_large_word(mut buffer: Buffer, rhs: Word) -> UBig {
         debug_assert!(buffer.len() >= 2);
         if add::add_word_in_place(&mut buffer, rhs) {
             buffer.push_may_reallocate(1);
@@ -272,13 +357,20 @@ impl UBig {
         buffer.into()
     }
 
+    /// Copy words into a new buffer with space for a carry.
+    pub(crate) fn copy_for_add(words: &[Word]) -> Buffer {
+        let mut buffer = Buffer::allocate(words.len() + 1);
+        buffer.extend_from_slice(words);
+        buffer
+    }
+
     /// Add two large numbers.
-    fn add_large(mut buffer: Buffer, rhs: &[Word]) -> UBig {
+    pub(crate) fn add_large(mut buffer: Buffer, rhs: &[Word]) -> UBig {
         let n = buffer.len().min(rhs.len());
         let overflow = add::add_same_len_in_place(&mut buffer[..n], &rhs[..n]);
         if rhs.len() > n {
             buffer.ensure_capacity(rhs.len());
-            buffer.extend(&rhs[n..]);
+            buffer.extend_from_slice(&rhs[n..]);
         }
         if overflow && add::add_one_in_place(&mut buffer[n..]) {
             buffer.push_may_reallocate(1);
@@ -286,14 +378,14 @@ impl UBig {
         buffer.into()
     }
 
-    fn from_ibig_after_sub(x: IBig) -> UBig {
+    pub(crate) fn from_ibig_after_sub(x: IBig) -> UBig {
         match UBig::try_from(x) {
             Ok(v) => v,
             Err(_) => panic!("UBig subtraction overflow"),
         }
     }
 
-    fn sub_large_word(mut lhs: Buffer, rhs: Word) -> UBig {
+    pub(crate) fn sub_large_word(mut lhs: Buffer, rhs: Word) -> UBig {
         let overflow = add::sub_word_in_place(&mut lhs, rhs);
         assert!(!overflow);
         lhs.into()
@@ -344,7 +436,7 @@ impl IBig {
         }
     }
 
-    fn sub_word_word(lhs: Word, rhs: Word) -> IBig {
+    pub(crate) fn sub_word_word(lhs: Word, rhs: Word) -> IBig {
         if lhs >= rhs {
             IBig::from(lhs - rhs)
         } else {
@@ -352,11 +444,11 @@ impl IBig {
         }
     }
 
-    fn sub_large_word(lhs: Buffer, rhs: Word) -> IBig {
+    pub(crate) fn sub_large_word(lhs: Buffer, rhs: Word) -> IBig {
         UBig::sub_large_word(lhs, rhs).into()
     }
 
-    fn sub_large(mut lhs: Buffer, rhs: &[Word]) -> IBig {
+    pub(crate) fn sub_large(mut lhs: Buffer, rhs: &[Word]) -> IBig {
         if lhs.len() >= rhs.len() {
             let sign = add::sub_in_place_with_sign(&mut lhs, rhs);
             IBig::from_sign_magnitude(sign, lhs.into())
@@ -364,7 +456,7 @@ impl IBig {
             let n = lhs.len();
             let borrow = add::sub_same_len_in_place_swap(&rhs[..n], &mut lhs);
             lhs.ensure_capacity(rhs.len());
-            lhs.extend(&rhs[n..]);
+            lhs.extend_from_slice(&rhs[n..]);
             if borrow {
                 let overflow = add::sub_one_in_place(&mut lhs[n..]);
                 assert!(!overflow);
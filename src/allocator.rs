@@ -0,0 +1,143 @@
+//! Allocators for the words of numbers.
+//!
+//! With the `allocator_api` feature, numbers can keep their words in memory from a custom
+//! allocator. Without it, `WordAllocator` is an empty stand-in for the global allocator, so the
+//! rest of the crate can pass it around regardless of the feature.
+
+use crate::arch::word::Word;
+use alloc::vec::Vec;
+
+#[cfg(feature = "allocator_api")]
+use alloc::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    sync::Arc,
+};
+#[cfg(feature = "allocator_api")]
+use core::ptr::NonNull;
+
+/// Allocator for the words of a number: the global allocator, or a shared custom allocator.
+#[cfg(feature = "allocator_api")]
+#[derive(Clone)]
+pub(crate) struct WordAllocator(Option<Arc<dyn Allocator + Send + Sync>>);
+
+/// Allocator for the words of a number: always the global allocator.
+#[cfg(not(feature = "allocator_api"))]
+#[derive(Clone)]
+pub(crate) struct WordAllocator;
+
+/// Words on the heap, in memory from a `WordAllocator`.
+#[cfg(feature = "allocator_api")]
+pub(crate) type WordVec = Vec<Word, WordAllocator>;
+
+/// Words on the heap, in memory from a `WordAllocator`.
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) type WordVec = Vec<Word>;
+
+#[cfg(feature = "allocator_api")]
+impl WordAllocator {
+    /// The global allocator.
+    pub(crate) fn global() -> WordAllocator {
+        WordAllocator(None)
+    }
+
+    /// Custom allocator.
+    pub(crate) fn new<A: Allocator + Send + Sync + 'static>(allocator: A) -> WordAllocator {
+        WordAllocator(Some(Arc::new(allocator)))
+    }
+
+    /// Whether `self` and `other` are the same allocator.
+    pub(crate) fn same_as(&self, other: &WordAllocator) -> bool {
+        match (&self.0, &other.0) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Empty vector with capacity for exactly `capacity` words.
+    pub(crate) fn vec_with_capacity(&self, capacity: usize) -> WordVec {
+        Vec::with_capacity_in(capacity, self.clone())
+    }
+
+    /// Empty vector with capacity for exactly `capacity` words, or `None` if the allocator can't
+    /// provide the memory.
+    pub(crate) fn try_vec_with_capacity(&self, capacity: usize) -> Option<WordVec> {
+        let mut vec = Vec::new_in(self.clone());
+        vec.try_reserve_exact(capacity).ok()?;
+        Some(vec)
+    }
+
+    /// The allocator of `vec`.
+    pub(crate) fn of(vec: &WordVec) -> &WordAllocator {
+        vec.allocator()
+    }
+
+    fn inner(&self) -> &(dyn Allocator + Send + Sync) {
+        match &self.0 {
+            None => &Global,
+            Some(allocator) => &**allocator,
+        }
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
+impl WordAllocator {
+    /// The global allocator.
+    pub(crate) fn global() -> WordAllocator {
+        WordAllocator
+    }
+
+    /// Whether `self` and `other` are the same allocator.
+    pub(crate) fn same_as(&self, _other: &WordAllocator) -> bool {
+        true
+    }
+
+    /// Empty vector with capacity for exactly `capacity` words.
+    pub(crate) fn vec_with_capacity(&self, capacity: usize) -> WordVec {
+        Vec::with_capacity(capacity)
+    }
+
+    /// Empty vector with capacity for exactly `capacity` words, or `None` if the allocator can't
+    /// provide the memory.
+    pub(crate) fn try_vec_with_capacity(&self, capacity: usize) -> Option<WordVec> {
+        let mut vec = Vec::new();
+        vec.try_reserve_exact(capacity).ok()?;
+        Some(vec)
+    }
+
+    /// The allocator of `vec`.
+    pub(crate) fn of(_vec: &WordVec) -> &WordAllocator {
+        &WordAllocator
+    }
+}
+
+// Safe because all clones share the same underlying allocator, which stays alive as long as any
+// of them does.
+#[cfg(feature = "allocator_api")]
+unsafe impl Allocator for WordAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner().allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.inner().deallocate(ptr, layout)
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner().grow(ptr, old_layout, new_layout)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner().shrink(ptr, old_layout, new_layout)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{
+    arch::word::Word,
+    primitive::{extend_word, split_double_word},
+};
+
+/// Multiply a word sequence by a `Word` in place with carry in.
+///
+/// Returns carry.
+pub(crate) fn mul_word_in_place_with_carry(words: &mut [Word], rhs: Word, mut carry: Word) -> Word {
+    for a in words {
+        // a * b + carry <= MAX * MAX + MAX < DoubleWord::MAX
+        let (v_lo, v_hi) =
+            split_double_word(extend_word(*a) * extend_word(rhs) + extend_word(carry));
+        *a = v_lo;
+        carry = v_hi;
+    }
+    carry
+}
+
+/// words += mult * rhs
+///
+/// Returns carry.
+pub(crate) fn add_mul_word_same_len_in_place(words: &mut [Word], mult: Word, rhs: &[Word]) -> Word {
+    debug_assert!(words.len() == rhs.len());
+    let mut carry: Word = 0;
+    for (a, b) in words.iter_mut().zip(rhs.iter()) {
+        // a + mult * b + carry <= MAX * MAX + 2 * MAX <= DoubleWord::MAX
+        let (v_lo, v_hi) = split_double_word(
+            extend_word(*a) + extend_word(carry) + extend_word(mult) * extend_word(*b),
+        );
+        *a = v_lo;
+        carry = v_hi;
+    }
+    carry
+}
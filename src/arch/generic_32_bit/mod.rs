@@ -4,6 +4,9 @@ pub(crate) mod add;
 #[path = "../generic/digits.rs"]
 pub(crate) mod digits;
 
+#[path = "../generic/mul.rs"]
+pub(crate) mod mul;
+
 pub(crate) mod ntt;
 
 pub(crate) mod word;
@@ -1,5 +1,8 @@
 /// Machine word.
-pub(crate) type Word = u32;
+///
+/// [UBigRef](crate::UBigRef) and [IBigRef](crate::IBigRef) view numbers stored as little-endian
+/// slices of words.
+pub type Word = u32;
 
 /// Signed machine word.
 pub(crate) type SignedWord = i32;
@@ -2,6 +2,7 @@
 
 pub(crate) use arch_impl::add;
 pub(crate) use arch_impl::digits;
+pub(crate) use arch_impl::mul;
 pub(crate) use arch_impl::ntt;
 pub(crate) use arch_impl::word;
 
@@ -3,6 +3,9 @@ pub(crate) mod add;
 #[path = "../generic/digits.rs"]
 pub(crate) mod digits;
 
+#[path = "../generic/mul.rs"]
+pub(crate) mod mul;
+
 #[path = "../generic_32_bit/ntt.rs"]
 pub(crate) mod ntt;
 
@@ -3,6 +3,8 @@ pub(crate) mod add;
 #[path = "../generic/digits.rs"]
 pub(crate) mod digits;
 
+pub(crate) mod mul;
+
 #[path = "../generic_64_bit/ntt.rs"]
 pub(crate) mod ntt;
 
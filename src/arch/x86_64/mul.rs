@@ -0,0 +1,258 @@
+//! Multiply-accumulate kernels using BMI2 and ADX instructions.
+//!
+//! `mulx` multiplies without modifying flags, and `adcx` and `adox` add along two independent
+//! carry chains, so the low and high halves of the products are accumulated in a single pass.
+//!
+//! With the `std` feature the kernels are selected by runtime CPU feature detection. Otherwise
+//! they are used if the target features are enabled at compile time, e.g. with
+//! `-C target-cpu=native`.
+
+use crate::arch::word::Word;
+use core::arch::asm;
+
+#[path = "../generic/mul.rs"]
+mod generic;
+
+/// Number of words processed by one iteration of the kernels.
+const UNROLL: usize = 4;
+
+/// Shorter sequences use the generic code, which the compiler can inline and unroll.
+const MIN_LEN: usize = 16;
+
+/// Is the `mulx` instruction available?
+#[inline]
+fn has_bmi2() -> bool {
+    #[cfg(feature = "std")]
+    {
+        std::is_x86_feature_detected!("bmi2")
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(target_feature = "bmi2")
+    }
+}
+
+/// Are the `mulx`, `adcx` and `adox` instructions available?
+#[inline]
+fn has_bmi2_adx() -> bool {
+    #[cfg(feature = "std")]
+    {
+        std::is_x86_feature_detected!("bmi2") && std::is_x86_feature_detected!("adx")
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(all(target_feature = "bmi2", target_feature = "adx"))
+    }
+}
+
+/// Multiply a word sequence by a `Word` in place with carry in.
+///
+/// Returns carry.
+pub(crate) fn mul_word_in_place_with_carry(words: &mut [Word], rhs: Word, carry: Word) -> Word {
+    if words.len() < MIN_LEN || !has_bmi2() {
+        return generic::mul_word_in_place_with_carry(words, rhs, carry);
+    }
+    let (head, tail) = words.split_at_mut(words.len() % UNROLL);
+    let carry = generic::mul_word_in_place_with_carry(head, rhs, carry);
+    // SAFETY: BMI2 is available and tail.len() is a multiple of UNROLL.
+    unsafe { mul_word_in_place_with_carry_bmi2(tail, rhs, carry) }
+}
+
+/// words += mult * rhs
+///
+/// Returns carry.
+pub(crate) fn add_mul_word_same_len_in_place(words: &mut [Word], mult: Word, rhs: &[Word]) -> Word {
+    debug_assert!(words.len() == rhs.len());
+    if words.len() < MIN_LEN || !has_bmi2_adx() {
+        return generic::add_mul_word_same_len_in_place(words, mult, rhs);
+    }
+    let split = words.len() % UNROLL;
+    let (words_head, words_tail) = words.split_at_mut(split);
+    let (rhs_head, rhs_tail) = rhs.split_at(split);
+    let carry = generic::add_mul_word_same_len_in_place(words_head, mult, rhs_head);
+    // SAFETY: BMI2 and ADX are available, and the tails have the same length, a multiple of
+    // UNROLL.
+    unsafe { add_mul_word_same_len_in_place_with_carry_adx(words_tail, mult, rhs_tail, carry) }
+}
+
+/// Multiply a word sequence by a `Word` in place with carry in.
+///
+/// Returns carry.
+///
+/// # Safety
+///
+/// The CPU must support BMI2. words.len() must be a multiple of UNROLL.
+unsafe fn mul_word_in_place_with_carry_bmi2(words: &mut [Word], rhs: Word, carry: Word) -> Word {
+    debug_assert_eq!(words.len() % UNROLL, 0);
+    let len = words.len();
+    let mut carry = carry;
+    // rcx counts from -len up to 0. `lea` and `jrcxz` preserve the carry flag.
+    asm!(
+        "clc",
+        "2:",
+        "jrcxz 3f",
+        "mulx {hi}, rax, [{words} + 8*rcx]",
+        "adc rax, {carry}",
+        "mov [{words} + 8*rcx], rax",
+        "mulx {carry}, rax, [{words} + 8*rcx + 8]",
+        "adc rax, {hi}",
+        "mov [{words} + 8*rcx + 8], rax",
+        "mulx {hi}, rax, [{words} + 8*rcx + 16]",
+        "adc rax, {carry}",
+        "mov [{words} + 8*rcx + 16], rax",
+        "mulx {carry}, rax, [{words} + 8*rcx + 24]",
+        "adc rax, {hi}",
+        "mov [{words} + 8*rcx + 24], rax",
+        "lea rcx, [rcx + 4]",
+        "jmp 2b",
+        "3:",
+        "adc {carry}, 0",
+        words = in(reg) words.as_mut_ptr().add(len),
+        carry = inout(reg) carry,
+        hi = out(reg) _,
+        inout("rcx") len.wrapping_neg() => _,
+        in("rdx") rhs,
+        out("rax") _,
+        options(nostack),
+    );
+    carry
+}
+
+/// words += mult * rhs + carry
+///
+/// Returns carry.
+///
+/// # Safety
+///
+/// The CPU must support BMI2 and ADX. words and rhs must have the same length, a multiple of
+/// UNROLL.
+unsafe fn add_mul_word_same_len_in_place_with_carry_adx(
+    words: &mut [Word],
+    mult: Word,
+    rhs: &[Word],
+    carry: Word,
+) -> Word {
+    debug_assert!(words.len() == rhs.len());
+    debug_assert_eq!(words.len() % UNROLL, 0);
+    let len = words.len();
+    let mut carry = carry;
+    // The carry flag chains the high halves of the products, the overflow flag chains the
+    // additions to words. rcx counts from -len up to 0. `lea` and `jrcxz` preserve both flags.
+    asm!(
+        "xor eax, eax",
+        "2:",
+        "jrcxz 3f",
+        "mulx {hi}, rax, [{rhs} + 8*rcx]",
+        "adcx rax, {carry}",
+        "adox rax, [{words} + 8*rcx]",
+        "mov [{words} + 8*rcx], rax",
+        "mulx {carry}, rax, [{rhs} + 8*rcx + 8]",
+        "adcx rax, {hi}",
+        "adox rax, [{words} + 8*rcx + 8]",
+        "mov [{words} + 8*rcx + 8], rax",
+        "mulx {hi}, rax, [{rhs} + 8*rcx + 16]",
+        "adcx rax, {carry}",
+        "adox rax, [{words} + 8*rcx + 16]",
+        "mov [{words} + 8*rcx + 16], rax",
+        "mulx {carry}, rax, [{rhs} + 8*rcx + 24]",
+        "adcx rax, {hi}",
+        "adox rax, [{words} + 8*rcx + 24]",
+        "mov [{words} + 8*rcx + 24], rax",
+        "lea rcx, [rcx + 4]",
+        "jmp 2b",
+        "3:",
+        // The result fits in len + 1 words, so this doesn't overflow.
+        "mov eax, 0",
+        "adcx {carry}, rax",
+        "adox {carry}, rax",
+        words = in(reg) words.as_mut_ptr().add(len),
+        rhs = in(reg) rhs.as_ptr().add(len),
+        carry = inout(reg) carry,
+        hi = out(reg) _,
+        inout("rcx") len.wrapping_neg() => _,
+        in("rdx") mult,
+        out("rax") _,
+        options(nostack),
+    );
+    carry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mul::testing;
+    use alloc::{vec, vec::Vec};
+
+    /// Report a kernel test that can't run on this CPU.
+    ///
+    /// CI sets `IBIG_TEST_BMI2_ADX` on runners with BMI2 and ADX, so that the tests can't pass
+    /// there without running the kernels.
+    fn skip(kernel: &str) {
+        assert!(
+            option_env!("IBIG_TEST_BMI2_ADX").is_none(),
+            "{}: IBIG_TEST_BMI2_ADX is set, but the CPU lacks BMI2 or ADX",
+            kernel
+        );
+        #[cfg(feature = "std")]
+        std::eprintln!("skipped {}: the CPU lacks BMI2 or ADX", kernel);
+    }
+
+    /// Pseudo-random words, all ones for the largest carries, and zeros.
+    fn test_words(n: usize, seed: Word) -> Vec<Vec<Word>> {
+        let mut words = testing::test_factors(n, seed);
+        words.push(vec![0; n]);
+        words
+    }
+
+    #[test]
+    fn test_mul_word_in_place_with_carry() {
+        if !has_bmi2() {
+            skip("mul_word_in_place_with_carry");
+            return;
+        }
+        for n in 0..20 {
+            for words in &test_words(n, 1) {
+                for (rhs, carry) in &[(0, 0), (3, 5), (Word::MAX, Word::MAX), (0x1234567, 0)] {
+                    let mut expected = words.clone();
+                    let expected_carry =
+                        generic::mul_word_in_place_with_carry(&mut expected, *rhs, *carry);
+                    let mut result = words.clone();
+                    let result_carry = mul_word_in_place_with_carry(&mut result, *rhs, *carry);
+                    assert_eq!(
+                        (result, result_carry),
+                        (expected, expected_carry),
+                        "n={}",
+                        n
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_mul_word_same_len_in_place() {
+        if !has_bmi2_adx() {
+            skip("add_mul_word_same_len_in_place");
+            return;
+        }
+        for n in 0..20 {
+            for words in &test_words(n, 1) {
+                for rhs in &test_words(n, 2) {
+                    for mult in &[0, 1, 0x1234567, Word::MAX] {
+                        let mut expected = words.clone();
+                        let expected_carry =
+                            generic::add_mul_word_same_len_in_place(&mut expected, *mult, rhs);
+                        let mut result = words.clone();
+                        let result_carry = add_mul_word_same_len_in_place(&mut result, *mult, rhs);
+                        assert_eq!(
+                            (result, result_carry),
+                            (expected, expected_carry),
+                            "n={}",
+                            n
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
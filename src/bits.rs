@@ -10,10 +10,7 @@ use crate::{
     sign::Sign::*,
     ubig::{Repr::*, UBig},
 };
-use core::{
-    mem,
-    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not},
-};
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
 
 impl UBig {
     /// Returns true if the `n`-th bit is set.
@@ -49,16 +46,16 @@ impl UBig {
     /// assert_eq!(a, ubig!(0b10000000101));
     /// ```
     pub fn set_bit(&mut self, n: usize) {
-        match mem::take(self).into_repr() {
+        self.update(|x| match x.into_repr() {
             Small(word) => {
                 if n < WORD_BITS_USIZE {
-                    *self = UBig::from_word(word | 1 << n)
+                    UBig::from_word(word | 1 << n)
                 } else {
-                    *self = UBig::with_bit_word_slow(word, n)
+                    UBig::with_bit_word_slow(word, n)
                 }
             }
-            Large(buffer) => *self = UBig::with_bit_large(buffer, n),
-        }
+            Large(buffer) => UBig::with_bit_large(buffer, n),
+        });
     }
 
     fn with_bit_word_slow(word: Word, n: usize) -> UBig {
@@ -94,14 +91,16 @@ impl UBig {
     /// assert_eq!(a, ubig!(0b100));
     /// ```
     pub fn clear_bit(&mut self, n: usize) {
-        match mem::take(self).into_repr() {
+        self.update(|x| match x.into_repr() {
             Small(word) => {
                 if n < WORD_BITS_USIZE {
-                    *self = UBig::from_word(word & !(1 << n))
+                    UBig::from_word(word & !(1 << n))
+                } else {
+                    UBig::from_word(word)
                 }
             }
-            Large(buffer) => *self = UBig::without_bit_large(buffer, n),
-        }
+            Large(buffer) => UBig::without_bit_large(buffer, n),
+        });
     }
 
     fn without_bit_large(mut buffer: Buffer, n: usize) -> UBig {
@@ -135,7 +134,7 @@ impl UBig {
         }
     }
 
-    fn trailing_zeros_large(words: &[Word]) -> usize {
+    pub(crate) fn trailing_zeros_large(words: &[Word]) -> usize {
         debug_assert!(*words.last().unwrap() != 0);
 
         for (idx, word) in words.iter().enumerate() {
@@ -195,7 +194,7 @@ impl UBig {
         }
     }
 
-    fn is_power_of_two_large(words: &[Word]) -> bool {
+    pub(crate) fn is_power_of_two_large(words: &[Word]) -> bool {
         debug_assert!(*words.last().unwrap() != 0);
 
         words[..words.len() - 1].iter().all(|x| *x == 0) && words.last().unwrap().is_power_of_two()
@@ -345,13 +344,13 @@ impl BitAnd<&UBig> for &UBig {
 
 impl BitAndAssign<UBig> for UBig {
     fn bitand_assign(&mut self, rhs: UBig) {
-        *self = mem::take(self) & rhs;
+        self.update(|x| x & rhs);
     }
 }
 
 impl BitAndAssign<&UBig> for UBig {
     fn bitand_assign(&mut self, rhs: &UBig) {
-        *self = mem::take(self) & rhs;
+        self.update(|x| x & rhs);
     }
 }
 
@@ -432,13 +431,13 @@ impl BitOr<&UBig> for &UBig {
 
 impl BitOrAssign<UBig> for UBig {
     fn bitor_assign(&mut self, rhs: UBig) {
-        *self = mem::take(self) | rhs;
+        self.update(|x| x | rhs);
     }
 }
 
 impl BitOrAssign<&UBig> for UBig {
     fn bitor_assign(&mut self, rhs: &UBig) {
-        *self = mem::take(self) | rhs;
+        self.update(|x| x | rhs);
     }
 }
 
@@ -456,7 +455,7 @@ impl UBig {
         }
         if rhs.len() > buffer.len() {
             buffer.ensure_capacity(rhs.len());
-            buffer.extend(&rhs[buffer.len()..]);
+            buffer.extend_from_slice(&rhs[buffer.len()..]);
         }
         buffer.into()
     }
@@ -527,13 +526,13 @@ impl BitXor<&UBig> for &UBig {
 
 impl BitXorAssign<UBig> for UBig {
     fn bitxor_assign(&mut self, rhs: UBig) {
-        *self = mem::take(self) ^ rhs;
+        self.update(|x| x ^ rhs);
     }
 }
 
 impl BitXorAssign<&UBig> for UBig {
     fn bitxor_assign(&mut self, rhs: &UBig) {
-        *self = mem::take(self) ^ rhs;
+        self.update(|x| x ^ rhs);
     }
 }
 
@@ -551,7 +550,7 @@ impl UBig {
         }
         if rhs.len() > buffer.len() {
             buffer.ensure_capacity(rhs.len());
-            buffer.extend(&rhs[buffer.len()..]);
+            buffer.extend_from_slice(&rhs[buffer.len()..]);
         }
         buffer.into()
     }
@@ -698,13 +697,13 @@ impl BitAnd<&IBig> for &IBig {
 
 impl BitAndAssign<IBig> for IBig {
     fn bitand_assign(&mut self, rhs: IBig) {
-        *self = mem::take(self) & rhs;
+        self.update(|x| x & rhs);
     }
 }
 
 impl BitAndAssign<&IBig> for IBig {
     fn bitand_assign(&mut self, rhs: &IBig) {
-        *self = mem::take(self) & rhs;
+        self.update(|x| x & rhs);
     }
 }
 
@@ -757,13 +756,13 @@ impl BitOr<&IBig> for &IBig {
 
 impl BitOrAssign<IBig> for IBig {
     fn bitor_assign(&mut self, rhs: IBig) {
-        *self = mem::take(self) | rhs;
+        self.update(|x| x | rhs);
     }
 }
 
 impl BitOrAssign<&IBig> for IBig {
     fn bitor_assign(&mut self, rhs: &IBig) {
-        *self = mem::take(self) | rhs;
+        self.update(|x| x | rhs);
     }
 }
 
@@ -816,13 +815,13 @@ impl BitXor<&IBig> for &IBig {
 
 impl BitXorAssign<IBig> for IBig {
     fn bitxor_assign(&mut self, rhs: IBig) {
-        *self = mem::take(self) ^ rhs;
+        self.update(|x| x ^ rhs);
     }
 }
 
 impl BitXorAssign<&IBig> for IBig {
     fn bitxor_assign(&mut self, rhs: &IBig) {
-        *self = mem::take(self) ^ rhs;
+        self.update(|x| x ^ rhs);
     }
 }
 
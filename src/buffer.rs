@@ -1,31 +1,119 @@
 //! Word buffer.
 
-use crate::{arch::word::Word, ubig::UBig};
+use crate::{
+    allocator::{WordAllocator, WordVec},
+    arch::word::Word,
+    error::{AllocError, CapacityError},
+    ubig::UBig,
+};
 
-use alloc::vec::Vec;
 use core::{
+    fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
     iter,
     ops::{Deref, DerefMut},
 };
 
+/// Number of `Word`s stored inline, without a heap allocation.
+///
+/// Chosen with the `inline-2` and `inline-4` features. 0 means everything is on the heap.
+#[cfg(any(feature = "inline-2", feature = "inline-4", test))]
+pub(crate) const INLINE_CAPACITY: usize = if cfg!(feature = "inline-4") {
+    4
+} else if cfg!(feature = "inline-2") {
+    2
+} else {
+    0
+};
+
+/// Buffer for Words.
+///
+/// UBig operations are usually performed by creating a Buffer with appropriate capacity, filling it
+/// in with Words, and then converting to UBig.
+///
+/// If its capacity is exceeded, the `Buffer` will panic.
+#[cfg(not(any(feature = "inline-2", feature = "inline-4")))]
+pub(crate) struct Buffer(WordVec);
+
 /// Buffer for Words.
 ///
 /// UBig operations are usually performed by creating a Buffer with appropriate capacity, filling it
 /// in with Words, and then converting to UBig.
 ///
+/// Buffers of up to `INLINE_CAPACITY` words are stored inline.
+///
 /// If its capacity is exceeded, the `Buffer` will panic.
-#[derive(Debug, Eq, Hash, PartialEq)]
-pub(crate) struct Buffer(Vec<Word>);
+#[cfg(any(feature = "inline-2", feature = "inline-4"))]
+pub(crate) struct Buffer(Words);
+
+/// Storage of a `Buffer`.
+#[cfg(any(feature = "inline-2", feature = "inline-4"))]
+enum Words {
+    /// Words stored inline, with capacity `INLINE_CAPACITY`.
+    Inline {
+        len: usize,
+        words: [Word; INLINE_CAPACITY],
+        /// The allocator to use if the buffer grows.
+        allocator: WordAllocator,
+    },
+    /// Words stored on the heap.
+    Heap(WordVec),
+}
 
 impl Buffer {
     /// Creates a `Buffer` with at least specified capacity.
     ///
     /// It leaves some extra space for future growth.
     pub(crate) fn allocate(num_words: usize) -> Buffer {
+        Buffer::allocate_in(num_words, WordAllocator::global())
+    }
+
+    /// Creates a `Buffer` with at least specified capacity in memory from `allocator`.
+    pub(crate) fn allocate_in(num_words: usize, allocator: WordAllocator) -> Buffer {
         if num_words > Buffer::MAX_CAPACITY {
             UBig::panic_number_too_large();
         }
-        Buffer(Vec::with_capacity(Buffer::default_capacity(num_words)))
+        #[cfg(any(feature = "inline-2", feature = "inline-4"))]
+        {
+            if num_words <= INLINE_CAPACITY {
+                return Buffer::inline(allocator);
+            }
+        }
+        Buffer::heap(allocator.vec_with_capacity(Buffer::default_capacity(num_words)))
+    }
+
+    /// Creates a `Buffer` with at least specified capacity, or fails if it is too large or the
+    /// allocator can't provide the memory.
+    pub(crate) fn try_allocate(num_words: usize) -> Result<Buffer, AllocError> {
+        Buffer::try_allocate_in(num_words, WordAllocator::global())
+    }
+
+    /// Creates a `Buffer` with at least specified capacity in memory from `allocator`, or fails if
+    /// it is too large or the allocator can't provide the memory.
+    pub(crate) fn try_allocate_in(
+        num_words: usize,
+        allocator: WordAllocator,
+    ) -> Result<Buffer, AllocError> {
+        if num_words > Buffer::MAX_CAPACITY {
+            return Err(CapacityError.into());
+        }
+        #[cfg(any(feature = "inline-2", feature = "inline-4"))]
+        {
+            if num_words <= INLINE_CAPACITY {
+                return Ok(Buffer::inline(allocator));
+            }
+        }
+        let vec = allocator
+            .try_vec_with_capacity(Buffer::default_capacity(num_words))
+            .ok_or(AllocError::OutOfMemory)?;
+        Ok(Buffer::heap(vec))
+    }
+
+    /// Creates a `Buffer` containing a copy of `words`, sized as `Buffer::allocate(words.len())`.
+    pub(crate) fn from_words(words: &[Word]) -> Buffer {
+        let mut buffer = Buffer::allocate(words.len());
+        buffer.extend_from_slice(words);
+        buffer
     }
 
     /// Ensure there is enough capacity in the buffer for `num_words`. Will reallocate if there is
@@ -43,6 +131,23 @@ impl Buffer {
         }
     }
 
+    /// Makes sure that the capacity is compact, or fails if memory can't be allocated.
+    pub(crate) fn try_shrink(&mut self) -> Result<(), AllocError> {
+        if self.capacity() > Buffer::max_compact_capacity(self.len()) {
+            let mut new_buffer = Buffer::try_allocate_in(self.len(), self.allocator().clone())?;
+            new_buffer.clone_from(self);
+            *self = new_buffer;
+        }
+        Ok(())
+    }
+
+    /// Clone, or fail if memory can't be allocated.
+    pub(crate) fn try_clone(&self) -> Result<Buffer, AllocError> {
+        let mut new_buffer = Buffer::try_allocate_in(self.len(), self.allocator().clone())?;
+        new_buffer.clone_from(self);
+        Ok(new_buffer)
+    }
+
     /// Change capacity to store `num_words` plus some extra space for future growth.
     ///
     /// # Panics
@@ -50,11 +155,101 @@ impl Buffer {
     /// Panics if `num_words < len()`.
     fn reallocate(&mut self, num_words: usize) {
         assert!(num_words >= self.len());
-        let mut new_buffer = Buffer::allocate(num_words);
+        let mut new_buffer = Buffer::allocate_in(num_words, self.allocator().clone());
         new_buffer.clone_from(self);
         *self = new_buffer
     }
 
+    /// Append a Word and reallocate if necessary.
+    pub(crate) fn push_may_reallocate(&mut self, word: Word) {
+        self.ensure_capacity(self.len() + 1);
+        self.push(word);
+    }
+
+    /// Pop leading zero words.
+    pub(crate) fn pop_leading_zeros(&mut self) {
+        while let Some(0) = self.last() {
+            self.pop();
+        }
+    }
+
+    /// Clone from `other` and resize if necessary, keeping the allocator of `self`.
+    ///
+    /// Equivalent to, but more efficient than:
+    ///
+    /// ```ignore
+    /// buffer.ensure_capacity(source.len());
+    /// buffer.clone_from(source);
+    /// buffer.shrink();
+    /// ```
+    pub(crate) fn resizing_clone_from(&mut self, source: &Buffer) {
+        let cap = self.capacity();
+        let n = source.len();
+        if cap >= n && cap <= Buffer::max_compact_capacity(n) {
+            self.clone_from(&source);
+        } else {
+            *self = Buffer::allocate_in(n, self.allocator().clone());
+            self.clone_from(source);
+        }
+    }
+
+    /// Clear the buffer for reuse with `num_words` words.
+    ///
+    /// Keeps a larger capacity than needed, so that a number that is the destination of repeated
+    /// operations keeps its memory when the results get shorter. Allocates a new buffer from the
+    /// same allocator instead if the capacity is too small.
+    pub(crate) fn reuse(mut self, num_words: usize) -> Buffer {
+        if self.capacity() >= num_words {
+            self.truncate(0);
+            self
+        } else {
+            let allocator = self.allocator().clone();
+            drop(self);
+            Buffer::allocate_in(num_words, allocator)
+        }
+    }
+
+    /// Maximum number of `Word`s.
+    ///
+    /// We allow 1 extra word beyond `UBig::MAX_LEN` to allow a leading zero
+    /// in operations.
+    pub(crate) const MAX_CAPACITY: usize = UBig::MAX_LEN + 1;
+
+    /// Default capacity for a given number of `Word`s.
+    /// It should be between `num_words` and `max_capacity(num_words).
+    ///
+    /// Requires that `num_words <= MAX_CAPACITY`.
+    ///
+    /// Provides `2 + 0.125 * num_words` extra space.
+    fn default_capacity(num_words: usize) -> usize {
+        debug_assert!(num_words <= Buffer::MAX_CAPACITY);
+        (num_words + num_words / 8 + 2).min(Buffer::MAX_CAPACITY)
+    }
+
+    /// Maximum compact capacity for a given number of `Word`s.
+    ///
+    /// Requires that `num_words <= Buffer::MAX_CAPACITY`.
+    ///
+    /// Allows `4 + 0.25 * num_words` overhead.
+    fn max_compact_capacity(num_words: usize) -> usize {
+        debug_assert!(num_words <= Buffer::MAX_CAPACITY);
+        (num_words + num_words / 4 + 4).min(Buffer::MAX_CAPACITY)
+    }
+}
+
+/// Operations on heap storage.
+#[cfg(not(any(feature = "inline-2", feature = "inline-4")))]
+impl Buffer {
+    /// Buffer stored in `vec`.
+    fn heap(vec: WordVec) -> Buffer {
+        Buffer(vec)
+    }
+
+    /// The allocator of the buffer.
+    pub(crate) fn allocator(&self) -> &WordAllocator {
+        WordAllocator::of(&self.0)
+    }
+
     /// Return buffer capacity.
     pub(crate) fn capacity(&self) -> usize {
         self.0.capacity()
@@ -70,12 +265,6 @@ impl Buffer {
         self.0.push(word);
     }
 
-    /// Append a Word and reallocate if necessary.
-    pub(crate) fn push_may_reallocate(&mut self, word: Word) {
-        self.ensure_capacity(self.len() + 1);
-        self.push(word);
-    }
-
     /// Append `n` zeros.
     ///
     /// # Panics
@@ -96,94 +285,223 @@ impl Buffer {
         self.0.splice(..0, iter::repeat(0).take(n));
     }
 
+    /// Append a copy of `words`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not enough capacity.
+    pub(crate) fn extend_from_slice(&mut self, words: &[Word]) {
+        assert!(words.len() <= self.capacity() - self.len());
+        self.0.extend_from_slice(words);
+    }
+
     /// Pop the most significant `Word`.
     pub(crate) fn pop(&mut self) -> Option<Word> {
         self.0.pop()
     }
 
-    /// Pop leading zero words.
-    pub(crate) fn pop_leading_zeros(&mut self) {
-        while let Some(0) = self.last() {
-            self.pop();
-        }
-    }
-
     /// Truncate length to `len`.
     pub(crate) fn truncate(&mut self, len: usize) {
         assert!(self.len() >= len);
-
         self.0.truncate(len);
     }
 
     /// Erase first n elements.
     pub(crate) fn erase_front(&mut self, n: usize) {
         assert!(self.len() >= n);
-
         self.0.drain(..n);
     }
+}
+
+/// Operations on inline or heap storage.
+#[cfg(any(feature = "inline-2", feature = "inline-4"))]
+impl Buffer {
+    /// Buffer stored in `vec`.
+    fn heap(vec: WordVec) -> Buffer {
+        Buffer(Words::Heap(vec))
+    }
+
+    /// Empty inline buffer.
+    fn inline(allocator: WordAllocator) -> Buffer {
+        Buffer(Words::Inline {
+            len: 0,
+            words: [0; INLINE_CAPACITY],
+            allocator,
+        })
+    }
 
-    /// Clone from `other` and resize if necessary.
+    /// The allocator of the buffer.
+    pub(crate) fn allocator(&self) -> &WordAllocator {
+        match &self.0 {
+            Words::Inline { allocator, .. } => allocator,
+            Words::Heap(vec) => WordAllocator::of(vec),
+        }
+    }
+
+    /// Return buffer capacity.
+    pub(crate) fn capacity(&self) -> usize {
+        match &self.0 {
+            Words::Inline { .. } => INLINE_CAPACITY,
+            Words::Heap(vec) => vec.capacity(),
+        }
+    }
+
+    /// Append a Word to the buffer.
     ///
-    /// Equivalent to, but more efficient than:
+    /// # Panics
     ///
-    /// ```ignore
-    /// buffer.ensure_capacity(source.len());
-    /// buffer.clone_from(source);
-    /// buffer.shrink();
-    /// ```
-    pub(crate) fn resizing_clone_from(&mut self, source: &Buffer) {
-        let cap = self.capacity();
-        let n = source.len();
-        if cap >= n && cap <= Buffer::max_compact_capacity(n) {
-            self.clone_from(&source);
-        } else {
-            *self = source.clone();
+    /// Panics if there is not enough capacity.
+    pub(crate) fn push(&mut self, word: Word) {
+        match &mut self.0 {
+            Words::Inline { len, words, .. } => {
+                // Indexing checks the capacity.
+                words[*len] = word;
+                *len += 1;
+            }
+            Words::Heap(vec) => {
+                assert!(vec.len() < vec.capacity());
+                vec.push(word);
+            }
         }
     }
 
-    /// Maximum number of `Word`s.
+    /// Append `n` zeros.
     ///
-    /// We allow 1 extra word beyond `UBig::MAX_LEN` to allow a leading zero
-    /// in operations.
-    pub(crate) const MAX_CAPACITY: usize = UBig::MAX_LEN + 1;
+    /// # Panics
+    ///
+    /// Panics if there is not enough capacity.
+    pub(crate) fn push_zeros(&mut self, n: usize) {
+        assert!(n <= self.capacity() - self.len());
+        match &mut self.0 {
+            Words::Inline { len, words, .. } => {
+                for word in words[*len..*len + n].iter_mut() {
+                    *word = 0;
+                }
+                *len += n;
+            }
+            Words::Heap(vec) => vec.extend(iter::repeat(0).take(n)),
+        }
+    }
 
-    /// Default capacity for a given number of `Word`s.
-    /// It should be between `num_words` and `max_capacity(num_words).
+    /// Insert `n` zeros in front.
     ///
-    /// Requires that `num_words <= MAX_CAPACITY`.
+    /// # Panics
     ///
-    /// Provides `2 + 0.125 * num_words` extra space.
-    fn default_capacity(num_words: usize) -> usize {
-        debug_assert!(num_words <= Buffer::MAX_CAPACITY);
-        (num_words + num_words / 8 + 2).min(Buffer::MAX_CAPACITY)
+    /// Panics if there is not enough capacity.
+    pub(crate) fn push_zeros_front(&mut self, n: usize) {
+        assert!(n <= self.capacity() - self.len());
+        match &mut self.0 {
+            Words::Inline { len, words, .. } => {
+                words.copy_within(..*len, n);
+                for word in words[..n].iter_mut() {
+                    *word = 0;
+                }
+                *len += n;
+            }
+            Words::Heap(vec) => {
+                vec.splice(..0, iter::repeat(0).take(n));
+            }
+        }
     }
 
-    /// Maximum compact capacity for a given number of `Word`s.
+    /// Append a copy of `words`.
     ///
-    /// Requires that `num_words <= Buffer::MAX_CAPACITY`.
+    /// # Panics
     ///
-    /// Allows `4 + 0.25 * num_words` overhead.
-    fn max_compact_capacity(num_words: usize) -> usize {
-        debug_assert!(num_words <= Buffer::MAX_CAPACITY);
-        (num_words + num_words / 4 + 4).min(Buffer::MAX_CAPACITY)
+    /// Panics if there is not enough capacity.
+    pub(crate) fn extend_from_slice(&mut self, words: &[Word]) {
+        match &mut self.0 {
+            Words::Inline {
+                len,
+                words: inline_words,
+                ..
+            } => {
+                inline_words[*len..*len + words.len()].copy_from_slice(words);
+                *len += words.len();
+            }
+            Words::Heap(vec) => {
+                assert!(words.len() <= vec.capacity() - vec.len());
+                vec.extend_from_slice(words);
+            }
+        }
+    }
+
+    /// Pop the most significant `Word`.
+    pub(crate) fn pop(&mut self) -> Option<Word> {
+        match &mut self.0 {
+            Words::Inline { len: 0, .. } => None,
+            Words::Inline { len, words, .. } => {
+                *len -= 1;
+                Some(words[*len])
+            }
+            Words::Heap(vec) => vec.pop(),
+        }
+    }
+
+    /// Truncate length to `len`.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        assert!(self.len() >= len);
+
+        match &mut self.0 {
+            Words::Inline {
+                len: inline_len, ..
+            } => *inline_len = len,
+            Words::Heap(vec) => vec.truncate(len),
+        }
+    }
+
+    /// Erase first n elements.
+    pub(crate) fn erase_front(&mut self, n: usize) {
+        assert!(self.len() >= n);
+
+        match &mut self.0 {
+            Words::Inline { len, words, .. } => {
+                words.copy_within(n..*len, 0);
+                *len -= n;
+            }
+            Words::Heap(vec) => {
+                vec.drain(..n);
+            }
+        }
     }
 }
 
 impl Clone for Buffer {
-    /// New buffer will be sized as `Buffer::allocate(self.len())`.
+    /// New buffer will be sized as `Buffer::allocate(self.len())`, in the same allocator.
     fn clone(&self) -> Buffer {
-        let mut new_buffer = Buffer::allocate(self.len());
+        let mut new_buffer = Buffer::allocate_in(self.len(), self.allocator().clone());
         new_buffer.clone_from(self);
         new_buffer
     }
 
     /// If capacity is exceeded, panic.
     fn clone_from(&mut self, source: &Buffer) {
-        assert!(self.capacity() >= source.len());
-        self.0.clone_from(&source.0);
+        self.truncate(0);
+        self.extend_from_slice(source);
+    }
+}
+
+impl Debug for Buffer {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Buffer").field(&&**self).finish()
     }
 }
 
+impl PartialEq for Buffer {
+    fn eq(&self, other: &Buffer) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Buffer {}
+
+impl Hash for Buffer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+#[cfg(not(any(feature = "inline-2", feature = "inline-4")))]
 impl Deref for Buffer {
     type Target = [Word];
 
@@ -192,30 +510,42 @@ impl Deref for Buffer {
     }
 }
 
+#[cfg(not(any(feature = "inline-2", feature = "inline-4")))]
 impl DerefMut for Buffer {
     fn deref_mut(&mut self) -> &mut [Word] {
         &mut self.0
     }
 }
 
-impl Extend<Word> for Buffer {
-    fn extend<T>(&mut self, iter: T)
-    where
-        T: IntoIterator<Item = Word>,
-    {
-        for word in iter {
-            self.push(word);
+#[cfg(any(feature = "inline-2", feature = "inline-4"))]
+impl Deref for Buffer {
+    type Target = [Word];
+
+    fn deref(&self) -> &[Word] {
+        match &self.0 {
+            Words::Inline { len, words, .. } => &words[..*len],
+            Words::Heap(vec) => vec,
         }
     }
 }
 
-impl<'a> Extend<&'a Word> for Buffer {
+#[cfg(any(feature = "inline-2", feature = "inline-4"))]
+impl DerefMut for Buffer {
+    fn deref_mut(&mut self) -> &mut [Word] {
+        match &mut self.0 {
+            Words::Inline { len, words, .. } => &mut words[..*len],
+            Words::Heap(vec) => vec,
+        }
+    }
+}
+
+impl Extend<Word> for Buffer {
     fn extend<T>(&mut self, iter: T)
     where
-        T: IntoIterator<Item = &'a Word>,
+        T: IntoIterator<Item = Word>,
     {
         for word in iter {
-            self.push(*word);
+            self.push(word);
         }
     }
 }
@@ -224,6 +554,17 @@ impl<'a> Extend<&'a Word> for Buffer {
 mod tests {
     use super::*;
 
+    /// Capacity of `Buffer::allocate(num_words)`.
+    // INLINE_CAPACITY is 0 without the inline features.
+    #[allow(clippy::absurd_extreme_comparisons)]
+    fn expected_capacity(num_words: usize) -> usize {
+        if INLINE_CAPACITY != 0 && num_words <= INLINE_CAPACITY {
+            INLINE_CAPACITY
+        } else {
+            Buffer::default_capacity(num_words)
+        }
+    }
+
     #[test]
     fn test_default_capacity() {
         assert_eq!(Buffer::default_capacity(2), 4);
@@ -249,15 +590,26 @@ mod tests {
         let _ = Buffer::allocate(Buffer::MAX_CAPACITY + 1);
     }
 
+    #[test]
+    fn test_try_allocate() {
+        let buffer = Buffer::try_allocate(1000).unwrap();
+        assert!(buffer.capacity() >= Buffer::default_capacity(1000));
+        assert_eq!(
+            Buffer::try_allocate(Buffer::MAX_CAPACITY + 1),
+            Err(AllocError::Capacity(CapacityError))
+        );
+    }
+
     #[test]
     fn test_ensure_capacity() {
         let mut buffer = Buffer::allocate(2);
         buffer.push(7);
-        assert_eq!(buffer.capacity(), 4);
-        buffer.ensure_capacity(4);
-        assert_eq!(buffer.capacity(), 4);
-        buffer.ensure_capacity(5);
-        assert_eq!(buffer.capacity(), 7);
+        let capacity = expected_capacity(2);
+        assert_eq!(buffer.capacity(), capacity);
+        buffer.ensure_capacity(capacity);
+        assert_eq!(buffer.capacity(), capacity);
+        buffer.ensure_capacity(capacity + 1);
+        assert_eq!(buffer.capacity(), expected_capacity(capacity + 1));
         assert_eq!(&buffer[..], [7]);
     }
 
@@ -267,7 +619,7 @@ mod tests {
         buffer.push(7);
         buffer.push(8);
         buffer.shrink();
-        assert_eq!(buffer.capacity(), Buffer::default_capacity(2));
+        assert_eq!(buffer.capacity(), expected_capacity(2));
         assert_eq!(&buffer[..], [7, 8]);
     }
 
@@ -298,8 +650,17 @@ mod tests {
         let mut buffer = Buffer::allocate(5);
         buffer.push(1);
         let list: [Word; 2] = [2, 3];
-        buffer.extend(&list);
+        buffer.extend_from_slice(&list);
         assert_eq!(&buffer[..], [1, 2, 3]);
+        buffer.extend(list.iter().map(|word| word + 2));
+        assert_eq!(&buffer[..], [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extend_failed() {
+        let mut buffer = Buffer::allocate(2);
+        buffer.extend_from_slice(&[7; 10]);
     }
 
     #[test]
@@ -363,7 +724,7 @@ mod tests {
         buffer.push(8);
         let buffer2 = buffer.clone();
         assert_eq!(buffer, buffer2);
-        assert_eq!(buffer2.capacity(), Buffer::default_capacity(2));
+        assert_eq!(buffer2.capacity(), expected_capacity(2));
     }
 
     #[test]
@@ -377,13 +738,57 @@ mod tests {
         assert_eq!(buffer2.capacity(), Buffer::default_capacity(50));
     }
 
+    #[test]
+    fn test_inline() {
+        let mut buffer = Buffer::allocate(2);
+        if INLINE_CAPACITY != 0 {
+            assert_eq!(buffer.capacity(), INLINE_CAPACITY);
+        }
+        buffer.push(1);
+        buffer.push_zeros_front(1);
+        assert_eq!(&buffer[..], [0, 1]);
+        buffer.erase_front(1);
+        assert_eq!(&buffer[..], [1]);
+        buffer.push_zeros(1);
+        assert_eq!(buffer.pop(), Some(0));
+        for i in 0..10 {
+            buffer.push_may_reallocate(i);
+        }
+        assert_eq!(buffer.len(), 11);
+        assert_eq!(buffer[10], 9);
+        let mut buffer2 = Buffer::allocate(2);
+        buffer2.push(5);
+        buffer2.push(6);
+        buffer2.clone_from(&buffer2.clone());
+        assert_eq!(&buffer2[..], [5, 6]);
+        buffer2.truncate(1);
+        assert_eq!(buffer2.pop(), Some(5));
+        assert_eq!(buffer2.pop(), None);
+    }
+
+    #[test]
+    fn test_reuse() {
+        let mut buffer = Buffer::allocate(100);
+        buffer.push(7);
+        let cap = buffer.capacity();
+        let buffer = buffer.reuse(100);
+        assert_eq!(buffer.len(), 0);
+        assert_eq!(buffer.capacity(), cap);
+        let buffer = buffer.reuse(cap + 1);
+        assert!(buffer.capacity() > cap);
+        let cap = buffer.capacity();
+        let buffer = buffer.reuse(10);
+        assert_eq!(buffer.len(), 0);
+        assert_eq!(buffer.capacity(), cap);
+    }
+
     #[test]
     fn test_resizing_clone_from() {
         let mut buf = Buffer::allocate(5);
         assert_eq!(buf.capacity(), 7);
 
         let mut buf2 = Buffer::allocate(4);
-        assert_eq!(buf2.capacity(), 6);
+        assert_eq!(buf2.capacity(), expected_capacity(4));
         for i in 0..4 {
             buf2.push(i);
         }
@@ -400,7 +805,74 @@ mod tests {
         assert_eq!(buf.len(), 100);
 
         buf.resizing_clone_from(&buf2);
-        assert_eq!(buf.capacity(), 6);
+        assert_eq!(buf.capacity(), expected_capacity(4));
         assert_eq!(&buf[..], [0, 1, 2, 3]);
     }
+
+    #[test]
+    fn test_inline_heap_push() {
+        let mut buffer = Buffer::allocate(1);
+        let capacity = expected_capacity(1);
+        assert_eq!(buffer.capacity(), capacity);
+        for i in 0..capacity {
+            buffer.push(i as Word);
+        }
+        assert_eq!(buffer.capacity(), capacity);
+        buffer.push_may_reallocate(100);
+        assert_eq!(buffer.capacity(), Buffer::default_capacity(capacity + 1));
+        assert_eq!(buffer.len(), capacity + 1);
+        assert!(buffer[..capacity].iter().copied().eq(0..capacity as Word));
+        assert_eq!(buffer[capacity], 100);
+    }
+
+    #[test]
+    fn test_inline_heap_ensure_capacity() {
+        for n in 0..=INLINE_CAPACITY + 1 {
+            let mut buffer = Buffer::allocate(n);
+            buffer.push_may_reallocate(7);
+            let capacity = expected_capacity(n);
+            buffer.ensure_capacity(capacity);
+            assert_eq!(buffer.capacity(), capacity);
+            buffer.ensure_capacity(capacity + 1);
+            assert_eq!(buffer.capacity(), Buffer::default_capacity(capacity + 1));
+            assert_eq!(&buffer[..], [7]);
+        }
+    }
+
+    #[test]
+    fn test_inline_heap_shrink() {
+        for n in 0..=INLINE_CAPACITY + 1 {
+            let mut buffer = Buffer::allocate(100);
+            for i in 0..n {
+                buffer.push(i as Word);
+            }
+            buffer.shrink();
+            assert_eq!(buffer.capacity(), expected_capacity(n));
+            assert!(buffer.iter().copied().eq(0..n as Word));
+            buffer.try_shrink().unwrap();
+            assert_eq!(buffer.capacity(), expected_capacity(n));
+
+            // Growing back onto the heap keeps the words.
+            buffer.ensure_capacity(10);
+            assert_eq!(buffer.capacity(), Buffer::default_capacity(10));
+            assert!(buffer.iter().copied().eq(0..n as Word));
+        }
+    }
+
+    #[test]
+    fn test_inline_heap_resizing_clone_from() {
+        let mut heap = Buffer::allocate(10);
+        heap.extend_from_slice(&[1; 10]);
+        for n in 0..=INLINE_CAPACITY {
+            let mut small = Buffer::allocate(n);
+            small.push_zeros(n);
+            let mut buffer = heap.clone();
+            buffer.resizing_clone_from(&small);
+            assert_eq!(buffer.capacity(), expected_capacity(n));
+            assert_eq!(buffer, small);
+            buffer.resizing_clone_from(&heap);
+            assert_eq!(buffer.capacity(), Buffer::default_capacity(10));
+            assert_eq!(buffer, heap);
+        }
+    }
 }
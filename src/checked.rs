@@ -0,0 +1,183 @@
+//! Checked, overflowing and saturating operations.
+
+use crate::{primitive::PrimitiveUnsigned, ubig::UBig};
+
+impl UBig {
+    /// Checked subtraction: `self - rhs`, or `None` if the result would be negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(5).checked_sub(&ubig!(3)), Some(ubig!(2)));
+    /// assert_eq!(ubig!(3).checked_sub(&ubig!(5)), None);
+    /// ```
+    pub fn checked_sub(&self, rhs: &UBig) -> Option<UBig> {
+        if self >= rhs {
+            Some(self - rhs)
+        } else {
+            None
+        }
+    }
+
+    /// Overflowing subtraction.
+    ///
+    /// Returns the magnitude of `self - rhs` and whether the result would be negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(5).overflowing_sub(&ubig!(3)), (ubig!(2), false));
+    /// assert_eq!(ubig!(3).overflowing_sub(&ubig!(5)), (ubig!(2), true));
+    /// ```
+    pub fn overflowing_sub(&self, rhs: &UBig) -> (UBig, bool) {
+        if self >= rhs {
+            (self - rhs, false)
+        } else {
+            (rhs - self, true)
+        }
+    }
+
+    /// Saturating subtraction: `self - rhs`, or 0 if the result would be negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(5).saturating_sub(&ubig!(3)), ubig!(2));
+    /// assert_eq!(ubig!(3).saturating_sub(&ubig!(5)), ubig!(0));
+    /// ```
+    pub fn saturating_sub(&self, rhs: &UBig) -> UBig {
+        self.checked_sub(rhs).unwrap_or_default()
+    }
+
+    /// Absolute difference: `|self - rhs|`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(5).abs_diff(&ubig!(3)), ubig!(2));
+    /// assert_eq!(ubig!(3).abs_diff(&ubig!(5)), ubig!(2));
+    /// ```
+    pub fn abs_diff(&self, rhs: &UBig) -> UBig {
+        self.overflowing_sub(rhs).0
+    }
+
+    /// Checked division: `self / rhs`, or `None` if `rhs` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(23).checked_div(&ubig!(10)), Some(ubig!(2)));
+    /// assert_eq!(ubig!(23).checked_div(&ubig!(0)), None);
+    /// ```
+    pub fn checked_div(&self, rhs: &UBig) -> Option<UBig> {
+        if *rhs == UBig::from_word(0) {
+            None
+        } else {
+            Some(self / rhs)
+        }
+    }
+
+    /// Checked remainder: `self % rhs`, or `None` if `rhs` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(23).checked_rem(&ubig!(10)), Some(ubig!(3)));
+    /// assert_eq!(ubig!(23).checked_rem(&ubig!(0)), None);
+    /// ```
+    pub fn checked_rem(&self, rhs: &UBig) -> Option<UBig> {
+        if *rhs == UBig::from_word(0) {
+            None
+        } else {
+            Some(self % rhs)
+        }
+    }
+
+    /// Checked exponentiation: `self^exp`, or `None` if the result would be longer than
+    /// [UBig::MAX_BIT_LEN].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(ubig!(3).checked_pow(3), Some(ubig!(27)));
+    /// assert_eq!(ubig!(2).checked_pow(UBig::MAX_BIT_LEN), None);
+    /// ```
+    pub fn checked_pow(&self, exp: usize) -> Option<UBig> {
+        let bits = self.bit_len();
+        if bits <= 1 {
+            return Some(self.pow(exp));
+        }
+        // The result has between (bits - 1) * exp + 1 and bits * exp bits.
+        match (bits - 1).checked_mul(exp) {
+            Some(min_bits) if min_bits < UBig::MAX_BIT_LEN => {}
+            _ => return None,
+        }
+        match bits.checked_mul(exp) {
+            Some(max_bits) if max_bits <= UBig::MAX_BIT_LEN => return Some(self.pow(exp)),
+            _ => {}
+        }
+
+        // Close to the limit. Check every product. The intermediate results are powers of self
+        // not larger than the result.
+        let mut p = usize::BIT_SIZE - 1 - exp.leading_zeros();
+        let mut res = self.clone();
+        while p > 0 {
+            p -= 1;
+            res = res.checked_mul_bits(&res)?;
+            if exp & (1 << p) != 0 {
+                res = res.checked_mul_bits(self)?;
+            }
+        }
+        Some(res)
+    }
+
+    /// Checked left shift: `self << n`, or `None` if the result would be longer than
+    /// [UBig::MAX_BIT_LEN].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(ubig!(3).checked_shl(2), Some(ubig!(12)));
+    /// assert_eq!(ubig!(3).checked_shl(UBig::MAX_BIT_LEN), None);
+    /// assert_eq!(ubig!(0).checked_shl(usize::MAX), Some(ubig!(0)));
+    /// ```
+    pub fn checked_shl(&self, n: usize) -> Option<UBig> {
+        let bits = self.bit_len();
+        if bits == 0 {
+            return Some(UBig::from_word(0));
+        }
+        match bits.checked_add(n) {
+            Some(bits) if bits <= UBig::MAX_BIT_LEN => Some(self << n),
+            _ => None,
+        }
+    }
+
+    /// `self * rhs`, or `None` if the result would be longer than [UBig::MAX_BIT_LEN].
+    fn checked_mul_bits(&self, rhs: &UBig) -> Option<UBig> {
+        // The product has either bits - 1 or bits bits.
+        let bits = self.bit_len().checked_add(rhs.bit_len())?;
+        if bits <= UBig::MAX_BIT_LEN {
+            Some(self * rhs)
+        } else if bits - 1 > UBig::MAX_BIT_LEN {
+            None
+        } else {
+            // self * rhs < 2^MAX_BIT_LEN iff self <= (2^MAX_BIT_LEN - 1) / rhs
+            let mut half = UBig::from_word(0);
+            half.set_bit(UBig::MAX_BIT_LEN - 1);
+            let max = (&half - UBig::from_word(1)) + half;
+            if *self <= max / rhs {
+                Some(self * rhs)
+            } else {
+                None
+            }
+        }
+    }
+}
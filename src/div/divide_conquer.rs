@@ -1,16 +1,13 @@
 //! Divide and conquer division algorithm.
 
 use crate::{
-    add,
-    arch::word::{SignedWord, Word},
-    div,
-    fast_divide::FastDivideNormalized,
-    memory::Memory,
-    mul,
-    sign::Sign::*,
+    arch::word::Word, div, fast_divide::FastDivideNormalized, memory::Memory, mul, thresholds,
 };
 use alloc::alloc::Layout;
-use static_assertions::const_assert;
+
+/// Minimum value of the simple division threshold. Guarantees that the lower half of the
+/// quotient in `div_rem_in_place_same_len` has at least 2 words.
+pub(crate) const MIN_LEN_SIMPLE: usize = 3;
 
 /// Memory requirement for division.
 pub(crate) fn memory_requirement_exact(lhs_len: usize, rhs_len: usize) -> Layout {
@@ -38,12 +35,13 @@ pub(crate) fn div_rem_in_place(
     fast_div_rhs_top: FastDivideNormalized,
     memory: &mut Memory,
 ) -> bool {
-    assert!(lhs.len() > rhs.len() + div::MAX_LEN_SIMPLE && rhs.len() > div::MAX_LEN_SIMPLE);
+    let max_len_simple = thresholds::current().div_simple;
+    assert!(lhs.len() > rhs.len() + max_len_simple && rhs.len() > max_len_simple);
 
     let mut overflow = false;
     let n = rhs.len();
     let mut m = lhs.len();
-    assert!(n > div::MAX_LEN_SIMPLE && m >= n);
+    assert!(m >= n);
     while m >= 2 * n {
         let o = div_rem_in_place_same_len(&mut lhs[m - 2 * n..m], rhs, fast_div_rhs_top, memory);
         if o {
@@ -71,9 +69,8 @@ fn div_rem_in_place_same_len(
     memory: &mut Memory,
 ) -> bool {
     let n = rhs.len();
-    assert!(n > div::MAX_LEN_SIMPLE && lhs.len() == 2 * n);
-    // To guarantee n_lo >= 2.
-    const_assert!(div::MAX_LEN_SIMPLE >= 3);
+    // n >= MIN_LEN_SIMPLE + 1 guarantees n_lo >= 2.
+    assert!(n > MIN_LEN_SIMPLE && lhs.len() == 2 * n);
     let n_lo = n / 2;
 
     // Divide lhs[n_lo..] by rhs, putting quotient in lhs[n+n_lo..] and remainder in lhs[n_lo..n+n_lo].
@@ -108,30 +105,10 @@ fn div_rem_in_place_small_quotient(
     assert!(n >= 2 && lhs.len() >= n);
     let m = lhs.len() - n;
     assert!(m < n);
-    if m <= div::MAX_LEN_SIMPLE {
+    if m <= thresholds::current().div_simple {
         return div::simple::div_rem_in_place(lhs, rhs, fast_div_rhs_top);
     }
-    // Use top m words of the divisor to get a quotient approximation. It may be too large by at most 2.
-    // Quotient is in lhs[n..], remainder in lhs[..n].
-    // This is 2m / m division.
-    let mut q_overflow: SignedWord =
-        div_rem_in_place_same_len(&mut lhs[n - m..], &rhs[n - m..], fast_div_rhs_top, memory)
-            .into();
-    let (rem, q) = lhs.split_at_mut(n);
-
-    // Subtract q * (the rest of rhs) from rem.
-    // The multiplication here is m words by * (n-m) words.
-    let mut rem_overflow: SignedWord = mul::add_signed_mul(rem, Negative, q, &rhs[..n - m], memory);
-    if q_overflow != 0 {
-        rem_overflow -= SignedWord::from(add::sub_same_len_in_place(&mut rem[m..], &rhs[..n - m]));
-    }
-
-    // If the remainder overflowed, adjust q and rem.
-    while rem_overflow < 0 {
-        rem_overflow += SignedWord::from(add::add_same_len_in_place(rem, rhs));
-        q_overflow -= SignedWord::from(add::sub_one_in_place(q));
-    }
-
-    assert!(rem_overflow == 0 && q_overflow >= 0 && q_overflow <= 1);
-    q_overflow != 0
+    div::div_rem_in_place_small_quotient(lhs, rhs, memory, |lhs, rhs, memory| {
+        div_rem_in_place_same_len(lhs, rhs, fast_div_rhs_top, memory)
+    })
 }
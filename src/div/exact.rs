@@ -0,0 +1,257 @@
+//! Exact division and divisibility testing from the low end.
+//!
+//! Jebelean, "An algorithm for exact division", 1993.
+//!
+//! Quotient words are computed from the bottom using the 2-adic inverse of the lowest word of an
+//! odd divisor, so only the low words of the product of the quotient and the divisor need to be
+//! subtracted.
+//!
+//! For long quotients and divisors, the quotient is computed in blocks as long as the divisor,
+//! using fast multiplication: each block is the product of the bottom words of the dividend and
+//! the 2-adic inverse of the divisor, computed by Newton's method.
+
+use crate::{
+    add,
+    arch::word::Word,
+    math,
+    memory::{self, Memory},
+    mul,
+    primitive::extend_word,
+    primitive::split_double_word,
+    sign::Sign::*,
+    thresholds,
+};
+use alloc::alloc::Layout;
+
+/// words = words / rhs
+///
+/// rhs must be odd and divide words.
+pub(crate) fn div_exact_by_word_in_place(words: &mut [Word], rhs: Word) {
+    debug_assert!(rhs & 1 == 1);
+    let inv = math::inverse_word_2adic(rhs);
+    let mut borrow: Word = 0;
+    for word in words.iter_mut() {
+        let (a, overflow) = word.overflowing_sub(borrow);
+        let q = a.wrapping_mul(inv);
+        *word = q;
+        // q * rhs = a (mod 2^WORD_BITS), so the high word of q * rhs is the borrow into the
+        // next word.
+        let (_, hi) = split_double_word(extend_word(q) * extend_word(rhs));
+        borrow = hi + Word::from(overflow);
+    }
+    debug_assert!(borrow == 0);
+}
+
+/// lhs = lhs / rhs
+///
+/// rhs must be odd and divide lhs.
+///
+/// The quotient is stored in the bottom `lhs.len() - rhs.len() + 1` words of lhs. The top words
+/// are left in an unspecified state.
+pub(crate) fn div_exact_in_place(lhs: &mut [Word], rhs: &[Word]) {
+    let n = rhs.len();
+    debug_assert!(lhs.len() >= n && rhs[0] & 1 == 1);
+    // The quotient fits in quotient_len words, so only the bottom quotient_len words matter.
+    let quotient_len = lhs.len() - n + 1;
+    div_exact_low(&mut lhs[..quotient_len], rhs);
+}
+
+/// lhs = lhs / rhs mod 2^(WORD_BITS * lhs.len())
+///
+/// rhs must be odd.
+fn div_exact_low(lhs: &mut [Word], rhs: &[Word]) {
+    debug_assert!(rhs[0] & 1 == 1);
+    let inv = math::inverse_word_2adic(rhs[0]);
+    for i in 0..lhs.len() {
+        let q = lhs[i].wrapping_mul(inv);
+        let len = rhs.len().min(lhs.len() - i);
+        let borrow = mul::sub_mul_word_same_len_in_place(&mut lhs[i..i + len], q, &rhs[..len]);
+        if i + len < lhs.len() {
+            let _overflow = add::sub_word_in_place(&mut lhs[i + len..], borrow);
+        }
+        debug_assert!(lhs[i] == 0);
+        lhs[i] = q;
+    }
+}
+
+/// Is lhs divisible by rhs?
+///
+/// rhs must be odd. lhs is left in an unspecified state.
+pub(crate) fn is_divisible_in_place(lhs: &mut [Word], rhs: &[Word]) -> bool {
+    let n = rhs.len();
+    debug_assert!(lhs.len() >= n && rhs[0] & 1 == 1);
+    let inv = math::inverse_word_2adic(rhs[0]);
+    let quotient_len = lhs.len() - n + 1;
+    // After subtracting q * rhs, where q has quotient_len words, lhs is divisible by
+    // 2^(WORD_BITS * quotient_len). The result r = (lhs - q * rhs) / 2^(WORD_BITS * quotient_len)
+    // satisfies -rhs < r < rhs, so lhs is divisible by rhs iff r = 0.
+    for i in 0..quotient_len {
+        let q = lhs[i].wrapping_mul(inv);
+        let len = n.min(lhs.len() - i);
+        let borrow = mul::sub_mul_word_same_len_in_place(&mut lhs[i..i + len], q, &rhs[..len]);
+        if i + len == lhs.len() {
+            if borrow != 0 {
+                return false;
+            }
+        } else if add::sub_word_in_place(&mut lhs[i + len..], borrow) {
+            // Negative, and it can never become non-negative again.
+            return false;
+        }
+    }
+    lhs[quotient_len..].iter().all(|word| *word == 0)
+}
+
+/// Temporary memory required by `div_exact_in_place_large`.
+pub(crate) fn memory_requirement_large(lhs_len: usize, rhs_len: usize) -> Layout {
+    // The inverse and a product of a block and the inverse or rhs, then memory for the
+    // multiplications.
+    let quotient_len = lhs_len - rhs_len + 1;
+    let block_len = quotient_len.min(rhs_len);
+    memory::add_layout(
+        memory::array_layout::<Word>(3 * block_len),
+        mul::memory_requirement_up_to(block_len),
+    )
+}
+
+/// lhs = lhs / rhs
+///
+/// Same as `div_exact_in_place`, using fast multiplication.
+pub(crate) fn div_exact_in_place_large(lhs: &mut [Word], rhs: &[Word], memory: &mut Memory) {
+    let n = rhs.len();
+    debug_assert!(lhs.len() >= n && rhs[0] & 1 == 1);
+    let quotient_len = lhs.len() - n + 1;
+    div_exact_low_large(&mut lhs[..quotient_len], rhs, memory);
+}
+
+/// lhs = lhs / rhs mod 2^(WORD_BITS * lhs.len())
+///
+/// rhs must be odd.
+///
+/// The quotient is computed in blocks of up to rhs.len() words, from the bottom. Each block is
+/// the bottom words of lhs times the 2-adic inverse of rhs, and then block * rhs is subtracted
+/// from lhs.
+fn div_exact_low_large(lhs: &mut [Word], rhs: &[Word], memory: &mut Memory) {
+    let k = lhs.len();
+    let block_len = k.min(rhs.len());
+    let (inverse, mut memory) = memory.allocate_slice_fill::<Word>(block_len, 0);
+    inverse_2adic(inverse, rhs, &mut memory);
+
+    let mut start = 0;
+    while start < k {
+        let len = block_len.min(k - start);
+        let lhs = &mut lhs[start..];
+        // block = lhs * inverse mod 2^(WORD_BITS * len)
+        let (product, mut mul_memory) = memory.allocate_slice_fill::<Word>(2 * len, 0);
+        let overflow = mul::add_signed_mul(
+            product,
+            Positive,
+            &lhs[..len],
+            &inverse[..len],
+            &mut mul_memory,
+        );
+        debug_assert!(overflow == 0);
+        lhs[..len].copy_from_slice(&product[..len]);
+
+        // Only the bottom k - start words of lhs matter. The bottom len words of block * rhs are
+        // equal to the bottom len words of lhs, which are replaced by the block.
+        let rhs_len = rhs.len().min(lhs.len());
+        if len < lhs.len() {
+            let (block, lhs_hi) = lhs.split_at_mut(len);
+            let (product, mut mul_memory) = memory.allocate_slice_fill::<Word>(len + rhs_len, 0);
+            let overflow =
+                mul::add_signed_mul(product, Positive, block, &rhs[..rhs_len], &mut mul_memory);
+            debug_assert!(overflow == 0);
+            let product_hi = &product[len..product.len().min(len + lhs_hi.len())];
+            let _overflow = add::sub_in_place(lhs_hi, product_hi);
+        }
+        start += len;
+    }
+}
+
+/// inverse = 1 / rhs mod 2^(WORD_BITS * inverse.len())
+///
+/// rhs must be odd and have at least inverse.len() words.
+///
+/// Newton's method: if x = 1 / rhs mod 2^(WORD_BITS * lo), then x - x * (rhs * x - 1) is the
+/// inverse mod 2^(WORD_BITS * 2 * lo).
+fn inverse_2adic(inverse: &mut [Word], rhs: &[Word], memory: &mut Memory) {
+    let n = inverse.len();
+    debug_assert!(rhs.len() >= n && rhs[0] & 1 == 1);
+    if n <= thresholds::current().div_simple {
+        inverse.fill(0);
+        inverse[0] = 1;
+        div_exact_low(inverse, &rhs[..n]);
+        return;
+    }
+
+    let lo = n - n / 2;
+    let (x, inverse_hi) = inverse.split_at_mut(lo);
+    inverse_2adic(x, rhs, memory);
+    // rhs * x - 1 = e * 2^(WORD_BITS * lo)
+    let (product, mut memory) = memory.allocate_slice_fill::<Word>(n + lo, 0);
+    let overflow = mul::add_signed_mul(product, Positive, &rhs[..n], x, &mut memory);
+    debug_assert!(overflow == 0);
+    let e = &product[lo..n];
+    // inverse_hi = -x * e mod 2^(WORD_BITS * (n - lo))
+    let (product, mut memory) = memory.allocate_slice_fill::<Word>(lo + e.len(), 0);
+    let overflow = mul::add_signed_mul(product, Positive, x, e, &mut memory);
+    debug_assert!(overflow == 0);
+    inverse_hi.fill(0);
+    let _overflow = add::sub_same_len_in_place(inverse_hi, &product[..n - lo]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{memory::MemoryAllocation, mul::testing};
+    use alloc::vec;
+
+    #[test]
+    fn test_div_exact_by_word_in_place() {
+        let mut words = [0, 0, 3];
+        div_exact_by_word_in_place(&mut words, 3);
+        assert_eq!(words, [0, 0, 1]);
+
+        let mut words = [Word::MAX - 14, Word::MAX, 14];
+        // 15 * [MAX, MAX, 0] = [MAX - 14, MAX, 14]
+        div_exact_by_word_in_place(&mut words, 15);
+        assert_eq!(words, [Word::MAX, Word::MAX, 0]);
+    }
+
+    #[test]
+    fn test_div_exact_in_place() {
+        // [1, 1] * [1, 0, 1] = [1, 1, 1, 1]
+        let mut words = [1, 1, 1, 1];
+        div_exact_in_place(&mut words, &[1, 1]);
+        assert_eq!(words[..3], [1, 0, 1]);
+    }
+
+    #[test]
+    fn test_large() {
+        for (q_len, n) in &[(100, 100), (300, 40), (40, 300), (257, 129), (1000, 500)] {
+            let mut rhs = testing::pseudo_random_words(*n, 1);
+            rhs[0] |= 1;
+            let q = testing::pseudo_random_words(*q_len, 2);
+            let mut lhs = vec![0; q_len + n];
+            let mut allocation =
+                MemoryAllocation::new(mul::memory_requirement_exact(*n.min(q_len)));
+            let overflow =
+                mul::add_signed_mul(&mut lhs, Positive, &q, &rhs, &mut allocation.memory());
+            assert!(overflow == 0);
+
+            let mut allocation = MemoryAllocation::new(memory_requirement_large(lhs.len(), *n));
+            let mut quotient = lhs;
+            div_exact_in_place_large(&mut quotient, &rhs, &mut allocation.memory());
+            assert_eq!(quotient[..*q_len], q[..], "q_len={} n={}", q_len, n);
+            assert_eq!(quotient[*q_len], 0);
+        }
+    }
+
+    #[test]
+    fn test_is_divisible_in_place() {
+        assert!(is_divisible_in_place(&mut [1, 1, 1, 1], &[1, 1]));
+        assert!(!is_divisible_in_place(&mut [1, 1, 1, 2], &[1, 1]));
+        assert!(is_divisible_in_place(&mut [3, 5], &[3, 5]));
+        assert!(!is_divisible_in_place(&mut [3, 5], &[3, 6]));
+    }
+}
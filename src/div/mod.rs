@@ -1,19 +1,35 @@
 //! Division functions.
 
 use crate::{
-    arch::word::Word,
+    add,
+    arch::word::{SignedWord, Word},
     fast_divide::FastDivideNormalized,
     memory::{self, Memory},
+    mul,
     primitive::{double_word, extend_word},
     shift,
+    sign::Sign::*,
+    thresholds::{self, ThresholdConfig},
 };
 use alloc::alloc::Layout;
 
 mod divide_conquer;
+pub(crate) mod exact;
+mod newton;
 mod simple;
 
-/// If divisor or quotient is at most this length, use the simple division algorithm.
-const MAX_LEN_SIMPLE: usize = 32;
+/// Do the division algorithms support the lengths the thresholds use them for?
+pub(crate) const fn thresholds_valid(config: &ThresholdConfig) -> bool {
+    config.div_simple >= divide_conquer::MIN_LEN_SIMPLE
+        && config.div_newton >= config.div_simple
+        && config.div_newton >= newton::MAX_LEN_RECIPROCAL_DIVIDE
+}
+
+/// Should division use the simple algorithm rather than divide and conquer or Newton's method?
+fn use_simple(lhs_len: usize, rhs_len: usize) -> bool {
+    let max_len_simple = thresholds::current().div_simple;
+    rhs_len <= max_len_simple || lhs_len - rhs_len <= max_len_simple
+}
 
 /// Normalize a large divisor.
 ///
@@ -108,13 +124,37 @@ pub(crate) fn fast_rem_by_normalized_word(
     rem
 }
 
+/// Should exact division and divisibility testing use the simple algorithms in [exact]?
+pub(crate) fn exact_prefers_simple(lhs_len: usize, rhs_len: usize) -> bool {
+    debug_assert!(lhs_len >= rhs_len);
+    use_simple(lhs_len, rhs_len)
+}
+
+/// Should exact division use full division rather than the algorithms in [exact]?
+///
+/// Beyond the simple range, computing the quotient with the 2-adic inverse of the divisor only
+/// pays off when the quotient is much shorter than the divisor, and testing divisibility this
+/// way never does.
+pub(crate) fn exact_prefers_full(lhs_len: usize, rhs_len: usize) -> bool {
+    debug_assert!(lhs_len >= rhs_len);
+    !use_simple(lhs_len, rhs_len) && 2 * (lhs_len - rhs_len + 1) > rhs_len
+}
+
+/// Should division use Newton's method rather than divide and conquer?
+fn use_newton(lhs_len: usize, rhs_len: usize) -> bool {
+    let min_len_newton = thresholds::current().div_newton;
+    rhs_len > min_len_newton && lhs_len - rhs_len > min_len_newton
+}
+
 /// Memory requirement for division.
 pub(crate) fn memory_requirement_exact(lhs_len: usize, rhs_len: usize) -> Layout {
     assert!(lhs_len >= rhs_len && rhs_len >= 2);
-    if rhs_len <= MAX_LEN_SIMPLE || lhs_len - rhs_len <= MAX_LEN_SIMPLE {
+    if use_simple(lhs_len, rhs_len) {
         memory::zero_layout()
-    } else {
+    } else if !use_newton(lhs_len, rhs_len) {
         divide_conquer::memory_requirement_exact(lhs_len, rhs_len)
+    } else {
+        newton::memory_requirement_exact(lhs_len, rhs_len)
     }
 }
 
@@ -135,11 +175,61 @@ pub(crate) fn div_rem_in_place(
 ) -> bool {
     assert!(lhs.len() >= rhs.len() && rhs.len() >= 2);
 
-    if rhs.len() <= MAX_LEN_SIMPLE || lhs.len() - rhs.len() <= MAX_LEN_SIMPLE {
+    if use_simple(lhs.len(), rhs.len()) {
         simple::div_rem_in_place(lhs, rhs, fast_div_rhs_top)
-    } else {
+    } else if !use_newton(lhs.len(), rhs.len()) {
         divide_conquer::div_rem_in_place(lhs, rhs, fast_div_rhs_top, memory)
+    } else {
+        newton::div_rem_in_place(lhs, rhs, memory)
+    }
+}
+
+/// Division in place with a quotient shorter than the divisor.
+///
+/// Divide lhs by rhs, replacing the top words of lhs by the quotient and the
+/// bottom words of lhs by the remainder.
+///
+/// With m the length of the quotient, `div_top` divides 2m words by the top m words of rhs in
+/// the same way.
+///
+/// lhs = [lhs / rhs, lhs % rhs]
+///
+/// Returns carry in the quotient. It is at most 1 because rhs is normalized.
+#[must_use]
+fn div_rem_in_place_small_quotient<F>(
+    lhs: &mut [Word],
+    rhs: &[Word],
+    memory: &mut Memory,
+    div_top: F,
+) -> bool
+where
+    F: FnOnce(&mut [Word], &[Word], &mut Memory) -> bool,
+{
+    let n = rhs.len();
+    assert!(lhs.len() >= n);
+    let m = lhs.len() - n;
+    assert!(m >= 2 && m < n);
+    // Use top m words of the divisor to get a quotient approximation. It may be too large by at most 2.
+    // Quotient is in lhs[n..], remainder in lhs[..n].
+    // This is 2m / m division.
+    let mut q_overflow: SignedWord = div_top(&mut lhs[n - m..], &rhs[n - m..], memory).into();
+    let (rem, q) = lhs.split_at_mut(n);
+
+    // Subtract q * (the rest of rhs) from rem.
+    // The multiplication here is m words by * (n-m) words.
+    let mut rem_overflow: SignedWord = mul::add_signed_mul(rem, Negative, q, &rhs[..n - m], memory);
+    if q_overflow != 0 {
+        rem_overflow -= SignedWord::from(add::sub_same_len_in_place(&mut rem[m..], &rhs[..n - m]));
+    }
+
+    // If the remainder overflowed, adjust q and rem.
+    while rem_overflow < 0 {
+        rem_overflow += SignedWord::from(add::add_same_len_in_place(rem, rhs));
+        q_overflow -= SignedWord::from(add::sub_one_in_place(q));
     }
+
+    assert!(rem_overflow == 0 && q_overflow >= 0 && q_overflow <= 1);
+    q_overflow != 0
 }
 
 #[cfg(test)]
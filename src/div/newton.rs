@@ -0,0 +1,289 @@
+//! Division using a reciprocal of the divisor computed by Newton's method.
+
+use crate::{
+    add,
+    arch::word::Word,
+    cmp, div,
+    fast_divide::FastDivideNormalized,
+    memory::{self, Memory},
+    mul,
+    sign::Sign::*,
+};
+use alloc::alloc::Layout;
+use core::cmp::Ordering;
+
+/// If the divisor is at most this long, compute its reciprocal by division rather than by
+/// Newton's method.
+pub(crate) const MAX_LEN_RECIPROCAL_DIVIDE: usize = 64;
+
+/// Memory requirement for division.
+pub(crate) fn memory_requirement_exact(lhs_len: usize, rhs_len: usize) -> Layout {
+    assert!(lhs_len > rhs_len);
+    let m = lhs_len - rhs_len;
+    if m < rhs_len {
+        // Divide by the top m words, then multiply the quotient by the rest of the divisor.
+        return memory::max_layout(
+            memory_requirement_exact(2 * m, m),
+            mul::memory_requirement_up_to(m),
+        );
+    }
+    let n = rhs_len;
+    memory::add_layout(
+        // Reciprocal.
+        memory::array_layout::<Word>(n),
+        memory::max_layout(
+            memory_requirement_reciprocal(n),
+            // Quotient block and product of the top of lhs and the reciprocal.
+            memory::add_layout(
+                memory::array_layout::<Word>(3 * n),
+                mul::memory_requirement_up_to(n),
+            ),
+        ),
+    )
+}
+
+/// Memory requirement for the reciprocal of an n-word number.
+fn memory_requirement_reciprocal(n: usize) -> Layout {
+    if n <= MAX_LEN_RECIPROCAL_DIVIDE {
+        memory::add_layout(
+            memory::array_layout::<Word>(2 * n),
+            div::memory_requirement_exact(2 * n, n),
+        )
+    } else {
+        let h = n - (n - 1) / 2;
+        memory::add_layout(
+            memory::array_layout::<Word>(h),
+            memory::max_layout(
+                memory_requirement_reciprocal(h),
+                memory::add_layout(
+                    memory::array_layout::<Word>(n + 3 * h + 2),
+                    mul::memory_requirement_up_to(h),
+                ),
+            ),
+        )
+    }
+}
+
+/// Division in place using a reciprocal of the divisor.
+///
+/// Divide lhs by rhs, replacing the top words of lhs by the quotient and the
+/// bottom words of lhs by the remainder.
+///
+/// rhs must be normalized and lhs must be longer than rhs. A quotient shorter than rhs is
+/// computed using a reciprocal of the top words of rhs, followed by one correction step for the
+/// rest of rhs.
+///
+/// lhs = [lhs / rhs, lhs % rhs]
+///
+/// Returns carry in the quotient. It is at most 1 because rhs is normalized.
+#[must_use]
+pub(crate) fn div_rem_in_place(lhs: &mut [Word], rhs: &[Word], memory: &mut Memory) -> bool {
+    let n = rhs.len();
+    let m = lhs.len();
+    assert!(n >= 2 && m > n);
+    if m - n < n {
+        return div::div_rem_in_place_small_quotient(lhs, rhs, memory, div_rem_in_place);
+    }
+
+    let overflow = cmp::cmp_same_len(&lhs[m - n..], rhs) >= Ordering::Equal;
+    if overflow {
+        let overflow = add::sub_same_len_in_place(&mut lhs[m - n..], rhs);
+        assert!(!overflow);
+    }
+
+    let (reciprocal, mut memory) = memory.allocate_slice_fill::<Word>(n, 0);
+    approx_reciprocal(reciprocal, rhs, &mut memory);
+
+    // Compute the quotient from the top in blocks of at most n words.
+    let mut top = m;
+    while top > n {
+        let k = n.min(top - n);
+        div_rem_block(&mut lhs[top - n - k..top], rhs, reciprocal, &mut memory);
+        top -= k;
+    }
+    overflow
+}
+
+/// Divide a block with a quotient of at most n words.
+///
+/// lhs = [lhs / rhs, lhs % rhs]
+///
+/// The top n words of lhs must be less than rhs.
+fn div_rem_block(lhs: &mut [Word], rhs: &[Word], reciprocal: &[Word], memory: &mut Memory) {
+    let n = rhs.len();
+    let k = lhs.len() - n;
+    debug_assert!(k <= n);
+
+    // With X = B^n + reciprocal and A1 the top k words of lhs, the quotient is approximated
+    // by A1 * X / B^n. It is never too large and at most a few units too small.
+    let (product, mut memory) = memory.allocate_slice_fill::<Word>(k + n, 0);
+    let carry = mul::add_signed_mul(product, Positive, &lhs[n..], reciprocal, &mut memory);
+    debug_assert!(carry == 0);
+    let (q, mut memory) = memory.allocate_slice_copy(&product[n..]);
+    let overflow = add::add_same_len_in_place(q, &lhs[n..]);
+    debug_assert!(!overflow);
+
+    let carry = mul::add_signed_mul(lhs, Negative, q, rhs, &mut memory);
+    debug_assert!(carry == 0);
+    while lhs[n..].iter().any(|word| *word != 0)
+        || cmp::cmp_same_len(&lhs[..n], rhs) >= Ordering::Equal
+    {
+        let overflow = add::sub_in_place(lhs, rhs);
+        debug_assert!(!overflow);
+        let overflow = add::add_one_in_place(q);
+        debug_assert!(!overflow);
+    }
+    lhs[n..].copy_from_slice(q);
+}
+
+/// Approximate reciprocal.
+///
+/// a must be normalized. Sets x such that, with X = B^n + x and A = a,
+/// A * X < B^(2n) <= A * (X + 2).
+///
+/// Brent, Zimmermann, "Modern Computer Arithmetic", algorithm 3.5.
+fn approx_reciprocal(x: &mut [Word], a: &[Word], memory: &mut Memory) {
+    let n = a.len();
+    debug_assert!(x.len() == n && n >= 2 && a[n - 1].leading_zeros() == 0);
+
+    if n <= MAX_LEN_RECIPROCAL_DIVIDE {
+        // X = floor((B^(2n) - 1) / A)
+        let (lhs, mut memory) = memory.allocate_slice_fill::<Word>(2 * n, Word::MAX);
+        let fast_div_top = FastDivideNormalized::new(a[n - 1]);
+        let overflow = div::div_rem_in_place(lhs, a, fast_div_top, &mut memory);
+        assert!(overflow);
+        x.copy_from_slice(&lhs[n..]);
+        return;
+    }
+
+    let l = (n - 1) / 2;
+    let h = n - l;
+
+    // X_h = B^h + x_h, reciprocal of the top h words of A.
+    let (x_h, mut memory) = memory.allocate_slice_fill::<Word>(h, 0);
+    approx_reciprocal(x_h, &a[l..], &mut memory);
+
+    // T = A * X_h
+    let (t, mut memory) = memory.allocate_slice_fill::<Word>(n + h + 1, 0);
+    let carry = mul::add_signed_mul(&mut t[..n + h], Positive, a, x_h, &mut memory);
+    debug_assert!(carry == 0);
+    let overflow = add::add_in_place(&mut t[h..], a);
+    debug_assert!(!overflow);
+
+    // Make T < B^(n+h).
+    while t[n + h] != 0 {
+        let overflow = add::sub_one_in_place(x_h);
+        debug_assert!(!overflow);
+        let overflow = add::sub_in_place(t, a);
+        debug_assert!(!overflow);
+    }
+
+    // T = B^(n+h) - T < 2 B^n
+    for word in t[..n + h].iter_mut() {
+        *word = !*word;
+    }
+    let overflow = add::add_one_in_place(&mut t[..n + h]);
+    debug_assert!(!overflow);
+    debug_assert!(t[n + 1..].iter().all(|word| *word == 0));
+
+    // U = floor(T / B^l) * X_h
+    let t_m = &t[l..n + 1];
+    let (u, mut memory) = memory.allocate_slice_fill::<Word>(2 * h + 1, 0);
+    let carry = mul::add_signed_mul(u, Positive, t_m, x_h, &mut memory);
+    debug_assert!(carry == 0);
+    let overflow = add::add_in_place(&mut u[h..], t_m);
+    debug_assert!(!overflow);
+
+    // X = X_h B^l + floor(U / B^(2h-l))
+    for word in x[..l].iter_mut() {
+        *word = 0;
+    }
+    x[l..].copy_from_slice(x_h);
+    let overflow = add::add_in_place(x, &u[2 * h - l..]);
+    debug_assert!(!overflow);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{memory::MemoryAllocation, mul::testing, primitive::WORD_BITS};
+
+    #[test]
+    fn test_approx_reciprocal() {
+        for n in &[2, 3, 64, 65, 100, 201, 500] {
+            let n = *n;
+            // A simple pseudo-random normalized number.
+            let mut a = testing::pseudo_random_words(n, 1);
+            a[n - 1] |= 1 << (WORD_BITS - 1);
+            for variant in 0..3 {
+                match variant {
+                    1 => a.iter_mut().take(n - 1).for_each(|word| *word = 0),
+                    2 => a.iter_mut().take(n - 1).for_each(|word| *word = Word::MAX),
+                    _ => {}
+                }
+                check_reciprocal(&a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_rem_in_place() {
+        for (n, m) in &[
+            (65, 130),
+            (100, 250),
+            (300, 1000),
+            (300, 1500),
+            (300, 500),
+            (1000, 1070),
+        ] {
+            let (n, m) = (*n, *m);
+            let mut rhs = testing::pseudo_random_words(n, 2);
+            rhs[n - 1] |= 1 << (WORD_BITS - 1);
+            let lhs = testing::pseudo_random_words(m, 3);
+
+            let mut expected = lhs.clone();
+            let mut allocation = MemoryAllocation::new(div::memory_requirement_exact(m, n));
+            let mut memory = allocation.memory();
+            let expected_overflow = div::div_rem_in_place(
+                &mut expected,
+                &rhs,
+                FastDivideNormalized::new(rhs[n - 1]),
+                &mut memory,
+            );
+
+            let mut actual = lhs.clone();
+            let mut allocation = MemoryAllocation::new(memory_requirement_exact(m, n));
+            let mut memory = allocation.memory();
+            let overflow = div_rem_in_place(&mut actual, &rhs, &mut memory);
+            assert_eq!(overflow, expected_overflow);
+            assert_eq!(actual, expected);
+        }
+    }
+
+    /// Check A * X < B^(2n) <= A * (X + 2).
+    fn check_reciprocal(a: &[Word]) {
+        let n = a.len();
+        let mut allocation = MemoryAllocation::new(memory::add_layout(
+            memory_requirement_reciprocal(n),
+            mul::memory_requirement_up_to(n),
+        ));
+        let mut memory = allocation.memory();
+        let mut x = alloc::vec![0; n];
+        approx_reciprocal(&mut x, a, &mut memory);
+
+        // A * X = A * x + A * B^n
+        let mut product = alloc::vec![0; 2 * n + 1];
+        let carry = mul::add_signed_mul(&mut product[..2 * n], Positive, a, &x, &mut memory);
+        assert_eq!(carry, 0);
+        let overflow = add::add_in_place(&mut product[n..], a);
+        assert!(!overflow);
+        assert_eq!(product[2 * n], 0);
+
+        // A * (X + 2)
+        let overflow = add::add_in_place(&mut product, a);
+        assert!(!overflow);
+        let overflow = add::add_in_place(&mut product, a);
+        assert!(!overflow);
+        assert_ne!(product[2 * n], 0);
+    }
+}
@@ -0,0 +1,193 @@
+//! Exact division and divisibility.
+
+use crate::{
+    div, div_ops,
+    ibig::IBig,
+    memory::MemoryAllocation,
+    ubig::{Repr::*, UBig},
+};
+
+impl UBig {
+    /// Exact division: `self / rhs` when `rhs` is known to divide `self`.
+    ///
+    /// This is faster than regular division when the quotient or the divisor is short, or the
+    /// quotient is much shorter than the divisor. Otherwise it is as fast as regular division.
+    ///
+    /// If `rhs` does not divide `self`, the result is an unspecified value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(91).div_exact(&ubig!(13)), ubig!(7));
+    /// let a = ubig!(3).pow(100) << 50;
+    /// assert_eq!(a.div_exact(&(ubig!(3).pow(40) << 20)), ubig!(3).pow(60) << 30);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is 0.
+    pub fn div_exact(&self, rhs: &UBig) -> UBig {
+        let shift = match rhs.trailing_zeros() {
+            None => div_ops::panic_divide_by_0(),
+            Some(shift) => shift,
+        };
+        if self.len() >= rhs.len() && div::exact_prefers_full(self.len(), rhs.len()) {
+            // As fast, and without shifted copies.
+            return self / rhs;
+        }
+        let lhs = self >> shift;
+        let rhs = rhs >> shift;
+        match (lhs.into_repr(), rhs.repr()) {
+            (Small(word0), Small(word1)) => UBig::from_word(word0 / *word1),
+            (Small(_), Large(_)) => UBig::from_word(0),
+            (Large(mut buffer0), Small(word1)) => {
+                div::exact::div_exact_by_word_in_place(&mut buffer0, *word1);
+                buffer0.into()
+            }
+            (Large(mut buffer0), Large(buffer1)) => {
+                if buffer0.len() < buffer1.len() {
+                    UBig::from_word(0)
+                } else {
+                    if div::exact_prefers_simple(buffer0.len(), buffer1.len()) {
+                        div::exact::div_exact_in_place(&mut buffer0, buffer1);
+                    } else {
+                        let mut allocation = MemoryAllocation::new(
+                            div::exact::memory_requirement_large(buffer0.len(), buffer1.len()),
+                        );
+                        div::exact::div_exact_in_place_large(
+                            &mut buffer0,
+                            buffer1,
+                            &mut allocation.memory(),
+                        );
+                    }
+                    buffer0.truncate(buffer0.len() - buffer1.len() + 1);
+                    buffer0.into()
+                }
+            }
+        }
+    }
+
+    /// Is `self` divisible by `rhs`?
+    ///
+    /// Zero is divisible by every number including zero. Non-zero numbers are not divisible by
+    /// zero.
+    ///
+    /// This is faster than computing the remainder when the quotient or the divisor is short.
+    /// Otherwise it is as fast.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert!(ubig!(91).is_divisible_by(&ubig!(13)));
+    /// assert!(!ubig!(92).is_divisible_by(&ubig!(13)));
+    /// assert!(ubig!(0).is_divisible_by(&ubig!(0)));
+    /// ```
+    pub fn is_divisible_by(&self, rhs: &UBig) -> bool {
+        let shift = match (self.trailing_zeros(), rhs.trailing_zeros()) {
+            (None, _) => return true,
+            (Some(_), None) => return false,
+            (Some(lhs_zeros), Some(rhs_zeros)) => {
+                if lhs_zeros < rhs_zeros {
+                    return false;
+                }
+                rhs_zeros
+            }
+        };
+        if self.len() >= rhs.len() && !div::exact_prefers_simple(self.len(), rhs.len()) {
+            // As fast, and without shifted copies.
+            return self % rhs == UBig::from_word(0);
+        }
+        // An odd number divides lhs iff it divides lhs >> shift.
+        let lhs = self >> shift;
+        let rhs = rhs >> shift;
+        match (lhs.into_repr(), rhs.repr()) {
+            (Small(word0), Small(word1)) => word0 % *word1 == 0,
+            (Small(_), Large(_)) => false,
+            (Large(buffer0), Small(word1)) => div::rem_by_word(&buffer0, *word1) == 0,
+            (Large(mut buffer0), Large(buffer1)) => {
+                if buffer0.len() < buffer1.len() {
+                    false
+                } else if div::exact_prefers_simple(buffer0.len(), buffer1.len()) {
+                    div::exact::is_divisible_in_place(&mut buffer0, buffer1)
+                } else {
+                    UBig::from(buffer0) % rhs == UBig::from_word(0)
+                }
+            }
+        }
+    }
+
+    /// Is `self` divisible by `2^n`?
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert!(ubig!(24).is_divisible_by_pow2(3));
+    /// assert!(!ubig!(24).is_divisible_by_pow2(4));
+    /// assert!(ubig!(0).is_divisible_by_pow2(1000));
+    /// ```
+    pub fn is_divisible_by_pow2(&self, n: usize) -> bool {
+        match self.trailing_zeros() {
+            None => true,
+            Some(zeros) => zeros >= n,
+        }
+    }
+}
+
+impl IBig {
+    /// Exact division: `self / rhs` when `rhs` is known to divide `self`.
+    ///
+    /// This is faster than regular division when the quotient or the divisor is short, or the
+    /// quotient is much shorter than the divisor. Otherwise it is as fast as regular division.
+    ///
+    /// If `rhs` does not divide `self`, the result is an unspecified value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert_eq!(ibig!(-91).div_exact(&ibig!(13)), ibig!(-7));
+    /// assert_eq!(ibig!(-91).div_exact(&ibig!(-13)), ibig!(7));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is 0.
+    pub fn div_exact(&self, rhs: &IBig) -> IBig {
+        IBig::from_sign_magnitude(
+            self.sign() * rhs.sign(),
+            self.magnitude().div_exact(rhs.magnitude()),
+        )
+    }
+
+    /// Is `self` divisible by `rhs`?
+    ///
+    /// Zero is divisible by every number including zero. Non-zero numbers are not divisible by
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert!(ibig!(-91).is_divisible_by(&ibig!(13)));
+    /// assert!(!ibig!(-92).is_divisible_by(&ibig!(-13)));
+    /// ```
+    pub fn is_divisible_by(&self, rhs: &IBig) -> bool {
+        self.magnitude().is_divisible_by(rhs.magnitude())
+    }
+
+    /// Is `self` divisible by `2^n`?
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert!(ibig!(-24).is_divisible_by_pow2(3));
+    /// assert!(!ibig!(-24).is_divisible_by_pow2(4));
+    /// ```
+    pub fn is_divisible_by_pow2(&self, n: usize) -> bool {
+        self.magnitude().is_divisible_by_pow2(n)
+    }
+}
@@ -5,14 +5,18 @@ use crate::{
     buffer::Buffer,
     div,
     ibig::IBig,
-    memory::MemoryAllocation,
-    ops::{Abs, DivEuclid, DivRem, DivRemEuclid, RemEuclid},
+    memory,
+    ops::{
+        Abs, DivCeil, DivEuclid, DivFloor, DivRem, DivRemEuclid, DivRemFloor, DivRound, RemEuclid,
+        TieBreak,
+    },
+    scratch::Scratch,
     shift,
     sign::Sign::*,
     ubig::{Repr::*, UBig},
 };
 use core::{
-    mem,
+    cmp::Ordering,
     ops::{Div, DivAssign, Rem, RemAssign},
 };
 
@@ -102,13 +106,13 @@ impl Div<&UBig> for &UBig {
 
 impl DivAssign<UBig> for UBig {
     fn div_assign(&mut self, rhs: UBig) {
-        *self = mem::take(self) / rhs;
+        self.update(|x| x / rhs);
     }
 }
 
 impl DivAssign<&UBig> for UBig {
     fn div_assign(&mut self, rhs: &UBig) {
-        *self = mem::take(self) / rhs;
+        self.update(|x| x / rhs);
     }
 }
 
@@ -200,13 +204,13 @@ impl Rem<&UBig> for &UBig {
 
 impl RemAssign<UBig> for UBig {
     fn rem_assign(&mut self, rhs: UBig) {
-        *self = mem::take(self) % rhs;
+        self.update(|x| x % rhs);
     }
 }
 
 impl RemAssign<&UBig> for UBig {
     fn rem_assign(&mut self, rhs: &UBig) {
-        *self = mem::take(self) % rhs;
+        self.update(|x| x % rhs);
     }
 }
 
@@ -400,6 +404,146 @@ impl DivRemEuclid<&UBig> for &UBig {
     }
 }
 
+impl DivFloor<UBig> for UBig {
+    type Output = UBig;
+
+    fn div_floor(self, rhs: UBig) -> UBig {
+        self / rhs
+    }
+}
+
+impl DivFloor<&UBig> for UBig {
+    type Output = UBig;
+
+    fn div_floor(self, rhs: &UBig) -> UBig {
+        self / rhs
+    }
+}
+
+impl DivFloor<UBig> for &UBig {
+    type Output = UBig;
+
+    fn div_floor(self, rhs: UBig) -> UBig {
+        self / rhs
+    }
+}
+
+impl DivFloor<&UBig> for &UBig {
+    type Output = UBig;
+
+    fn div_floor(self, rhs: &UBig) -> UBig {
+        self / rhs
+    }
+}
+
+impl DivRemFloor<UBig> for UBig {
+    type OutputDiv = UBig;
+    type OutputRem = UBig;
+
+    fn div_rem_floor(self, rhs: UBig) -> (UBig, UBig) {
+        self.div_rem(rhs)
+    }
+}
+
+impl DivRemFloor<&UBig> for UBig {
+    type OutputDiv = UBig;
+    type OutputRem = UBig;
+
+    fn div_rem_floor(self, rhs: &UBig) -> (UBig, UBig) {
+        self.div_rem(rhs)
+    }
+}
+
+impl DivRemFloor<UBig> for &UBig {
+    type OutputDiv = UBig;
+    type OutputRem = UBig;
+
+    fn div_rem_floor(self, rhs: UBig) -> (UBig, UBig) {
+        self.div_rem(rhs)
+    }
+}
+
+impl DivRemFloor<&UBig> for &UBig {
+    type OutputDiv = UBig;
+    type OutputRem = UBig;
+
+    fn div_rem_floor(self, rhs: &UBig) -> (UBig, UBig) {
+        self.div_rem(rhs)
+    }
+}
+
+impl DivCeil<UBig> for UBig {
+    type Output = UBig;
+
+    fn div_ceil(self, rhs: UBig) -> UBig {
+        let (q, r) = self.div_rem(rhs);
+        UBig::div_ceil_from_trunc(q, &r)
+    }
+}
+
+impl DivCeil<&UBig> for UBig {
+    type Output = UBig;
+
+    fn div_ceil(self, rhs: &UBig) -> UBig {
+        let (q, r) = self.div_rem(rhs);
+        UBig::div_ceil_from_trunc(q, &r)
+    }
+}
+
+impl DivCeil<UBig> for &UBig {
+    type Output = UBig;
+
+    fn div_ceil(self, rhs: UBig) -> UBig {
+        let (q, r) = self.div_rem(rhs);
+        UBig::div_ceil_from_trunc(q, &r)
+    }
+}
+
+impl DivCeil<&UBig> for &UBig {
+    type Output = UBig;
+
+    fn div_ceil(self, rhs: &UBig) -> UBig {
+        let (q, r) = self.div_rem(rhs);
+        UBig::div_ceil_from_trunc(q, &r)
+    }
+}
+
+impl DivRound<UBig> for UBig {
+    type Output = UBig;
+
+    fn div_round(self, rhs: UBig, ties: TieBreak) -> UBig {
+        let (q, r) = self.div_rem(&rhs);
+        UBig::div_round_from_trunc(q, &r, &rhs, ties)
+    }
+}
+
+impl DivRound<&UBig> for UBig {
+    type Output = UBig;
+
+    fn div_round(self, rhs: &UBig, ties: TieBreak) -> UBig {
+        let (q, r) = self.div_rem(rhs);
+        UBig::div_round_from_trunc(q, &r, rhs, ties)
+    }
+}
+
+impl DivRound<UBig> for &UBig {
+    type Output = UBig;
+
+    fn div_round(self, rhs: UBig, ties: TieBreak) -> UBig {
+        let (q, r) = self.div_rem(&rhs);
+        UBig::div_round_from_trunc(q, &r, &rhs, ties)
+    }
+}
+
+impl DivRound<&UBig> for &UBig {
+    type Output = UBig;
+
+    fn div_round(self, rhs: &UBig, ties: TieBreak) -> UBig {
+        let (q, r) = self.div_rem(rhs);
+        UBig::div_round_from_trunc(q, &r, rhs, ties)
+    }
+}
+
 impl Div<IBig> for IBig {
     type Output = IBig;
 
@@ -446,13 +590,13 @@ impl Div<&IBig> for &IBig {
 
 impl DivAssign<IBig> for IBig {
     fn div_assign(&mut self, rhs: IBig) {
-        *self = mem::take(self) / rhs;
+        self.update(|x| x / rhs);
     }
 }
 
 impl DivAssign<&IBig> for IBig {
     fn div_assign(&mut self, rhs: &IBig) {
-        *self = mem::take(self) / rhs;
+        self.update(|x| x / rhs);
     }
 }
 
@@ -502,13 +646,13 @@ impl Rem<&IBig> for &IBig {
 
 impl RemAssign<IBig> for IBig {
     fn rem_assign(&mut self, rhs: IBig) {
-        *self = mem::take(self) % rhs;
+        self.update(|x| x % rhs);
     }
 }
 
 impl RemAssign<&IBig> for IBig {
     fn rem_assign(&mut self, rhs: &IBig) {
-        *self = mem::take(self) % rhs;
+        self.update(|x| x % rhs);
     }
 }
 
@@ -726,9 +870,157 @@ impl DivRemEuclid<&IBig> for &IBig {
     }
 }
 
+impl DivFloor<IBig> for IBig {
+    type Output = IBig;
+
+    fn div_floor(self, rhs: IBig) -> IBig {
+        let (q, r) = self.div_rem(&rhs);
+        IBig::div_rem_floor_from_trunc(q, r, &rhs).0
+    }
+}
+
+impl DivFloor<&IBig> for IBig {
+    type Output = IBig;
+
+    fn div_floor(self, rhs: &IBig) -> IBig {
+        let (q, r) = self.div_rem(rhs);
+        IBig::div_rem_floor_from_trunc(q, r, rhs).0
+    }
+}
+
+impl DivFloor<IBig> for &IBig {
+    type Output = IBig;
+
+    fn div_floor(self, rhs: IBig) -> IBig {
+        let (q, r) = self.div_rem(&rhs);
+        IBig::div_rem_floor_from_trunc(q, r, &rhs).0
+    }
+}
+
+impl DivFloor<&IBig> for &IBig {
+    type Output = IBig;
+
+    fn div_floor(self, rhs: &IBig) -> IBig {
+        let (q, r) = self.div_rem(rhs);
+        IBig::div_rem_floor_from_trunc(q, r, rhs).0
+    }
+}
+
+impl DivRemFloor<IBig> for IBig {
+    type OutputDiv = IBig;
+    type OutputRem = IBig;
+
+    fn div_rem_floor(self, rhs: IBig) -> (IBig, IBig) {
+        let (q, r) = self.div_rem(&rhs);
+        IBig::div_rem_floor_from_trunc(q, r, &rhs)
+    }
+}
+
+impl DivRemFloor<&IBig> for IBig {
+    type OutputDiv = IBig;
+    type OutputRem = IBig;
+
+    fn div_rem_floor(self, rhs: &IBig) -> (IBig, IBig) {
+        let (q, r) = self.div_rem(rhs);
+        IBig::div_rem_floor_from_trunc(q, r, rhs)
+    }
+}
+
+impl DivRemFloor<IBig> for &IBig {
+    type OutputDiv = IBig;
+    type OutputRem = IBig;
+
+    fn div_rem_floor(self, rhs: IBig) -> (IBig, IBig) {
+        let (q, r) = self.div_rem(&rhs);
+        IBig::div_rem_floor_from_trunc(q, r, &rhs)
+    }
+}
+
+impl DivRemFloor<&IBig> for &IBig {
+    type OutputDiv = IBig;
+    type OutputRem = IBig;
+
+    fn div_rem_floor(self, rhs: &IBig) -> (IBig, IBig) {
+        let (q, r) = self.div_rem(rhs);
+        IBig::div_rem_floor_from_trunc(q, r, rhs)
+    }
+}
+
+impl DivCeil<IBig> for IBig {
+    type Output = IBig;
+
+    fn div_ceil(self, rhs: IBig) -> IBig {
+        let (q, r) = self.div_rem(&rhs);
+        IBig::div_ceil_from_trunc(q, &r, &rhs)
+    }
+}
+
+impl DivCeil<&IBig> for IBig {
+    type Output = IBig;
+
+    fn div_ceil(self, rhs: &IBig) -> IBig {
+        let (q, r) = self.div_rem(rhs);
+        IBig::div_ceil_from_trunc(q, &r, rhs)
+    }
+}
+
+impl DivCeil<IBig> for &IBig {
+    type Output = IBig;
+
+    fn div_ceil(self, rhs: IBig) -> IBig {
+        let (q, r) = self.div_rem(&rhs);
+        IBig::div_ceil_from_trunc(q, &r, &rhs)
+    }
+}
+
+impl DivCeil<&IBig> for &IBig {
+    type Output = IBig;
+
+    fn div_ceil(self, rhs: &IBig) -> IBig {
+        let (q, r) = self.div_rem(rhs);
+        IBig::div_ceil_from_trunc(q, &r, rhs)
+    }
+}
+
+impl DivRound<IBig> for IBig {
+    type Output = IBig;
+
+    fn div_round(self, rhs: IBig, ties: TieBreak) -> IBig {
+        let (q, r) = self.div_rem(&rhs);
+        IBig::div_round_from_trunc(q, &r, &rhs, ties)
+    }
+}
+
+impl DivRound<&IBig> for IBig {
+    type Output = IBig;
+
+    fn div_round(self, rhs: &IBig, ties: TieBreak) -> IBig {
+        let (q, r) = self.div_rem(rhs);
+        IBig::div_round_from_trunc(q, &r, rhs, ties)
+    }
+}
+
+impl DivRound<IBig> for &IBig {
+    type Output = IBig;
+
+    fn div_round(self, rhs: IBig, ties: TieBreak) -> IBig {
+        let (q, r) = self.div_rem(&rhs);
+        IBig::div_round_from_trunc(q, &r, &rhs, ties)
+    }
+}
+
+impl DivRound<&IBig> for &IBig {
+    type Output = IBig;
+
+    fn div_round(self, rhs: &IBig, ties: TieBreak) -> IBig {
+        let (q, r) = self.div_rem(rhs);
+        IBig::div_round_from_trunc(q, &r, rhs, ties)
+    }
+}
+
 impl UBig {
     /// `lhs / rhs`
-    fn div_word(lhs: Word, rhs: Word) -> UBig {
+    pub(crate) fn div_word(lhs: Word, rhs: Word) -> UBig {
         match lhs.checked_div(rhs) {
             Some(res) => UBig::from_word(res),
             None => panic_divide_by_0(),
@@ -736,7 +1028,7 @@ impl UBig {
     }
 
     /// `lhs % rhs`
-    fn rem_word(lhs: Word, rhs: Word) -> UBig {
+    pub(crate) fn rem_word(lhs: Word, rhs: Word) -> UBig {
         match lhs.checked_rem(rhs) {
             Some(res) => UBig::from_word(res),
             None => panic_divide_by_0(),
@@ -744,7 +1036,7 @@ impl UBig {
     }
 
     /// (lhs / rhs, lhs % rhs)
-    fn div_rem_word(lhs: Word, rhs: Word) -> (UBig, UBig) {
+    pub(crate) fn div_rem_word(lhs: Word, rhs: Word) -> (UBig, UBig) {
         // If division works, remainder also works.
         match lhs.checked_div(rhs) {
             Some(res) => (UBig::from_word(res), UBig::from_word(lhs % rhs)),
@@ -753,13 +1045,13 @@ impl UBig {
     }
 
     /// `lhs / rhs`
-    fn div_large_word(lhs: Buffer, rhs: Word) -> UBig {
+    pub(crate) fn div_large_word(lhs: Buffer, rhs: Word) -> UBig {
         let (q, _) = UBig::div_rem_large_word(lhs, rhs);
         q
     }
 
     /// `lhs % rhs`
-    fn rem_large_word(lhs: &[Word], rhs: Word) -> UBig {
+    pub(crate) fn rem_large_word(lhs: &[Word], rhs: Word) -> UBig {
         if rhs == 0 {
             panic_divide_by_0();
         }
@@ -767,7 +1059,7 @@ impl UBig {
     }
 
     /// (buffer / rhs, buffer % rhs)
-    fn div_rem_large_word(mut buffer: Buffer, rhs: Word) -> (UBig, UBig) {
+    pub(crate) fn div_rem_large_word(mut buffer: Buffer, rhs: Word) -> (UBig, UBig) {
         if rhs == 0 {
             panic_divide_by_0();
         }
@@ -776,15 +1068,15 @@ impl UBig {
     }
 
     /// `lhs / rhs`
-    fn div_large(mut lhs: Buffer, mut rhs: Buffer) -> UBig {
-        let _shift = UBig::div_rem_in_lhs(&mut lhs, &mut rhs);
+    pub(crate) fn div_large(mut lhs: Buffer, mut rhs: Buffer) -> UBig {
+        let _shift = UBig::div_rem_in_lhs(&mut lhs, &mut rhs, &mut Scratch::new());
         lhs.erase_front(rhs.len());
         lhs.into()
     }
 
     /// `lhs % rhs`
-    fn rem_large(mut lhs: Buffer, mut rhs: Buffer) -> UBig {
-        let shift = UBig::div_rem_in_lhs(&mut lhs, &mut rhs);
+    pub(crate) fn rem_large(mut lhs: Buffer, mut rhs: Buffer) -> UBig {
+        let shift = UBig::div_rem_in_lhs(&mut lhs, &mut rhs, &mut Scratch::new());
         let n = rhs.len();
         rhs.copy_from_slice(&lhs[..n]);
         let low_bits = shift::shr_in_place(&mut rhs, shift);
@@ -793,8 +1085,17 @@ impl UBig {
     }
 
     /// `(lhs / rhs, lhs % rhs)`
-    fn div_rem_large(mut lhs: Buffer, mut rhs: Buffer) -> (UBig, UBig) {
-        let shift = UBig::div_rem_in_lhs(&mut lhs, &mut rhs);
+    pub(crate) fn div_rem_large(lhs: Buffer, rhs: Buffer) -> (UBig, UBig) {
+        UBig::div_rem_large_with_scratch(lhs, rhs, &mut Scratch::new())
+    }
+
+    /// `(lhs / rhs, lhs % rhs)` using scratch memory.
+    fn div_rem_large_with_scratch(
+        mut lhs: Buffer,
+        mut rhs: Buffer,
+        scratch: &mut Scratch,
+    ) -> (UBig, UBig) {
+        let shift = UBig::div_rem_in_lhs(&mut lhs, &mut rhs, scratch);
         let n = rhs.len();
         rhs.copy_from_slice(&lhs[..n]);
         let low_bits = shift::shr_in_place(&mut rhs, shift);
@@ -806,15 +1107,13 @@ impl UBig {
     /// lhs = (lhs / rhs, lhs % rhs)
     ///
     /// Returns shift.
-    fn div_rem_in_lhs(lhs: &mut Buffer, rhs: &mut Buffer) -> u32 {
+    fn div_rem_in_lhs(lhs: &mut Buffer, rhs: &mut Buffer, scratch: &mut Scratch) -> u32 {
         let (shift, fast_div_rhs_top) = div::normalize_large(rhs);
         let lhs_carry = shift::shl_in_place(lhs, shift);
         if lhs_carry != 0 {
             lhs.push_may_reallocate(lhs_carry);
         }
-        let mut allocation =
-            MemoryAllocation::new(div::memory_requirement_exact(lhs.len(), rhs.len()));
-        let mut memory = allocation.memory();
+        let mut memory = scratch.memory(div::memory_requirement_exact(lhs.len(), rhs.len()));
         let overflow = div::div_rem_in_place(lhs, rhs, fast_div_rhs_top, &mut memory);
         if overflow {
             lhs.push_may_reallocate(1);
@@ -823,6 +1122,170 @@ impl UBig {
     }
 }
 
-fn panic_divide_by_0() -> ! {
+impl UBig {
+    /// Division into existing numbers: `*q = a / b` and `*r = a % b`.
+    ///
+    /// Reuses the memory of `q` and `r` when they have enough capacity, and uses `scratch` for
+    /// temporary memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Scratch, UBig};
+    /// let (mut q, mut r) = (UBig::default(), UBig::default());
+    /// let mut scratch = Scratch::new();
+    /// UBig::div_rem_into(&mut q, &mut r, &ubig!(23), &ubig!(10), &mut scratch);
+    /// assert_eq!((q, r), (ubig!(2), ubig!(3)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` is 0.
+    pub fn div_rem_into(q: &mut UBig, r: &mut UBig, a: &UBig, b: &UBig, scratch: &mut Scratch) {
+        match (a.repr(), b.repr()) {
+            (Large(lhs), Large(rhs)) if lhs.len() >= rhs.len() => {
+                let (m, n) = (lhs.len(), rhs.len());
+                let mut memory = scratch.memory(memory::add_layout(
+                    memory::array_layout::<Word>(m + 1 + n),
+                    div::memory_requirement_exact(m + 1, n),
+                ));
+                let (rhs_normalized, mut memory) = memory.allocate_slice_copy(rhs);
+                let (shift, fast_div_rhs_top) = div::normalize_large(rhs_normalized);
+                let (lhs_normalized, mut memory) = memory.allocate_slice_fill::<Word>(m + 1, 0);
+                lhs_normalized[..m].copy_from_slice(lhs);
+                lhs_normalized[m] = shift::shl_in_place(&mut lhs_normalized[..m], shift);
+                // The top word is less than 2^shift, so the top n words are less than rhs.
+                let overflow = div::div_rem_in_place(
+                    lhs_normalized,
+                    rhs_normalized,
+                    fast_div_rhs_top,
+                    &mut memory,
+                );
+                debug_assert!(!overflow);
+
+                let mut quotient = q.take_buffer(m + 1 - n);
+                quotient.extend_from_slice(&lhs_normalized[n..]);
+                *q = UBig::from_reused_buffer(quotient);
+                let mut remainder = r.take_buffer(n);
+                remainder.extend_from_slice(&lhs_normalized[..n]);
+                let low_bits = shift::shr_in_place(&mut remainder, shift);
+                debug_assert!(low_bits == 0);
+                *r = UBig::from_reused_buffer(remainder);
+            }
+            _ => {
+                let (quotient, remainder) = a.div_rem(b);
+                q.assign(quotient);
+                r.assign(remainder);
+            }
+        }
+    }
+
+    /// `(self / rhs, self % rhs)`, using `scratch` for temporary memory.
+    ///
+    /// Same as [DivRem], but reuses the memory in `scratch` across calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Scratch};
+    /// let mut scratch = Scratch::new();
+    /// let a = ubig!(_0x123456789abcdef0123456789abcdef0123456789abcdef);
+    /// let b = ubig!(_0xfedcba9876543210fedcba9876543210);
+    /// assert_eq!(
+    ///     a.div_rem_with_scratch(&b, &mut scratch),
+    ///     (&a / &b, &a % &b)
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is 0.
+    pub fn div_rem_with_scratch(&self, rhs: &UBig, scratch: &mut Scratch) -> (UBig, UBig) {
+        match (self.repr(), rhs.repr()) {
+            (Large(buffer0), Large(buffer1)) if buffer0.len() >= buffer1.len() => {
+                UBig::div_rem_large_with_scratch(buffer0.clone(), buffer1.clone(), scratch)
+            }
+            _ => self.div_rem(rhs),
+        }
+    }
+
+    /// Quotient rounded up, from the truncated quotient and remainder.
+    fn div_ceil_from_trunc(q: UBig, r: &UBig) -> UBig {
+        if *r == UBig::from_word(0) {
+            q
+        } else {
+            q + UBig::from_word(1)
+        }
+    }
+
+    /// Quotient rounded to nearest, from the truncated quotient and remainder.
+    fn div_round_from_trunc(q: UBig, r: &UBig, rhs: &UBig, ties: TieBreak) -> UBig {
+        let round_up = match (r << 1).cmp(rhs) {
+            Ordering::Less => false,
+            Ordering::Greater => true,
+            Ordering::Equal => match ties {
+                TieBreak::Even => q.bit(0),
+                TieBreak::AwayFromZero | TieBreak::Up => true,
+                TieBreak::TowardZero | TieBreak::Down => false,
+            },
+        };
+        if round_up {
+            q + UBig::from_word(1)
+        } else {
+            q
+        }
+    }
+}
+
+impl IBig {
+    /// Floor quotient and remainder, from the truncated quotient and remainder.
+    fn div_rem_floor_from_trunc(q: IBig, r: IBig, rhs: &IBig) -> (IBig, IBig) {
+        // The remainder has the sign of lhs. If it is non-zero and differs from the sign of rhs,
+        // the quotient is negative and was rounded up.
+        if *r.magnitude() != UBig::from_word(0) && r.sign() != rhs.sign() {
+            (q - IBig::from(1u8), r + rhs)
+        } else {
+            (q, r)
+        }
+    }
+
+    /// Quotient rounded up, from the truncated quotient and remainder.
+    fn div_ceil_from_trunc(q: IBig, r: &IBig, rhs: &IBig) -> IBig {
+        // If the remainder is non-zero and has the same sign as rhs, the quotient is positive and
+        // was rounded down.
+        if *r.magnitude() != UBig::from_word(0) && r.sign() == rhs.sign() {
+            q + IBig::from(1u8)
+        } else {
+            q
+        }
+    }
+
+    /// Quotient rounded to nearest, from the truncated quotient and remainder.
+    fn div_round_from_trunc(q: IBig, r: &IBig, rhs: &IBig, ties: TieBreak) -> IBig {
+        if *r.magnitude() == UBig::from_word(0) {
+            return q;
+        }
+        // The exact quotient is between q and q + direction.
+        let direction = r.sign() * rhs.sign();
+        let away_from_zero = match (r.magnitude() << 1).cmp(rhs.magnitude()) {
+            Ordering::Less => false,
+            Ordering::Greater => true,
+            Ordering::Equal => match ties {
+                TieBreak::Even => q.magnitude().bit(0),
+                TieBreak::AwayFromZero => true,
+                TieBreak::TowardZero => false,
+                TieBreak::Up => direction == Positive,
+                TieBreak::Down => direction == Negative,
+            },
+        };
+        if away_from_zero {
+            q + IBig::from_sign_magnitude(direction, UBig::from_word(1))
+        } else {
+            q
+        }
+    }
+}
+
+pub(crate) fn panic_divide_by_0() -> ! {
     panic!("divide by 0")
 }
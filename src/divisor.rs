@@ -0,0 +1,335 @@
+//! Precomputed divisors.
+
+use crate::{
+    arch::word::Word,
+    buffer::Buffer,
+    div,
+    fast_divide::FastDivideNormalized,
+    ibig::IBig,
+    memory::MemoryAllocation,
+    ops::DivRem,
+    primitive::extend_word,
+    shift,
+    ubig::{Repr::*, UBig},
+};
+use alloc::vec::Vec;
+use core::ops::{Div, DivAssign, Rem, RemAssign};
+
+/// A divisor with precomputed data for fast repeated division.
+///
+/// Dividing by the same number many times is faster with a `Divisor` than with `UBig`, because
+/// the divisor is normalized and its top word reciprocal is computed only once.
+///
+/// [UBig] and [IBig] can be divided by `&Divisor` using `/`, `%` and
+/// [DivRem](crate::ops::DivRem). Division of [IBig] truncates towards zero, same as for
+/// [IBig] divisors.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{ibig, ops::DivRem, ubig, Divisor};
+/// let divisor = Divisor::new(&ubig!(100));
+/// assert_eq!(ubig!(12345) / &divisor, ubig!(123));
+/// assert_eq!(ubig!(12345) % &divisor, ubig!(45));
+/// assert_eq!(ibig!(-12345).div_rem(&divisor), (ibig!(-123), ibig!(-45)));
+/// ```
+pub struct Divisor(DivisorRepr);
+
+enum DivisorRepr {
+    Small(DivisorSmall),
+    Large(DivisorLarge),
+}
+
+struct DivisorSmall {
+    divisor: Word,
+    fast_div: FastDivideNormalized,
+}
+
+struct DivisorLarge {
+    normalized_divisor: Vec<Word>,
+    shift: u32,
+    fast_div_top: FastDivideNormalized,
+}
+
+impl Divisor {
+    /// Create a new divisor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Divisor};
+    /// let divisor = Divisor::new(&ubig!(100));
+    /// assert_eq!(divisor.divisor(), ubig!(100));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn new(n: &UBig) -> Divisor {
+        match n.repr() {
+            Small(0) => panic!("Divisor::new(0)"),
+            Small(word) => Divisor(DivisorRepr::Small(DivisorSmall::new(*word))),
+            Large(words) => Divisor(DivisorRepr::Large(DivisorLarge::new(words))),
+        }
+    }
+
+    /// The divisor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Divisor};
+    /// let divisor = Divisor::new(&ubig!(100));
+    /// assert_eq!(divisor.divisor(), ubig!(100));
+    /// ```
+    pub fn divisor(&self) -> UBig {
+        match &self.0 {
+            DivisorRepr::Small(small) => UBig::from_word(small.divisor),
+            DivisorRepr::Large(large) => large.divisor(),
+        }
+    }
+
+    /// (lhs / self, lhs % self)
+    fn div_rem_ubig(&self, lhs: UBig) -> (UBig, UBig) {
+        match &self.0 {
+            DivisorRepr::Small(small) => small.div_rem(lhs),
+            DivisorRepr::Large(large) => large.div_rem(lhs),
+        }
+    }
+
+    /// lhs % self
+    fn rem_ubig(&self, lhs: &UBig) -> UBig {
+        match &self.0 {
+            DivisorRepr::Small(small) => small.rem(lhs),
+            DivisorRepr::Large(large) => large.div_rem(lhs.clone()).1,
+        }
+    }
+}
+
+impl DivisorSmall {
+    fn new(divisor: Word) -> DivisorSmall {
+        debug_assert!(divisor != 0);
+        DivisorSmall {
+            divisor,
+            fast_div: FastDivideNormalized::new(divisor << divisor.leading_zeros()),
+        }
+    }
+
+    fn div_rem(&self, lhs: UBig) -> (UBig, UBig) {
+        match lhs.into_repr() {
+            Small(word) => (
+                UBig::from_word(word / self.divisor),
+                UBig::from_word(word % self.divisor),
+            ),
+            Large(mut buffer) => {
+                let rem = div::fast_div_by_word_in_place(&mut buffer, self.divisor, self.fast_div);
+                (buffer.into(), UBig::from_word(rem))
+            }
+        }
+    }
+
+    fn rem(&self, lhs: &UBig) -> UBig {
+        match lhs.repr() {
+            Small(word) => UBig::from_word(word % self.divisor),
+            Large(buffer) => {
+                let shift = self.divisor.leading_zeros();
+                let rem = div::fast_rem_by_normalized_word(buffer, self.fast_div);
+                let (_, rem) = self.fast_div.div_rem(extend_word(rem) << shift);
+                UBig::from_word(rem >> shift)
+            }
+        }
+    }
+}
+
+impl DivisorLarge {
+    fn new(divisor: &[Word]) -> DivisorLarge {
+        let mut normalized_divisor = divisor.to_vec();
+        let (shift, fast_div_top) = div::normalize_large(&mut normalized_divisor);
+        DivisorLarge {
+            normalized_divisor,
+            shift,
+            fast_div_top,
+        }
+    }
+
+    fn divisor(&self) -> UBig {
+        let mut buffer = Buffer::allocate(self.normalized_divisor.len());
+        buffer.extend_from_slice(&self.normalized_divisor);
+        let low_bits = shift::shr_in_place(&mut buffer, self.shift);
+        debug_assert!(low_bits == 0);
+        buffer.into()
+    }
+
+    fn div_rem(&self, lhs: UBig) -> (UBig, UBig) {
+        let n = self.normalized_divisor.len();
+        if lhs.len() < n {
+            return (UBig::from_word(0), lhs);
+        }
+        let mut buffer = match lhs.into_repr() {
+            Small(_) => unreachable!(),
+            Large(buffer) => buffer,
+        };
+        let lhs_carry = shift::shl_in_place(&mut buffer, self.shift);
+        if lhs_carry != 0 {
+            buffer.push_may_reallocate(lhs_carry);
+        }
+        let mut allocation = MemoryAllocation::new(div::memory_requirement_exact(buffer.len(), n));
+        let mut memory = allocation.memory();
+        let overflow = div::div_rem_in_place(
+            &mut buffer,
+            &self.normalized_divisor,
+            self.fast_div_top,
+            &mut memory,
+        );
+        if overflow {
+            buffer.push_may_reallocate(1);
+        }
+        let mut rem = Buffer::allocate(n);
+        rem.extend_from_slice(&buffer[..n]);
+        let low_bits = shift::shr_in_place(&mut rem, self.shift);
+        debug_assert!(low_bits == 0);
+        buffer.erase_front(n);
+        (buffer.into(), rem.into())
+    }
+}
+
+impl Div<&Divisor> for UBig {
+    type Output = UBig;
+
+    fn div(self, rhs: &Divisor) -> UBig {
+        rhs.div_rem_ubig(self).0
+    }
+}
+
+impl Div<&Divisor> for &UBig {
+    type Output = UBig;
+
+    fn div(self, rhs: &Divisor) -> UBig {
+        rhs.div_rem_ubig(self.clone()).0
+    }
+}
+
+impl DivAssign<&Divisor> for UBig {
+    fn div_assign(&mut self, rhs: &Divisor) {
+        self.update(|x| x / rhs);
+    }
+}
+
+impl Rem<&Divisor> for UBig {
+    type Output = UBig;
+
+    fn rem(self, rhs: &Divisor) -> UBig {
+        rhs.div_rem_ubig(self).1
+    }
+}
+
+impl Rem<&Divisor> for &UBig {
+    type Output = UBig;
+
+    fn rem(self, rhs: &Divisor) -> UBig {
+        rhs.rem_ubig(self)
+    }
+}
+
+impl RemAssign<&Divisor> for UBig {
+    fn rem_assign(&mut self, rhs: &Divisor) {
+        self.update(|x| x % rhs);
+    }
+}
+
+impl DivRem<&Divisor> for UBig {
+    type OutputDiv = UBig;
+    type OutputRem = UBig;
+
+    fn div_rem(self, rhs: &Divisor) -> (UBig, UBig) {
+        rhs.div_rem_ubig(self)
+    }
+}
+
+impl DivRem<&Divisor> for &UBig {
+    type OutputDiv = UBig;
+    type OutputRem = UBig;
+
+    fn div_rem(self, rhs: &Divisor) -> (UBig, UBig) {
+        rhs.div_rem_ubig(self.clone())
+    }
+}
+
+impl Div<&Divisor> for IBig {
+    type Output = IBig;
+
+    fn div(self, rhs: &Divisor) -> IBig {
+        // Truncate towards 0.
+        let (sign, mag) = self.into_sign_magnitude();
+        IBig::from_sign_magnitude(sign, mag / rhs)
+    }
+}
+
+impl Div<&Divisor> for &IBig {
+    type Output = IBig;
+
+    fn div(self, rhs: &Divisor) -> IBig {
+        // Truncate towards 0.
+        IBig::from_sign_magnitude(self.sign(), self.magnitude() / rhs)
+    }
+}
+
+impl DivAssign<&Divisor> for IBig {
+    fn div_assign(&mut self, rhs: &Divisor) {
+        self.update(|x| x / rhs);
+    }
+}
+
+impl Rem<&Divisor> for IBig {
+    type Output = IBig;
+
+    fn rem(self, rhs: &Divisor) -> IBig {
+        // Remainder with truncating division has same sign as lhs.
+        let (sign, mag) = self.into_sign_magnitude();
+        IBig::from_sign_magnitude(sign, mag % rhs)
+    }
+}
+
+impl Rem<&Divisor> for &IBig {
+    type Output = IBig;
+
+    fn rem(self, rhs: &Divisor) -> IBig {
+        // Remainder with truncating division has same sign as lhs.
+        IBig::from_sign_magnitude(self.sign(), self.magnitude() % rhs)
+    }
+}
+
+impl RemAssign<&Divisor> for IBig {
+    fn rem_assign(&mut self, rhs: &Divisor) {
+        self.update(|x| x % rhs);
+    }
+}
+
+impl DivRem<&Divisor> for IBig {
+    type OutputDiv = IBig;
+    type OutputRem = IBig;
+
+    fn div_rem(self, rhs: &Divisor) -> (IBig, IBig) {
+        // Truncate towards 0.
+        let (sign, mag) = self.into_sign_magnitude();
+        let (q, r) = mag.div_rem(rhs);
+        (
+            IBig::from_sign_magnitude(sign, q),
+            IBig::from_sign_magnitude(sign, r),
+        )
+    }
+}
+
+impl DivRem<&Divisor> for &IBig {
+    type OutputDiv = IBig;
+    type OutputRem = IBig;
+
+    fn div_rem(self, rhs: &Divisor) -> (IBig, IBig) {
+        // Truncate towards 0.
+        let (q, r) = self.magnitude().div_rem(rhs);
+        (
+            IBig::from_sign_magnitude(self.sign(), q),
+            IBig::from_sign_magnitude(self.sign(), r),
+        )
+    }
+}
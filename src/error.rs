@@ -35,3 +35,80 @@ impl Display for ParseError {
 
 #[cfg(feature = "std")]
 impl std::error::Error for ParseError {}
+
+/// Number too large: longer than [UBig::MAX_BIT_LEN](crate::UBig::MAX_BIT_LEN).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CapacityError;
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("number too large")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+/// Memory allocation failed.
+///
+/// Returned by the `try_*` functions, which allocate numbers, temporary memory and internal lists
+/// fallibly. With the `rayon` feature, rayon's thread pool may still allocate infallibly when
+/// work is split between threads.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AllocError {
+    /// The number would be too large.
+    Capacity(CapacityError),
+    /// The memory allocator could not provide enough memory.
+    OutOfMemory,
+}
+
+impl From<CapacityError> for AllocError {
+    fn from(err: CapacityError) -> AllocError {
+        AllocError::Capacity(err)
+    }
+}
+
+impl Display for AllocError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AllocError::Capacity(err) => err.fmt(f),
+            AllocError::OutOfMemory => f.write_str("out of memory"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {}
+
+/// Error parsing a number with fallible allocation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TryParseError {
+    /// Invalid input.
+    Parse(ParseError),
+    /// Memory allocation failed.
+    Alloc(AllocError),
+}
+
+impl From<ParseError> for TryParseError {
+    fn from(err: ParseError) -> TryParseError {
+        TryParseError::Parse(err)
+    }
+}
+
+impl From<AllocError> for TryParseError {
+    fn from(err: AllocError) -> TryParseError {
+        TryParseError::Alloc(err)
+    }
+}
+
+impl Display for TryParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TryParseError::Parse(err) => err.fmt(f),
+            TryParseError::Alloc(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryParseError {}
@@ -0,0 +1,447 @@
+//! Primality testing and integer factorization.
+
+use crate::{
+    arch::word::Word,
+    div,
+    ibig::IBig,
+    math,
+    modular::{Modulo, ModuloRing},
+    primitive::{double_word, extend_word},
+    ubig::{Repr::*, UBig},
+};
+use alloc::{vec, vec::Vec};
+
+/// Factorization: a list of `(prime, exponent)` pairs sorted by prime.
+pub(crate) type Factorization = Vec<(UBig, usize)>;
+
+/// Small primes used for quick divisibility checks before running the full primality test.
+const SMALL_PRIMES: [Word; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+/// Trial division is used for factors below this bound, Pollard's rho above.
+const TRIAL_DIVISION_LIMIT: Word = 1 << 10;
+
+/// Greatest common divisor.
+pub(crate) fn gcd(a: &UBig, b: &UBig) -> UBig {
+    let mut a = a.clone();
+    let mut b = b.clone();
+    while b != UBig::from_word(0) {
+        let r = &a % &b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Is `n` a prime?
+///
+/// Uses the Baillie-PSW test: a strong Fermat test to base 2 followed by a strong Lucas test.
+/// It is exact for `n < 2^64` and has no known counterexamples above that.
+pub(crate) fn is_prime(n: &UBig) -> bool {
+    if let Small(word) = n.repr() {
+        if *word < 2 {
+            return false;
+        }
+    }
+    for p in SMALL_PRIMES.iter() {
+        if div::rem_by_word(n.as_words(), *p) == 0 {
+            return *n == UBig::from_word(*p);
+        }
+    }
+    let ring = ModuloRing::new(n);
+    is_strong_probable_prime_base_2(n, &ring) && is_strong_lucas_probable_prime(n, &ring)
+}
+
+/// Miller-Rabin test to base 2.
+///
+/// n must be odd and greater than 2.
+fn is_strong_probable_prime_base_2(n: &UBig, ring: &ModuloRing) -> bool {
+    let n_minus_1 = n - UBig::from_word(1);
+    let s = n_minus_1.trailing_zeros().unwrap();
+    let d = &n_minus_1 >> s;
+
+    let one = ring.from(1);
+    let minus_one = -&one;
+    let mut x = ring.from(2).pow(&d);
+    if x == one || x == minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = &x * &x;
+        if x == minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/// Strong Lucas test with Selfridge's parameters: P = 1, Q = (1 - D) / 4 where D is the first
+/// element of 5, -7, 9, -11, ... with Jacobi symbol (D/n) = -1.
+///
+/// n must be odd, greater than 2 and not divisible by small primes.
+fn is_strong_lucas_probable_prime(n: &UBig, ring: &ModuloRing) -> bool {
+    let mut d_abs: Word = 5;
+    let mut d_negative = false;
+    loop {
+        match jacobi_small(d_abs, d_negative, n) {
+            -1 => break,
+            0 => return *n == UBig::from_word(d_abs),
+            _ => {}
+        }
+        if d_abs == 13 && is_square(n) {
+            // Perfect squares never have (D/n) = -1.
+            return false;
+        }
+        d_abs += 2;
+        d_negative = !d_negative;
+    }
+    let d = if d_negative {
+        -IBig::from(d_abs)
+    } else {
+        IBig::from(d_abs)
+    };
+    let q = (IBig::from(1u8) - &d) / IBig::from(4u8);
+
+    let n_plus_1 = n + UBig::from_word(1);
+    let s = n_plus_1.trailing_zeros().unwrap();
+    let k = &n_plus_1 >> s;
+
+    let d = ring.from(&d);
+    let q = ring.from(&q);
+    // Multiplication by half_mod replaces division by 2.
+    let half_mod = ring.from(&(&n_plus_1 >> 1));
+
+    // Compute U_k, V_k, Q^k with P = 1.
+    let mut u = ring.from(1);
+    let mut v = ring.from(1);
+    let mut q_k = q.clone();
+    for bit in (0..k.bit_len() - 1).rev() {
+        // Double.
+        u = &u * &v;
+        v = &v * &v - &q_k - &q_k;
+        q_k = &q_k * &q_k;
+        if k.bit(bit) {
+            // Increment.
+            let u_next = (&u + &v) * &half_mod;
+            v = (&d * &u + &v) * &half_mod;
+            u = u_next;
+            q_k = &q_k * &q;
+        }
+    }
+
+    let zero = ring.from(0);
+    if u == zero || v == zero {
+        return true;
+    }
+    for _ in 1..s {
+        v = &v * &v - &q_k - &q_k;
+        if v == zero {
+            return true;
+        }
+        q_k = &q_k * &q_k;
+    }
+    false
+}
+
+/// Jacobi symbol (D/n) for D = ±d_abs, d_abs odd, n odd.
+fn jacobi_small(d_abs: Word, d_negative: bool, n: &UBig) -> i8 {
+    let n_mod_4 = n.as_words()[0] & 3;
+    // Quadratic reciprocity: (d/n) = (n/d) * (-1)^((d-1)/2 * (n-1)/2).
+    let mut result = jacobi_word(div::rem_by_word(n.as_words(), d_abs), d_abs);
+    if d_abs & 3 == 3 && n_mod_4 == 3 {
+        result = -result;
+    }
+    // (-1/n) = (-1)^((n-1)/2)
+    if d_negative && n_mod_4 == 3 {
+        result = -result;
+    }
+    result
+}
+
+/// Jacobi symbol (a/n) for odd n.
+fn jacobi_word(mut a: Word, mut n: Word) -> i8 {
+    debug_assert!(n & 1 == 1);
+    a %= n;
+    let mut result = 1;
+    while a != 0 {
+        let tz = a.trailing_zeros();
+        a >>= tz;
+        // (2/n) = -1 iff n = 3, 5 (mod 8).
+        if tz & 1 == 1 && (n & 7 == 3 || n & 7 == 5) {
+            result = -result;
+        }
+        if a & 3 == 3 && n & 3 == 3 {
+            result = -result;
+        }
+        let r = n % a;
+        n = a;
+        a = r;
+    }
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// Is `n` a perfect square?
+fn is_square(n: &UBig) -> bool {
+    // Newton's method for the integer square root, starting above the root.
+    let mut x = UBig::from_word(1) << math::ceil_div(n.bit_len(), 2);
+    loop {
+        let y = (&x + n / &x) >> 1;
+        if y >= x {
+            return &x * &x == *n;
+        }
+        x = y;
+    }
+}
+
+/// All primes up to `n` inclusive, using the sieve of Eratosthenes.
+///
+/// The sieve takes `n / 2` bytes, and the result about `n / ln(n)` entries. There is no limit on
+/// `n`: callers document this cost.
+pub(crate) fn primes_up_to(n: usize) -> Vec<usize> {
+    if n < 2 {
+        return Vec::new();
+    }
+    // composite[i] is for 2 * i + 1.
+    let mut composite = vec![false; n / 2 + 1];
+    let mut primes = vec![2];
+    for i in 1..composite.len() {
+        if composite[i] {
+            continue;
+        }
+        let p = 2 * i + 1;
+        if p > n {
+            break;
+        }
+        primes.push(p);
+        if let Some(p2) = p.checked_mul(p) {
+            let mut j = p2 / 2;
+            while j < composite.len() {
+                composite[j] = true;
+                j += p;
+            }
+        }
+    }
+    primes
+}
+
+/// Factorize a non-zero number.
+pub(crate) fn factorize(n: &UBig) -> Factorization {
+    assert!(*n != UBig::from_word(0), "factorize(0)");
+
+    let mut factors = Factorization::new();
+    let twos = n.trailing_zeros().unwrap();
+    if twos != 0 {
+        factors.push((UBig::from_word(2), twos));
+    }
+    let mut n = n >> twos;
+
+    let mut p: Word = 3;
+    while p < TRIAL_DIVISION_LIMIT && !is_below_square(&n, p) {
+        let mut exponent = 0;
+        while div::rem_by_word(n.as_words(), p) == 0 {
+            n = match n.into_repr() {
+                Small(word) => UBig::from_word(word / p),
+                Large(buffer) => UBig::div_large_word(buffer, p),
+            };
+            exponent += 1;
+        }
+        if exponent != 0 {
+            factors.push((UBig::from_word(p), exponent));
+        }
+        p += 2;
+    }
+
+    if n != UBig::from_word(1) {
+        if is_below_square(&n, p) {
+            factors.push((n, 1));
+        } else {
+            factorize_large(n, &mut factors);
+        }
+    }
+
+    factors.sort();
+    merge_sorted(&mut factors);
+    factors
+}
+
+/// Is n < p^2?
+fn is_below_square(n: &UBig, p: Word) -> bool {
+    let square = extend_word(p) * extend_word(p);
+    match n.repr() {
+        Small(word) => extend_word(*word) < square,
+        Large(buffer) => buffer.len() == 2 && double_word(buffer[0], buffer[1]) < square,
+    }
+}
+
+/// Factorize a number without small prime factors, appending factors to `factors`.
+fn factorize_large(n: UBig, factors: &mut Factorization) {
+    if is_prime(&n) {
+        factors.push((n, 1));
+    } else {
+        let d = pollard_rho(&n);
+        let other = &n / &d;
+        factorize_large(d, factors);
+        factorize_large(other, factors);
+    }
+}
+
+/// Find a non-trivial divisor of an odd composite number using Pollard's rho algorithm with
+/// Brent's cycle detection.
+fn pollard_rho(n: &UBig) -> UBig {
+    // Number of steps between gcd computations.
+    const BATCH: usize = 128;
+
+    let ring = ModuloRing::new(n);
+    let one = UBig::from_word(1);
+    let mut c: Word = 1;
+    loop {
+        let c_mod = ring.from(c);
+
+        let mut x;
+        let mut y = ring.from(2);
+        let mut ys = y.clone();
+        let mut q = ring.from(1);
+        let mut g = one.clone();
+        let mut r: usize = 1;
+        while g == one {
+            x = y.clone();
+            for _ in 0..r {
+                y = step(&y, &c_mod);
+            }
+            let mut k = 0;
+            while k < r && g == one {
+                ys = y.clone();
+                for _ in 0..BATCH.min(r - k) {
+                    y = step(&y, &c_mod);
+                    q *= &x - &y;
+                }
+                g = gcd(&q.residue(), n);
+                k += BATCH;
+            }
+            r *= 2;
+
+            if g == *n {
+                // The batch overshot, retrace one step at a time.
+                loop {
+                    ys = step(&ys, &c_mod);
+                    g = gcd(&(&x - &ys).residue(), n);
+                    if g != one {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if g != *n {
+            return g;
+        }
+        c += 1;
+    }
+}
+
+/// x^2 + c
+fn step<'a>(x: &Modulo<'a>, c: &Modulo<'a>) -> Modulo<'a> {
+    x * x + c
+}
+
+/// Merge equal primes in a sorted factorization by adding exponents.
+pub(crate) fn merge_sorted(factors: &mut Factorization) {
+    factors.dedup_by(|next, prev| {
+        if next.0 == prev.0 {
+            prev.1 += next.1;
+            true
+        } else {
+            false
+        }
+    });
+}
+
+/// Multiply out a factorization.
+pub(crate) fn product(factors: &[(UBig, usize)]) -> UBig {
+    factors
+        .iter()
+        .fold(UBig::from_word(1), |acc, (p, e)| acc * p.pow(*e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ubig(x: u128) -> UBig {
+        UBig::from(x)
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes: Vec<u128> = (0..2000)
+            .filter(|&n| n >= 2 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+            .collect();
+        for n in 0..2000 {
+            assert_eq!(is_prime(&ubig(n)), primes.contains(&n), "{}", n);
+        }
+        // Strong pseudoprimes to base 2.
+        assert!(!is_prime(&ubig(2047)));
+        assert!(!is_prime(&ubig(3215031751)));
+        assert!(!is_prime(&ubig(3825123056546413051)));
+        // Strong Lucas pseudoprimes.
+        assert!(!is_prime(&ubig(5459)));
+        assert!(!is_prime(&ubig(5777)));
+        // Squares.
+        assert!(!is_prime(&ubig(1009 * 1009)));
+        assert!(is_prime(&ubig(0xffffffffffffffc5)));
+        assert!(is_prime(&((ubig(1) << 127) - ubig(1))));
+        assert!(!is_prime(&((ubig(1) << 128) + ubig(1))));
+    }
+
+    #[test]
+    fn test_primes_up_to() {
+        assert_eq!(primes_up_to(0), Vec::<usize>::new());
+        assert_eq!(primes_up_to(2), vec![2]);
+        assert_eq!(primes_up_to(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(primes_up_to(31).last(), Some(&31));
+        assert_eq!(primes_up_to(100000).len(), 9592);
+    }
+
+    #[test]
+    fn test_jacobi_word() {
+        assert_eq!(jacobi_word(1001, 9907), -1);
+        assert_eq!(jacobi_word(19, 45), 1);
+        assert_eq!(jacobi_word(8, 21), -1);
+        assert_eq!(jacobi_word(5, 21), 1);
+        assert_eq!(jacobi_word(3, 21), 0);
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(factorize(&ubig(1)), vec![]);
+        assert_eq!(factorize(&ubig(2)), vec![(ubig(2), 1)]);
+        assert_eq!(
+            factorize(&ubig(360)),
+            vec![(ubig(2), 3), (ubig(3), 2), (ubig(5), 1)]
+        );
+        assert_eq!(
+            factorize(&ubig(1000003 * 1000003 * 999983)),
+            vec![(ubig(999983), 1), (ubig(1000003), 2)]
+        );
+        // 2^64 + 1 = 274177 * 67280421310721
+        assert_eq!(
+            factorize(&((ubig(1) << 64) + ubig(1))),
+            vec![(ubig(274177), 1), (ubig(67280421310721), 1)]
+        );
+        let n = ubig(0xffffffffffffffc5) * ubig(4294967291) * ubig(4294967291);
+        assert_eq!(
+            factorize(&n),
+            vec![(ubig(4294967291), 2), (ubig(0xffffffffffffffc5), 1)]
+        );
+        assert_eq!(product(&factorize(&n)), n);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_factorize_zero() {
+        let _ = factorize(&ubig(0));
+    }
+}
@@ -0,0 +1,254 @@
+//! Factorials, binomial coefficients and related products.
+
+use crate::{arch::word::Word, factor, math, product, ubig::UBig};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// Below this, factorials are computed as a direct product.
+const MAX_FACTORIAL_DIRECT: usize = 32;
+
+impl UBig {
+    /// Factorial: `n! = 1 * 2 * ... * n`.
+    ///
+    /// Computed from the prime factorization of `n!`, with balanced products so that large
+    /// multiplications use the fast multiplication algorithms. Finding the primes up to `n`
+    /// takes about `n / 2` bytes of temporary memory, less than the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(UBig::factorial(0), ubig!(1));
+    /// assert_eq!(UBig::factorial(10), ubig!(3628800));
+    /// assert_eq!(UBig::factorial(30), ubig!(_265252859812191058636308480000000));
+    /// ```
+    pub fn factorial(n: usize) -> UBig {
+        if n <= MAX_FACTORIAL_DIRECT {
+            return product_of_small(2..=n);
+        }
+        let primes = factor::primes_up_to(n);
+        let exponents: Vec<usize> = primes.iter().map(|p| factorial_exponent(n, *p)).collect();
+        product_of_prime_powers(&primes, &exponents)
+    }
+
+    /// Double factorial: `n!! = n * (n-2) * (n-4) * ...`, down to 1 or 2.
+    ///
+    /// Like `factorial`, uses about `n / 2` bytes of temporary memory for a sieve of primes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(UBig::double_factorial(0), ubig!(1));
+    /// assert_eq!(UBig::double_factorial(9), ubig!(945));
+    /// assert_eq!(UBig::double_factorial(10), ubig!(3840));
+    /// ```
+    pub fn double_factorial(n: usize) -> UBig {
+        let m = n / 2;
+        if n & 1 == 0 {
+            // (2m)!! = 2^m * m!
+            return UBig::factorial(m) << m;
+        }
+        if n <= MAX_FACTORIAL_DIRECT {
+            return product_of_small((3..=n).step_by(2));
+        }
+        // (2m+1)! = (2m+1)!! * (2m)!! = (2m+1)!! * 2^m * m!
+        let primes = factor::primes_up_to(n);
+        let exponents: Vec<usize> = primes
+            .iter()
+            .map(|p| {
+                if *p == 2 {
+                    0
+                } else {
+                    factorial_exponent(n, *p) - factorial_exponent(m, *p)
+                }
+            })
+            .collect();
+        product_of_prime_powers(&primes, &exponents)
+    }
+
+    /// Primorial: `n#`, the product of all primes up to `n`.
+    ///
+    /// The sieve and the list of primes take several times more memory than the result: about
+    /// `n / 2` bytes plus a `usize` per prime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(UBig::primorial(1), ubig!(1));
+    /// assert_eq!(UBig::primorial(10), ubig!(210));
+    /// assert_eq!(UBig::primorial(11), ubig!(2310));
+    /// ```
+    pub fn primorial(n: usize) -> UBig {
+        product_of_small(factor::primes_up_to(n).into_iter())
+    }
+
+    /// Binomial coefficient: the number of `k`-element subsets of an `n`-element set.
+    ///
+    /// Returns 0 if `k > n`.
+    ///
+    /// When `min(k, n - k)` is at least `n / 8`, the result is computed from the primes up to `n`,
+    /// with about `n / 2` bytes of temporary memory. Otherwise it is a direct product of `k`
+    /// numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(UBig::binomial(5, 2), ubig!(10));
+    /// assert_eq!(UBig::binomial(5, 7), ubig!(0));
+    /// assert_eq!(UBig::binomial(100, 50), ubig!(100891344545564193334812497256));
+    /// ```
+    pub fn binomial(n: usize, k: usize) -> UBig {
+        if k > n {
+            return UBig::from_word(0);
+        }
+        let k = k.min(n - k);
+        if k == 0 {
+            return UBig::from_word(1);
+        }
+        if n / 8 > k {
+            // The sieve up to n would cost more than a direct product.
+            // C(n, k) = (n-k+1) * ... * n / k!
+            return product_of_small(n - k + 1..=n).div_exact(&UBig::factorial(k));
+        }
+        // Kummer: the exponent of p in C(n, k) is the exponent in n! minus those in k!, (n-k)!.
+        let primes = factor::primes_up_to(n);
+        let exponents: Vec<usize> = primes
+            .iter()
+            .map(|p| {
+                factorial_exponent(n, *p)
+                    - factorial_exponent(k, *p)
+                    - factorial_exponent(n - k, *p)
+            })
+            .collect();
+        product_of_prime_powers(&primes, &exponents)
+    }
+
+    /// Multinomial coefficient: `(k_1 + k_2 + ... + k_m)! / (k_1! * k_2! * ... * k_m!)`.
+    ///
+    /// This is the number of ways to split a set into labeled subsets of sizes `k_1`, ..., `k_m`.
+    ///
+    /// Uses about `n / 2` bytes of temporary memory for a sieve of primes up to the sum `n` of
+    /// `ks`, even when the result is small. For two parts, `binomial` avoids that when one part
+    /// is small.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(UBig::multinomial(&[]), ubig!(1));
+    /// assert_eq!(UBig::multinomial(&[2, 3]), UBig::binomial(5, 2));
+    /// assert_eq!(UBig::multinomial(&[1, 2, 3]), ubig!(60));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sum of `ks` overflows `usize`.
+    pub fn multinomial(ks: &[usize]) -> UBig {
+        let n = ks
+            .iter()
+            .try_fold(0usize, |sum, k| sum.checked_add(*k))
+            .expect("multinomial: sum overflows usize");
+        let primes = factor::primes_up_to(n);
+        let exponents: Vec<usize> = primes
+            .iter()
+            .map(|p| {
+                ks.iter().fold(factorial_exponent(n, *p), |e, k| {
+                    e - factorial_exponent(*k, *p)
+                })
+            })
+            .collect();
+        product_of_prime_powers(&primes, &exponents)
+    }
+}
+
+/// The exponent of prime `p` in `n!` (Legendre's formula).
+fn factorial_exponent(mut n: usize, p: usize) -> usize {
+    let mut exponent = 0;
+    while n >= p {
+        n /= p;
+        exponent += n;
+    }
+    exponent
+}
+
+/// Product of `primes[i]^exponents[i]`.
+///
+/// Processes the exponents bit by bit from the top, so that most of the work is squaring large
+/// numbers and multiplying by balanced products of primes.
+fn product_of_prime_powers(primes: &[usize], exponents: &[usize]) -> UBig {
+    debug_assert!(primes.len() == exponents.len());
+    let mut twos = 0;
+    let mut odd_primes = primes;
+    let mut odd_exponents = exponents;
+    if primes.first() == Some(&2) {
+        twos = exponents[0];
+        odd_primes = &primes[1..];
+        odd_exponents = &exponents[1..];
+    }
+
+    let max_exponent = odd_exponents.iter().copied().max().unwrap_or(0);
+    let mut result = UBig::from_word(1);
+    for bit in (0..math::bit_len(max_exponent)).rev() {
+        result = &result * &result;
+        let factors = odd_primes
+            .iter()
+            .zip(odd_exponents)
+            .filter(|(_, e)| *e >> bit & 1 != 0)
+            .map(|(p, _)| *p);
+        result *= product_of_small(factors);
+    }
+    result << twos
+}
+
+/// Product of small numbers.
+///
+/// Packs factors into `Word`s and then multiplies the `Word`s using a balanced product tree.
+fn product_of_small<I>(factors: I) -> UBig
+where
+    I: Iterator<Item = usize>,
+{
+    let mut words = Vec::new();
+    let mut acc: Word = 1;
+    for factor in factors {
+        match Word::try_from(factor) {
+            Ok(factor_word) => match acc.checked_mul(factor_word) {
+                Some(product) => acc = product,
+                None => {
+                    words.push(UBig::from_word(acc));
+                    acc = factor_word;
+                }
+            },
+            Err(_) => words.push(UBig::from(factor)),
+        }
+    }
+    words.push(UBig::from_word(acc));
+    product::product_balanced(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive::PrimitiveUnsigned;
+
+    #[test]
+    fn test_factorial_exponent() {
+        assert_eq!(factorial_exponent(0, 2), 0);
+        assert_eq!(factorial_exponent(10, 2), 8);
+        assert_eq!(factorial_exponent(100, 5), 24);
+        assert_eq!(
+            factorial_exponent(usize::MAX, 2),
+            usize::MAX - usize::BIT_SIZE as usize
+        );
+    }
+
+    #[test]
+    fn test_product_of_small() {
+        assert_eq!(product_of_small(0..0), UBig::from_word(1));
+        assert_eq!(product_of_small(1..=5), UBig::from_word(120));
+        let naive = (1..=100).fold(UBig::from_word(1), |acc, x| acc * UBig::from(x as u32));
+        assert_eq!(product_of_small(1..=100), naive);
+    }
+}
@@ -0,0 +1,98 @@
+//! Fibonacci and Lucas numbers.
+
+use crate::{math, ubig::UBig};
+
+impl UBig {
+    /// Fibonacci number: `F(0) = 0`, `F(1) = 1`, `F(n) = F(n-1) + F(n-2)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(UBig::fibonacci(0), ubig!(0));
+    /// assert_eq!(UBig::fibonacci(10), ubig!(55));
+    /// assert_eq!(UBig::fibonacci(100), ubig!(354224848179261915075));
+    /// ```
+    pub fn fibonacci(n: usize) -> UBig {
+        if n < 2 {
+            return UBig::from(n);
+        }
+        // Last doubling step with a single multiplication.
+        let k = n / 2;
+        let (f, g) = UBig::fibonacci2(k);
+        if n & 1 == 0 {
+            // F(2k) = F(k) * (F(k) + 2F(k-1))
+            let h = &f + (&g << 1);
+            f * h
+        } else {
+            // F(2k+1) = (2F(k) + F(k-1)) * (2F(k) - F(k-1)) + 2(-1)^k
+            let f2 = f << 1;
+            let product = (&f2 + &g) * (f2 - g);
+            if k & 1 == 0 {
+                product + UBig::from_word(2)
+            } else {
+                product - UBig::from_word(2)
+            }
+        }
+    }
+
+    /// A pair of consecutive Fibonacci numbers: `(F(n), F(n-1))`.
+    ///
+    /// For `n = 0` this is `(0, 1)`, since `F(-1) = 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(UBig::fibonacci2(0), (ubig!(0), ubig!(1)));
+    /// assert_eq!(UBig::fibonacci2(10), (ubig!(55), ubig!(34)));
+    /// ```
+    pub fn fibonacci2(n: usize) -> (UBig, UBig) {
+        if n == 0 {
+            return (UBig::from_word(0), UBig::from_word(1));
+        }
+        // Invariant: (f, g) = (F(k), F(k-1)) where k is the top bits of n.
+        let mut f = UBig::from_word(1);
+        let mut g = UBig::from_word(0);
+        let mut k_odd = true;
+        for bit in (0..math::bit_len(n) - 1).rev() {
+            // F(2k-1) = F(k)^2 + F(k-1)^2
+            // F(2k+1) = 4F(k)^2 - F(k-1)^2 + 2(-1)^k
+            // F(2k) = F(2k+1) - F(2k-1)
+            let f_sqr = &f * &f;
+            let g_sqr = &g * &g;
+            let f_2k_minus_1 = &f_sqr + &g_sqr;
+            let mut f_2k_plus_1 = (f_sqr << 2) - g_sqr;
+            if k_odd {
+                f_2k_plus_1 -= UBig::from_word(2);
+            } else {
+                f_2k_plus_1 += UBig::from_word(2);
+            }
+            let f_2k = &f_2k_plus_1 - &f_2k_minus_1;
+            k_odd = n >> bit & 1 != 0;
+            if k_odd {
+                f = f_2k_plus_1;
+                g = f_2k;
+            } else {
+                f = f_2k;
+                g = f_2k_minus_1;
+            }
+        }
+        (f, g)
+    }
+
+    /// Lucas number: `L(0) = 2`, `L(1) = 1`, `L(n) = L(n-1) + L(n-2)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// assert_eq!(UBig::lucas(0), ubig!(2));
+    /// assert_eq!(UBig::lucas(10), ubig!(123));
+    /// ```
+    pub fn lucas(n: usize) -> UBig {
+        // L(n) = F(n) + 2F(n-1)
+        let (f, g) = UBig::fibonacci2(n);
+        f + (g << 1)
+    }
+}
@@ -0,0 +1,543 @@
+//! Arithmetic on fixed width signed integers.
+
+use crate::{
+    div_ops,
+    fixed::{
+        uint_ops::{impl_binop, impl_shift},
+        Int, UInt,
+    },
+};
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign,
+    Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+
+impl<const WORDS: usize> Int<WORDS> {
+    /// Absolute value as an unsigned number. Never overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{Int, UInt};
+    /// assert_eq!(Int::<2>::NEG_ONE.unsigned_abs(), UInt::ONE);
+    /// assert_eq!(Int::<2>::MIN.unsigned_abs(), UInt::ONE << (UInt::<2>::BITS - 1));
+    /// ```
+    pub fn unsigned_abs(self) -> UInt<WORDS> {
+        if self.is_negative() {
+            self.wrapping_neg().0
+        } else {
+            self.0
+        }
+    }
+
+    /// Negation with overflow flag. Overflows only for `MIN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::ONE.overflowing_neg(), (Int::NEG_ONE, false));
+    /// assert_eq!(Int::<2>::MIN.overflowing_neg(), (Int::MIN, true));
+    /// ```
+    pub fn overflowing_neg(self) -> (Int<WORDS>, bool) {
+        let result = self.wrapping_neg();
+        (result, !self.is_zero() && result == self)
+    }
+
+    /// Checked negation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::ONE.checked_neg(), Some(Int::NEG_ONE));
+    /// assert_eq!(Int::<2>::MIN.checked_neg(), None);
+    /// ```
+    pub fn checked_neg(self) -> Option<Int<WORDS>> {
+        match self.overflowing_neg() {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping negation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::ONE.wrapping_neg(), Int::NEG_ONE);
+    /// assert_eq!(Int::<2>::MIN.wrapping_neg(), Int::MIN);
+    /// ```
+    pub fn wrapping_neg(self) -> Int<WORDS> {
+        Int(UInt::ZERO.wrapping_sub(self.0))
+    }
+
+    /// Checked absolute value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::NEG_ONE.checked_abs(), Some(Int::ONE));
+    /// assert_eq!(Int::<2>::MIN.checked_abs(), None);
+    /// ```
+    pub fn checked_abs(self) -> Option<Int<WORDS>> {
+        if self.is_negative() {
+            self.checked_neg()
+        } else {
+            Some(self)
+        }
+    }
+
+    /// Wrapping absolute value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::NEG_ONE.wrapping_abs(), Int::ONE);
+    /// assert_eq!(Int::<2>::MIN.wrapping_abs(), Int::MIN);
+    /// ```
+    pub fn wrapping_abs(self) -> Int<WORDS> {
+        Int(self.unsigned_abs())
+    }
+
+    /// Addition with overflow flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MAX.overflowing_add(Int::ONE), (Int::MIN, true));
+    /// assert_eq!(Int::<2>::MIN.overflowing_add(Int::ONE).1, false);
+    /// ```
+    pub fn overflowing_add(self, rhs: Int<WORDS>) -> (Int<WORDS>, bool) {
+        let result = Int(self.0.wrapping_add(rhs.0));
+        let overflow =
+            self.is_negative() == rhs.is_negative() && result.is_negative() != self.is_negative();
+        (result, overflow)
+    }
+
+    /// Checked addition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::NEG_ONE.checked_add(Int::ONE), Some(Int::ZERO));
+    /// assert_eq!(Int::<2>::MAX.checked_add(Int::ONE), None);
+    /// ```
+    pub fn checked_add(self, rhs: Int<WORDS>) -> Option<Int<WORDS>> {
+        match self.overflowing_add(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping addition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MAX.wrapping_add(Int::ONE), Int::MIN);
+    /// ```
+    pub fn wrapping_add(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        Int(self.0.wrapping_add(rhs.0))
+    }
+
+    /// Saturating addition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MAX.saturating_add(Int::ONE), Int::MAX);
+    /// assert_eq!(Int::<2>::MIN.saturating_add(Int::NEG_ONE), Int::MIN);
+    /// ```
+    pub fn saturating_add(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        match self.overflowing_add(rhs) {
+            (result, false) => result,
+            (_, true) => self.saturated(),
+        }
+    }
+
+    /// Subtraction with overflow flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MIN.overflowing_sub(Int::ONE), (Int::MAX, true));
+    /// assert_eq!(Int::<2>::MAX.overflowing_sub(Int::ONE).1, false);
+    /// ```
+    pub fn overflowing_sub(self, rhs: Int<WORDS>) -> (Int<WORDS>, bool) {
+        let result = Int(self.0.wrapping_sub(rhs.0));
+        let overflow =
+            self.is_negative() != rhs.is_negative() && result.is_negative() != self.is_negative();
+        (result, overflow)
+    }
+
+    /// Checked subtraction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::ZERO.checked_sub(Int::ONE), Some(Int::NEG_ONE));
+    /// assert_eq!(Int::<2>::MIN.checked_sub(Int::ONE), None);
+    /// ```
+    pub fn checked_sub(self, rhs: Int<WORDS>) -> Option<Int<WORDS>> {
+        match self.overflowing_sub(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping subtraction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MIN.wrapping_sub(Int::ONE), Int::MAX);
+    /// ```
+    pub fn wrapping_sub(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        Int(self.0.wrapping_sub(rhs.0))
+    }
+
+    /// Saturating subtraction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MIN.saturating_sub(Int::ONE), Int::MIN);
+    /// assert_eq!(Int::<2>::MAX.saturating_sub(Int::NEG_ONE), Int::MAX);
+    /// ```
+    pub fn saturating_sub(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        match self.overflowing_sub(rhs) {
+            (result, false) => result,
+            (_, true) => self.saturated(),
+        }
+    }
+
+    /// Multiplication with overflow flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MIN.overflowing_mul(Int::NEG_ONE), (Int::MIN, true));
+    /// assert_eq!(Int::<2>::MAX.overflowing_mul(Int::NEG_ONE), (Int::MIN + Int::ONE, false));
+    /// ```
+    pub fn overflowing_mul(self, rhs: Int<WORDS>) -> (Int<WORDS>, bool) {
+        let negative = self.is_negative() != rhs.is_negative();
+        let (magnitude, overflow) = self.unsigned_abs().overflowing_mul(rhs.unsigned_abs());
+        let result = Int(magnitude);
+        let result = if negative {
+            result.wrapping_neg()
+        } else {
+            result
+        };
+        let overflow = overflow || (!magnitude.is_zero() && result.is_negative() != negative);
+        (result, overflow)
+    }
+
+    /// Checked multiplication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MAX.checked_mul(Int::NEG_ONE), Some(Int::MIN + Int::ONE));
+    /// assert_eq!(Int::<2>::MIN.checked_mul(Int::NEG_ONE), None);
+    /// ```
+    pub fn checked_mul(self, rhs: Int<WORDS>) -> Option<Int<WORDS>> {
+        match self.overflowing_mul(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping multiplication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MIN.wrapping_mul(Int::NEG_ONE), Int::MIN);
+    /// ```
+    pub fn wrapping_mul(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        Int(self.0.wrapping_mul(rhs.0))
+    }
+
+    /// Saturating multiplication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MIN.saturating_mul(Int::NEG_ONE), Int::MAX);
+    /// assert_eq!(Int::<2>::MIN.saturating_mul(Int::MAX), Int::MIN);
+    /// ```
+    pub fn saturating_mul(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        match self.overflowing_mul(rhs) {
+            (result, false) => result,
+            (_, true) => {
+                if self.is_negative() == rhs.is_negative() {
+                    Int::MAX
+                } else {
+                    Int::MIN
+                }
+            }
+        }
+    }
+
+    /// Truncated division and remainder, or `None` if `rhs` is 0 or the quotient overflows.
+    ///
+    /// The quotient is rounded towards zero and the remainder has the sign of `self`, as for
+    /// primitive integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// # use core::convert::TryFrom;
+    /// let a = Int::<2>::try_from(-23).unwrap();
+    /// let b = Int::<2>::try_from(10).unwrap();
+    /// assert_eq!(
+    ///     a.checked_div_rem(b),
+    ///     Some((Int::try_from(-2).unwrap(), Int::try_from(-3).unwrap()))
+    /// );
+    /// assert_eq!(a.checked_div_rem(Int::ZERO), None);
+    /// assert_eq!(Int::<2>::MIN.checked_div_rem(Int::NEG_ONE), None);
+    /// ```
+    pub fn checked_div_rem(self, rhs: Int<WORDS>) -> Option<(Int<WORDS>, Int<WORDS>)> {
+        let (q, r) = self.unsigned_abs().checked_div_rem(rhs.unsigned_abs())?;
+        let q = if self.is_negative() != rhs.is_negative() {
+            Int(q).wrapping_neg()
+        } else {
+            Int(q)
+        };
+        let r = if self.is_negative() {
+            Int(r).wrapping_neg()
+        } else {
+            Int(r)
+        };
+        // Only MIN / -1 overflows, giving MIN.
+        if self == Int::MIN && rhs == Int::NEG_ONE {
+            return None;
+        }
+        Some((q, r))
+    }
+
+    /// Checked truncated division, or `None` if `rhs` is 0 or the quotient overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MIN.checked_div(Int::ONE), Some(Int::MIN));
+    /// assert_eq!(Int::<2>::MIN.checked_div(Int::NEG_ONE), None);
+    /// ```
+    pub fn checked_div(self, rhs: Int<WORDS>) -> Option<Int<WORDS>> {
+        self.checked_div_rem(rhs).map(|(q, _)| q)
+    }
+
+    /// Checked truncated remainder, or `None` if `rhs` is 0 or the quotient overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::NEG_ONE.checked_rem(Int::MAX), Some(Int::NEG_ONE));
+    /// assert_eq!(Int::<2>::ONE.checked_rem(Int::ZERO), None);
+    /// ```
+    pub fn checked_rem(self, rhs: Int<WORDS>) -> Option<Int<WORDS>> {
+        self.checked_div_rem(rhs).map(|(_, r)| r)
+    }
+
+    /// Checked shift left, or `None` if `n >= BITS`. Bits shifted out are lost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::NEG_ONE.checked_shl(Int::<2>::BITS - 1), Some(Int::MIN));
+    /// assert_eq!(Int::<2>::ONE.checked_shl(Int::<2>::BITS), None);
+    /// ```
+    pub fn checked_shl(self, n: usize) -> Option<Int<WORDS>> {
+        self.0.checked_shl(n).map(Int)
+    }
+
+    /// Wrapping shift left by `n % BITS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::NEG_ONE.wrapping_shl(Int::<2>::BITS), Int::NEG_ONE);
+    /// ```
+    pub fn wrapping_shl(self, n: usize) -> Int<WORDS> {
+        Int(self.0.wrapping_shl(n))
+    }
+
+    /// Checked arithmetic shift right, or `None` if `n >= BITS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::MIN.checked_shr(Int::<2>::BITS - 1), Some(Int::NEG_ONE));
+    /// assert_eq!(Int::<2>::ONE.checked_shr(Int::<2>::BITS), None);
+    /// ```
+    pub fn checked_shr(self, n: usize) -> Option<Int<WORDS>> {
+        if self.is_negative() {
+            // -1 - ((-1 - x) >> n) rounds towards negative infinity.
+            (!self.0).checked_shr(n).map(|x| Int(!x))
+        } else {
+            self.0.checked_shr(n).map(Int)
+        }
+    }
+
+    /// Wrapping arithmetic shift right by `n % BITS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert_eq!(Int::<2>::NEG_ONE.wrapping_shr(Int::<2>::BITS + 1), Int::NEG_ONE);
+    /// ```
+    pub fn wrapping_shr(self, n: usize) -> Int<WORDS> {
+        if self.is_negative() {
+            Int(!(!self.0).wrapping_shr(n))
+        } else {
+            Int(self.0.wrapping_shr(n))
+        }
+    }
+
+    /// The value an overflowing result with the sign of `self` saturates to.
+    fn saturated(self) -> Int<WORDS> {
+        if self.is_negative() {
+            Int::MIN
+        } else {
+            Int::MAX
+        }
+    }
+
+    fn add_impl(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        self.checked_add(rhs)
+            .unwrap_or_else(|| panic!("Int addition overflow"))
+    }
+
+    fn sub_impl(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        self.checked_sub(rhs)
+            .unwrap_or_else(|| panic!("Int subtraction overflow"))
+    }
+
+    fn mul_impl(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        self.checked_mul(rhs)
+            .unwrap_or_else(|| panic!("Int multiplication overflow"))
+    }
+
+    fn div_rem_impl(self, rhs: Int<WORDS>) -> (Int<WORDS>, Int<WORDS>) {
+        if rhs.is_zero() {
+            div_ops::panic_divide_by_0();
+        }
+        self.checked_div_rem(rhs)
+            .unwrap_or_else(|| panic!("Int division overflow"))
+    }
+
+    fn div_impl(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        self.div_rem_impl(rhs).0
+    }
+
+    fn rem_impl(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        self.div_rem_impl(rhs).1
+    }
+
+    fn bitand_impl(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        Int(self.0 & rhs.0)
+    }
+
+    fn bitor_impl(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        Int(self.0 | rhs.0)
+    }
+
+    fn bitxor_impl(self, rhs: Int<WORDS>) -> Int<WORDS> {
+        Int(self.0 ^ rhs.0)
+    }
+
+    fn shl_impl(self, n: usize) -> Int<WORDS> {
+        Int(self.0.shl_impl(n))
+    }
+
+    fn shr_impl(self, n: usize) -> Int<WORDS> {
+        self.checked_shr(n)
+            .unwrap_or_else(|| panic!("Int shift overflow"))
+    }
+
+    fn neg_impl(self) -> Int<WORDS> {
+        self.checked_neg()
+            .unwrap_or_else(|| panic!("Int negation overflow"))
+    }
+}
+
+impl<const WORDS: usize> Neg for Int<WORDS> {
+    type Output = Int<WORDS>;
+
+    fn neg(self) -> Int<WORDS> {
+        self.neg_impl()
+    }
+}
+
+impl<const WORDS: usize> Neg for &Int<WORDS> {
+    type Output = Int<WORDS>;
+
+    fn neg(self) -> Int<WORDS> {
+        (*self).neg_impl()
+    }
+}
+
+impl<const WORDS: usize> Not for Int<WORDS> {
+    type Output = Int<WORDS>;
+
+    fn not(self) -> Int<WORDS> {
+        Int(!self.0)
+    }
+}
+
+impl<const WORDS: usize> Not for &Int<WORDS> {
+    type Output = Int<WORDS>;
+
+    fn not(self) -> Int<WORDS> {
+        Int(!self.0)
+    }
+}
+
+impl_binop!(Int, Add, add, AddAssign, add_assign, add_impl);
+impl_binop!(Int, Sub, sub, SubAssign, sub_assign, sub_impl);
+impl_binop!(Int, Mul, mul, MulAssign, mul_assign, mul_impl);
+impl_binop!(Int, Div, div, DivAssign, div_assign, div_impl);
+impl_binop!(Int, Rem, rem, RemAssign, rem_assign, rem_impl);
+impl_binop!(
+    Int,
+    BitAnd,
+    bitand,
+    BitAndAssign,
+    bitand_assign,
+    bitand_impl
+);
+impl_binop!(Int, BitOr, bitor, BitOrAssign, bitor_assign, bitor_impl);
+impl_binop!(
+    Int,
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    bitxor_impl
+);
+impl_shift!(Int, Shl, shl, ShlAssign, shl_assign, shl_impl);
+impl_shift!(Int, Shr, shr, ShrAssign, shr_assign, shr_impl);
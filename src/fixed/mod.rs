@@ -0,0 +1,455 @@
+//! Fixed width integers.
+//!
+//! [UInt] and [Int] store a fixed number of machine words inline, without heap allocation. They
+//! use the same arithmetic kernels as [UBig](crate::UBig) and [IBig](crate::IBig). Like primitive
+//! integers they have wrapping, checked, overflowing and saturating operations. The operators
+//! panic on overflow.
+//!
+//! A machine word has 64 bits on 64-bit platforms and 32 bits on most others, so `UInt<WORDS>`
+//! has `WORDS * 64` or `WORDS * 32` bits. The aliases [U128] to [U4096] and [I128] to [I4096]
+//! have the same number of bits on every platform.
+//!
+//! [FixedModuloRing] does modular arithmetic in fixed width.
+//!
+//! # Examples
+//!
+//! ```
+//! # use ibig::{fixed::U256, ubig, UBig};
+//! # use core::convert::TryFrom;
+//! let a = U256::try_from(ubig!(1) << 200).unwrap();
+//! let b = U256::try_from(ubig!(3)).unwrap();
+//! assert_eq!(U256::BITS, 256);
+//! assert_eq!(UBig::from(a * b), ubig!(3) << 200);
+//! assert_eq!(a.checked_mul(a), None);
+//! ```
+
+use crate::{
+    arch::word::Word,
+    cmp,
+    error::OutOfBoundsError,
+    ibig::IBig,
+    memory::{self, Memory, MemoryAllocation, TempAllocator},
+    primitive::{WORD_BITS, WORD_BITS_USIZE, WORD_BYTES},
+    sign::Sign::*,
+    ubig::UBig,
+};
+use alloc::alloc::Layout;
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt::{self, Binary, Debug, Display, Formatter, LowerHex, Octal, UpperHex},
+    mem::MaybeUninit,
+};
+
+mod int_ops;
+mod modular;
+mod uint_ops;
+
+pub use modular::FixedModuloRing;
+
+/// Fixed width unsigned integer of `WORDS` machine words.
+///
+/// For a number of bits independent of the platform, use the aliases such as [U256].
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{fixed::UInt, ubig};
+/// # use core::convert::TryFrom;
+/// let a = UInt::<2>::try_from(ubig!(1000)).unwrap();
+/// assert_eq!(a + a, UInt::try_from(ubig!(2000)).unwrap());
+/// assert_eq!(UInt::<2>::MAX.checked_add(a), None);
+/// ```
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct UInt<const WORDS: usize>([Word; WORDS]);
+
+/// Fixed width signed integer of `WORDS` machine words, in two's complement.
+///
+/// For a number of bits independent of the platform, use the aliases such as [I256].
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{fixed::Int, ibig};
+/// # use core::convert::TryFrom;
+/// let a = Int::<2>::try_from(ibig!(-1000)).unwrap();
+/// assert_eq!(a * a, Int::try_from(ibig!(1000000)).unwrap());
+/// assert_eq!(Int::<2>::MIN.checked_neg(), None);
+/// ```
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Int<const WORDS: usize>(UInt<WORDS>);
+
+/// 128-bit unsigned integer.
+pub type U128 = UInt<{ 128 / WORD_BITS_USIZE }>;
+/// 256-bit unsigned integer.
+pub type U256 = UInt<{ 256 / WORD_BITS_USIZE }>;
+/// 512-bit unsigned integer.
+pub type U512 = UInt<{ 512 / WORD_BITS_USIZE }>;
+/// 1024-bit unsigned integer.
+pub type U1024 = UInt<{ 1024 / WORD_BITS_USIZE }>;
+/// 2048-bit unsigned integer.
+pub type U2048 = UInt<{ 2048 / WORD_BITS_USIZE }>;
+/// 4096-bit unsigned integer.
+pub type U4096 = UInt<{ 4096 / WORD_BITS_USIZE }>;
+
+/// 128-bit signed integer.
+pub type I128 = Int<{ 128 / WORD_BITS_USIZE }>;
+/// 256-bit signed integer.
+pub type I256 = Int<{ 256 / WORD_BITS_USIZE }>;
+/// 512-bit signed integer.
+pub type I512 = Int<{ 512 / WORD_BITS_USIZE }>;
+/// 1024-bit signed integer.
+pub type I1024 = Int<{ 1024 / WORD_BITS_USIZE }>;
+/// 2048-bit signed integer.
+pub type I2048 = Int<{ 2048 / WORD_BITS_USIZE }>;
+/// 4096-bit signed integer.
+pub type I4096 = Int<{ 4096 / WORD_BITS_USIZE }>;
+
+impl<const WORDS: usize> UInt<WORDS> {
+    /// Number of bits.
+    pub const BITS: usize = WORDS * WORD_BITS_USIZE;
+
+    /// 0.
+    pub const ZERO: UInt<WORDS> = UInt([0; WORDS]);
+
+    /// 1.
+    pub const ONE: UInt<WORDS> = {
+        let mut words = [0; WORDS];
+        words[0] = 1;
+        UInt(words)
+    };
+
+    /// The largest value, `2^BITS - 1`.
+    pub const MAX: UInt<WORDS> = UInt([Word::MAX; WORDS]);
+
+    /// Is it zero?
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert!(UInt::<2>::ZERO.is_zero());
+    /// assert!(!UInt::<2>::ONE.is_zero());
+    /// ```
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|word| *word == 0)
+    }
+
+    /// Length in bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::ZERO.bit_len(), 0);
+    /// assert_eq!(UInt::<2>::MAX.bit_len(), UInt::<2>::BITS);
+    /// ```
+    pub fn bit_len(&self) -> usize {
+        let len = self.len();
+        if len == 0 {
+            0
+        } else {
+            len * WORD_BITS_USIZE - self.0[len - 1].leading_zeros() as usize
+        }
+    }
+
+    /// Returns true if the `n`-th bit is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert!(UInt::<2>::ONE.bit(0));
+    /// assert!(!UInt::<2>::ONE.bit(1));
+    /// assert!(!UInt::<2>::MAX.bit(1000));
+    /// ```
+    pub fn bit(&self, n: usize) -> bool {
+        let idx = n / WORD_BITS_USIZE;
+        idx < WORDS && self.0[idx] & (1 << (n % WORD_BITS_USIZE)) != 0
+    }
+
+    /// Number of words without leading zeros.
+    fn len(&self) -> usize {
+        WORDS - self.0.iter().rev().take_while(|word| **word == 0).count()
+    }
+
+    /// The words without leading zeros.
+    fn words(&self) -> &[Word] {
+        &self.0[..self.len()]
+    }
+
+    /// Construct from words, which must fit.
+    fn from_words(words: &[Word]) -> UInt<WORDS> {
+        debug_assert!(words.len() <= WORDS);
+        let mut result = UInt::ZERO;
+        result.0[..words.len()].copy_from_slice(words);
+        result
+    }
+}
+
+impl<const WORDS: usize> Int<WORDS> {
+    /// Number of bits.
+    pub const BITS: usize = UInt::<WORDS>::BITS;
+
+    /// 0.
+    pub const ZERO: Int<WORDS> = Int(UInt::ZERO);
+
+    /// 1.
+    pub const ONE: Int<WORDS> = Int(UInt::ONE);
+
+    /// -1.
+    pub const NEG_ONE: Int<WORDS> = Int(UInt::MAX);
+
+    /// The smallest value, `-2^(BITS-1)`.
+    pub const MIN: Int<WORDS> = {
+        let mut words = [0; WORDS];
+        words[WORDS - 1] = 1 << (WORD_BITS - 1);
+        Int(UInt(words))
+    };
+
+    /// The largest value, `2^(BITS-1) - 1`.
+    pub const MAX: Int<WORDS> = {
+        let mut words = [Word::MAX; WORDS];
+        words[WORDS - 1] = Word::MAX >> 1;
+        Int(UInt(words))
+    };
+
+    /// Is it negative?
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert!(Int::<2>::NEG_ONE.is_negative());
+    /// assert!(!Int::<2>::ZERO.is_negative());
+    /// ```
+    pub fn is_negative(&self) -> bool {
+        WORDS != 0 && self.0 .0[WORDS - 1] >> (WORD_BITS - 1) != 0
+    }
+
+    /// Is it zero?
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::Int;
+    /// assert!(Int::<2>::ZERO.is_zero());
+    /// assert!(!Int::<2>::NEG_ONE.is_zero());
+    /// ```
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// The same bits as an unsigned number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{Int, UInt};
+    /// assert_eq!(Int::<2>::NEG_ONE.to_bits(), UInt::<2>::MAX);
+    /// ```
+    pub fn to_bits(self) -> UInt<WORDS> {
+        self.0
+    }
+
+    /// The same bits as a signed number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{Int, UInt};
+    /// assert_eq!(Int::from_bits(UInt::<2>::MAX), Int::<2>::NEG_ONE);
+    /// ```
+    pub fn from_bits(bits: UInt<WORDS>) -> Int<WORDS> {
+        Int(bits)
+    }
+}
+
+impl<const WORDS: usize> Default for UInt<WORDS> {
+    /// 0
+    fn default() -> UInt<WORDS> {
+        UInt::ZERO
+    }
+}
+
+impl<const WORDS: usize> Default for Int<WORDS> {
+    /// 0
+    fn default() -> Int<WORDS> {
+        Int::ZERO
+    }
+}
+
+impl<const WORDS: usize> Ord for UInt<WORDS> {
+    fn cmp(&self, other: &UInt<WORDS>) -> Ordering {
+        cmp::cmp_same_len(&self.0, &other.0)
+    }
+}
+
+impl<const WORDS: usize> PartialOrd for UInt<WORDS> {
+    fn partial_cmp(&self, other: &UInt<WORDS>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const WORDS: usize> Ord for Int<WORDS> {
+    fn cmp(&self, other: &Int<WORDS>) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            // Two's complement of numbers with the same sign compares like unsigned.
+            _ => self.0.cmp(&other.0),
+        }
+    }
+}
+
+impl<const WORDS: usize> PartialOrd for Int<WORDS> {
+    fn partial_cmp(&self, other: &Int<WORDS>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const WORDS: usize> From<UInt<WORDS>> for UBig {
+    fn from(x: UInt<WORDS>) -> UBig {
+        UBig::from(&x)
+    }
+}
+
+impl<const WORDS: usize> From<&UInt<WORDS>> for UBig {
+    fn from(x: &UInt<WORDS>) -> UBig {
+        UBig::from_words(x.words())
+    }
+}
+
+impl<const WORDS: usize> TryFrom<UBig> for UInt<WORDS> {
+    type Error = OutOfBoundsError;
+
+    fn try_from(x: UBig) -> Result<UInt<WORDS>, OutOfBoundsError> {
+        UInt::try_from(&x)
+    }
+}
+
+impl<const WORDS: usize> TryFrom<&UBig> for UInt<WORDS> {
+    type Error = OutOfBoundsError;
+
+    fn try_from(x: &UBig) -> Result<UInt<WORDS>, OutOfBoundsError> {
+        let words = x.as_words();
+        if words.len() > WORDS {
+            Err(OutOfBoundsError)
+        } else {
+            Ok(UInt::from_words(words))
+        }
+    }
+}
+
+impl<const WORDS: usize> From<Int<WORDS>> for IBig {
+    fn from(x: Int<WORDS>) -> IBig {
+        IBig::from(&x)
+    }
+}
+
+impl<const WORDS: usize> From<&Int<WORDS>> for IBig {
+    fn from(x: &Int<WORDS>) -> IBig {
+        let sign = if x.is_negative() { Negative } else { Positive };
+        IBig::from_sign_magnitude(sign, UBig::from(x.unsigned_abs()))
+    }
+}
+
+impl<const WORDS: usize> TryFrom<IBig> for Int<WORDS> {
+    type Error = OutOfBoundsError;
+
+    fn try_from(x: IBig) -> Result<Int<WORDS>, OutOfBoundsError> {
+        Int::try_from(&x)
+    }
+}
+
+impl<const WORDS: usize> TryFrom<&IBig> for Int<WORDS> {
+    type Error = OutOfBoundsError;
+
+    fn try_from(x: &IBig) -> Result<Int<WORDS>, OutOfBoundsError> {
+        let magnitude = UInt::<WORDS>::try_from(x.magnitude())?;
+        let (value, fits) = match x.sign() {
+            Positive => (Int(magnitude), !Int(magnitude).is_negative()),
+            Negative => {
+                let value = Int(magnitude).wrapping_neg();
+                (value, value.is_negative())
+            }
+        };
+        if fits || magnitude.is_zero() {
+            Ok(value)
+        } else {
+            Err(OutOfBoundsError)
+        }
+    }
+}
+
+/// Implement `TryFrom` between a primitive type and a fixed width type through a big integer.
+macro_rules! impl_try_from_primitive {
+    ($fixed:ident, $big:ty, $($t:ty)*) => {$(
+        impl<const WORDS: usize> TryFrom<$t> for $fixed<WORDS> {
+            type Error = OutOfBoundsError;
+
+            fn try_from(x: $t) -> Result<$fixed<WORDS>, OutOfBoundsError> {
+                $fixed::try_from(<$big>::from(x))
+            }
+        }
+
+        impl<const WORDS: usize> TryFrom<$fixed<WORDS>> for $t {
+            type Error = OutOfBoundsError;
+
+            fn try_from(x: $fixed<WORDS>) -> Result<$t, OutOfBoundsError> {
+                <$t>::try_from(<$big>::from(x))
+            }
+        }
+    )*};
+}
+
+impl_try_from_primitive!(UInt, UBig, u8 u16 u32 u64 u128 usize);
+impl_try_from_primitive!(Int, IBig, i8 i16 i32 i64 i128 isize);
+
+/// Implement formatting traits through a big integer.
+macro_rules! impl_fmt {
+    ($fixed:ident, $big:ty, $($trait:ident)*) => {$(
+        impl<const WORDS: usize> $trait for $fixed<WORDS> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                $trait::fmt(&<$big>::from(self), f)
+            }
+        }
+    )*};
+}
+
+impl_fmt!(UInt, UBig, Binary Debug Display LowerHex Octal UpperHex);
+impl_fmt!(Int, IBig, Binary Debug Display LowerHex Octal UpperHex);
+
+/// Number of `Word`s of stack space for temporary values.
+///
+/// Larger temporaries are allocated on the heap.
+const STACK_WORDS: usize = 512;
+
+/// Call `f` with a zeroed temporary of `len` words and memory for `layout`.
+///
+/// Uses the stack when it is large enough.
+fn with_temp<F, R>(len: usize, layout: Layout, f: F) -> R
+where
+    F: FnOnce(&mut [Word], &mut Memory) -> R,
+{
+    let total = memory::add_layout(memory::array_layout::<Word>(len), layout);
+    if total.size() + total.align() <= STACK_WORDS * WORD_BYTES {
+        let mut stack = MaybeUninit::<[Word; STACK_WORDS]>::uninit();
+        // Safe because Memory only hands out memory after initializing it, and the stack array is
+        // not used for anything else.
+        let mut memory = unsafe {
+            Memory::from_raw_parts(
+                stack.as_mut_ptr() as *mut u8,
+                STACK_WORDS * WORD_BYTES,
+                TempAllocator::global(),
+            )
+        };
+        let (temp, mut memory) = memory.allocate_slice_fill::<Word>(len, 0);
+        f(temp, &mut memory)
+    } else {
+        let mut allocation = MemoryAllocation::new(total);
+        let mut memory = allocation.memory();
+        let (temp, mut memory) = memory.allocate_slice_fill::<Word>(len, 0);
+        f(temp, &mut memory)
+    }
+}
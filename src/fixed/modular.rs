@@ -0,0 +1,290 @@
+//! Modular arithmetic in fixed width.
+
+use crate::{
+    arch::word::Word,
+    div,
+    fast_divide::FastDivideNormalized,
+    fixed::{with_temp, UInt},
+    memory, mul,
+    primitive::{extend_word, split_double_word},
+    shift,
+    sign::Sign::*,
+};
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// A ring of integers modulo a fixed width modulus.
+///
+/// Unlike [ModuloRing](crate::modular::ModuloRing), values are plain [UInt]s. Arguments of the
+/// arithmetic operations must already be reduced, that is less than the modulus.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::fixed::{FixedModuloRing, UInt};
+/// # use core::convert::TryFrom;
+/// let ring = FixedModuloRing::new(&UInt::<2>::try_from(100u8).unwrap());
+/// let a = ring.reduce(UInt::try_from(123u8).unwrap());
+/// assert_eq!(a, UInt::try_from(23u8).unwrap());
+/// assert_eq!(ring.mul(a, a), UInt::try_from(29u8).unwrap());
+/// ```
+#[derive(Clone)]
+pub struct FixedModuloRing<const WORDS: usize> {
+    modulus: UInt<WORDS>,
+    /// Number of words in the modulus.
+    len: usize,
+    /// The modulus shifted left so that the top bit of word `len - 1` is 1.
+    normalized_modulus: UInt<WORDS>,
+    shift: u32,
+    fast_div_top: FastDivideNormalized,
+}
+
+impl<const WORDS: usize> FixedModuloRing<WORDS> {
+    /// Create a new ring of integers modulo `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{FixedModuloRing, UInt};
+    /// let ring = FixedModuloRing::new(&UInt::<2>::MAX);
+    /// assert_eq!(ring.modulus(), UInt::MAX);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn new(n: &UInt<WORDS>) -> FixedModuloRing<WORDS> {
+        let len = n.len();
+        if len == 0 {
+            panic!("FixedModuloRing::new(0)");
+        }
+        let shift = n.0[len - 1].leading_zeros();
+        let mut normalized_modulus = *n;
+        let overflow = shift::shl_in_place(&mut normalized_modulus.0[..len], shift);
+        debug_assert!(overflow == 0);
+        FixedModuloRing {
+            modulus: *n,
+            len,
+            normalized_modulus,
+            shift,
+            fast_div_top: FastDivideNormalized::new(normalized_modulus.0[len - 1]),
+        }
+    }
+
+    /// The modulus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{FixedModuloRing, UInt};
+    /// let ring = FixedModuloRing::new(&UInt::<2>::MAX);
+    /// assert_eq!(ring.modulus(), UInt::MAX);
+    /// ```
+    pub fn modulus(&self) -> UInt<WORDS> {
+        self.modulus
+    }
+
+    /// Reduce `x` modulo the modulus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{FixedModuloRing, UInt};
+    /// # use core::convert::TryFrom;
+    /// let ring = FixedModuloRing::new(&UInt::<2>::try_from(100u8).unwrap());
+    /// assert_eq!(ring.reduce(UInt::try_from(1234u16).unwrap()), UInt::try_from(34u8).unwrap());
+    /// ```
+    pub fn reduce(&self, x: UInt<WORDS>) -> UInt<WORDS> {
+        if x < self.modulus {
+            x
+        } else {
+            self.reduce_words(x.words())
+        }
+    }
+
+    /// Modular addition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{FixedModuloRing, UInt};
+    /// let ring = FixedModuloRing::new(&UInt::<2>::MAX);
+    /// let a = UInt::MAX - UInt::ONE;
+    /// assert_eq!(ring.add(a, a), UInt::MAX - UInt::ONE - UInt::ONE);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is not reduced.
+    pub fn add(&self, a: UInt<WORDS>, b: UInt<WORDS>) -> UInt<WORDS> {
+        self.check_reduced(&a);
+        self.check_reduced(&b);
+        let (sum, overflow) = a.overflowing_add(b);
+        if overflow || sum >= self.modulus {
+            sum.wrapping_sub(self.modulus)
+        } else {
+            sum
+        }
+    }
+
+    /// Modular subtraction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{FixedModuloRing, UInt};
+    /// let ring = FixedModuloRing::new(&UInt::<2>::MAX);
+    /// assert_eq!(ring.sub(UInt::ZERO, UInt::ONE), UInt::MAX - UInt::ONE);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is not reduced.
+    pub fn sub(&self, a: UInt<WORDS>, b: UInt<WORDS>) -> UInt<WORDS> {
+        self.check_reduced(&a);
+        self.check_reduced(&b);
+        match a.overflowing_sub(b) {
+            (diff, false) => diff,
+            (diff, true) => diff.wrapping_add(self.modulus),
+        }
+    }
+
+    /// Modular negation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{FixedModuloRing, UInt};
+    /// let ring = FixedModuloRing::new(&UInt::<2>::MAX);
+    /// assert_eq!(ring.neg(UInt::ONE), UInt::MAX - UInt::ONE);
+    /// assert_eq!(ring.neg(UInt::ZERO), UInt::ZERO);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` is not reduced.
+    pub fn neg(&self, a: UInt<WORDS>) -> UInt<WORDS> {
+        self.sub(UInt::ZERO, a)
+    }
+
+    /// Modular multiplication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{FixedModuloRing, UInt};
+    /// let ring = FixedModuloRing::new(&UInt::<2>::MAX);
+    /// let a = UInt::MAX - UInt::ONE;
+    /// assert_eq!(ring.mul(a, a), UInt::ONE);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is not reduced.
+    pub fn mul(&self, a: UInt<WORDS>, b: UInt<WORDS>) -> UInt<WORDS> {
+        self.check_reduced(&a);
+        self.check_reduced(&b);
+        let a = a.words();
+        let b = b.words();
+        let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+        if b.is_empty() {
+            return UInt::ZERO;
+        }
+        let n = self.len;
+        if n == 1 {
+            let (low, high) = split_double_word(extend_word(a[0]) * extend_word(b[0]));
+            return self.reduce_words(&[low, high]);
+        }
+        let layout = memory::max_layout(
+            mul::memory_requirement_exact(b.len()),
+            div::memory_requirement_exact(2 * n + 1, n),
+        );
+        with_temp(2 * n + 1, layout, |temp, memory| {
+            let overflow =
+                mul::add_signed_mul(&mut temp[..a.len() + b.len()], Positive, a, b, memory);
+            debug_assert!(overflow == 0);
+            self.reduce_in_place(temp, memory)
+        })
+    }
+
+    /// Modular exponentiation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::{FixedModuloRing, UInt};
+    /// # use core::convert::TryFrom;
+    /// let ring = FixedModuloRing::new(&UInt::<2>::try_from(100u8).unwrap());
+    /// let a = UInt::try_from(3u8).unwrap();
+    /// let e = UInt::<1>::try_from(5u8).unwrap();
+    /// assert_eq!(ring.pow(a, &e), UInt::try_from(43u8).unwrap());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` is not reduced.
+    pub fn pow<const E: usize>(&self, a: UInt<WORDS>, exp: &UInt<E>) -> UInt<WORDS> {
+        self.check_reduced(&a);
+        let mut result = self.reduce(UInt::ONE);
+        for i in (0..exp.bit_len()).rev() {
+            result = self.mul(result, result);
+            if exp.bit(i) {
+                result = self.mul(result, a);
+            }
+        }
+        result
+    }
+
+    fn check_reduced(&self, x: &UInt<WORDS>) {
+        assert!(*x < self.modulus, "FixedModuloRing: value not reduced");
+    }
+
+    /// Reduce a number of at most `2 * WORDS` words.
+    fn reduce_words(&self, words: &[Word]) -> UInt<WORDS> {
+        let n = self.len;
+        if n == 1 {
+            let rem = div::fast_rem_by_normalized_word(words, self.fast_div_top);
+            let (_, rem) = self.fast_div_top.div_rem(extend_word(rem) << self.shift);
+            return UInt::from_words(&[rem >> self.shift]);
+        }
+        let len = words.len().max(n) + 1;
+        let layout = div::memory_requirement_exact(len, n);
+        with_temp(len, layout, |temp, memory| {
+            temp[..words.len()].copy_from_slice(words);
+            self.reduce_in_place(temp, memory)
+        })
+    }
+
+    /// Reduce `words`, whose top word must be 0.
+    ///
+    /// The modulus must have at least 2 words.
+    fn reduce_in_place(&self, words: &mut [Word], memory: &mut memory::Memory) -> UInt<WORDS> {
+        let n = self.len;
+        debug_assert!(n >= 2 && words.len() > n && *words.last().unwrap() == 0);
+        let (top, low) = words.split_last_mut().unwrap();
+        *top = shift::shl_in_place(low, self.shift);
+        let overflow = div::div_rem_in_place(
+            words,
+            &self.normalized_modulus.0[..n],
+            self.fast_div_top,
+            memory,
+        );
+        debug_assert!(!overflow);
+        let low_bits = shift::shr_in_place(&mut words[..n], self.shift);
+        debug_assert!(low_bits == 0);
+        UInt::from_words(&words[..n])
+    }
+}
+
+impl<const WORDS: usize> Debug for FixedModuloRing<WORDS> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("mod ")?;
+        Debug::fmt(&self.modulus, f)
+    }
+}
+
+impl<const WORDS: usize> Display for FixedModuloRing<WORDS> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("mod ")?;
+        Display::fmt(&self.modulus, f)
+    }
+}
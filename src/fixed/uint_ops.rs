@@ -0,0 +1,592 @@
+//! Arithmetic on fixed width unsigned integers.
+
+use crate::{
+    add, div, div_ops,
+    fixed::{with_temp, UInt},
+    mul,
+    primitive::WORD_BITS_USIZE,
+    shift,
+    sign::Sign::*,
+};
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign,
+    Mul, MulAssign, Not, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+
+impl<const WORDS: usize> UInt<WORDS> {
+    /// Addition with overflow flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::MAX.overflowing_add(UInt::ONE), (UInt::ZERO, true));
+    /// ```
+    pub fn overflowing_add(self, rhs: UInt<WORDS>) -> (UInt<WORDS>, bool) {
+        let mut result = self;
+        let overflow = add::add_same_len_in_place(&mut result.0, &rhs.0);
+        (result, overflow)
+    }
+
+    /// Checked addition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// # use core::convert::TryFrom;
+    /// assert_eq!(UInt::<2>::ONE.checked_add(UInt::ONE), Some(UInt::try_from(2u8).unwrap()));
+    /// assert_eq!(UInt::<2>::MAX.checked_add(UInt::ONE), None);
+    /// ```
+    pub fn checked_add(self, rhs: UInt<WORDS>) -> Option<UInt<WORDS>> {
+        match self.overflowing_add(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping addition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::MAX.wrapping_add(UInt::ONE), UInt::ZERO);
+    /// ```
+    pub fn wrapping_add(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.overflowing_add(rhs).0
+    }
+
+    /// Saturating addition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::MAX.saturating_add(UInt::ONE), UInt::MAX);
+    /// ```
+    pub fn saturating_add(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.checked_add(rhs).unwrap_or(UInt::MAX)
+    }
+
+    /// Subtraction with overflow flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::ZERO.overflowing_sub(UInt::ONE), (UInt::MAX, true));
+    /// ```
+    pub fn overflowing_sub(self, rhs: UInt<WORDS>) -> (UInt<WORDS>, bool) {
+        let mut result = self;
+        let overflow = add::sub_same_len_in_place(&mut result.0, &rhs.0);
+        (result, overflow)
+    }
+
+    /// Checked subtraction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::ONE.checked_sub(UInt::ONE), Some(UInt::ZERO));
+    /// assert_eq!(UInt::<2>::ZERO.checked_sub(UInt::ONE), None);
+    /// ```
+    pub fn checked_sub(self, rhs: UInt<WORDS>) -> Option<UInt<WORDS>> {
+        match self.overflowing_sub(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping subtraction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::ZERO.wrapping_sub(UInt::ONE), UInt::MAX);
+    /// ```
+    pub fn wrapping_sub(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.overflowing_sub(rhs).0
+    }
+
+    /// Saturating subtraction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::ZERO.saturating_sub(UInt::ONE), UInt::ZERO);
+    /// ```
+    pub fn saturating_sub(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.checked_sub(rhs).unwrap_or(UInt::ZERO)
+    }
+
+    /// Multiplication with overflow flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// # use core::convert::TryFrom;
+    /// let two = UInt::<2>::try_from(2u8).unwrap();
+    /// assert_eq!(UInt::<2>::MAX.overflowing_mul(two), (UInt::MAX - UInt::ONE, true));
+    /// ```
+    pub fn overflowing_mul(self, rhs: UInt<WORDS>) -> (UInt<WORDS>, bool) {
+        let a = self.words();
+        let b = rhs.words();
+        let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+        match b.len() {
+            0 => (UInt::ZERO, false),
+            1 => {
+                let mut result = UInt::from_words(a);
+                let carry = mul::mul_word_in_place(&mut result.0, b[0]);
+                (result, carry != 0)
+            }
+            _ => {
+                let len = a.len() + b.len();
+                with_temp(len, mul::memory_requirement_exact(b.len()), |c, memory| {
+                    let carry = mul::add_signed_mul(c, Positive, a, b, memory);
+                    debug_assert!(carry == 0);
+                    let low = len.min(WORDS);
+                    let overflow = c[low..].iter().any(|word| *word != 0);
+                    (UInt::from_words(&c[..low]), overflow)
+                })
+            }
+        }
+    }
+
+    /// Checked multiplication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::MAX.checked_mul(UInt::ONE), Some(UInt::MAX));
+    /// assert_eq!(UInt::<2>::MAX.checked_mul(UInt::MAX), None);
+    /// ```
+    pub fn checked_mul(self, rhs: UInt<WORDS>) -> Option<UInt<WORDS>> {
+        match self.overflowing_mul(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Wrapping multiplication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::MAX.wrapping_mul(UInt::MAX), UInt::ONE);
+    /// ```
+    pub fn wrapping_mul(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.overflowing_mul(rhs).0
+    }
+
+    /// Saturating multiplication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::MAX.saturating_mul(UInt::MAX), UInt::MAX);
+    /// ```
+    pub fn saturating_mul(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.checked_mul(rhs).unwrap_or(UInt::MAX)
+    }
+
+    /// Division and remainder, or `None` if `rhs` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// # use core::convert::TryFrom;
+    /// let a = UInt::<2>::try_from(23u8).unwrap();
+    /// let b = UInt::<2>::try_from(10u8).unwrap();
+    /// assert_eq!(
+    ///     a.checked_div_rem(b),
+    ///     Some((UInt::try_from(2u8).unwrap(), UInt::try_from(3u8).unwrap()))
+    /// );
+    /// assert_eq!(a.checked_div_rem(UInt::ZERO), None);
+    /// ```
+    pub fn checked_div_rem(self, rhs: UInt<WORDS>) -> Option<(UInt<WORDS>, UInt<WORDS>)> {
+        let m = self.len();
+        let n = rhs.len();
+        if n == 0 {
+            return None;
+        }
+        if m < n {
+            return Some((UInt::ZERO, self));
+        }
+        if n == 1 {
+            let mut quotient = self;
+            let rem = div::div_by_word_in_place(&mut quotient.0[..m], rhs.0[0]);
+            return Some((quotient, UInt::from_words(&[rem])));
+        }
+
+        let layout = div::memory_requirement_exact(m + 1, n);
+        let result = with_temp(m + 1 + n, layout, |temp, memory| {
+            let (lhs, rhs_normalized) = temp.split_at_mut(m + 1);
+            rhs_normalized.copy_from_slice(&rhs.0[..n]);
+            let (shift, fast_div_rhs_top) = div::normalize_large(rhs_normalized);
+            lhs[..m].copy_from_slice(&self.0[..m]);
+            lhs[m] = shift::shl_in_place(&mut lhs[..m], shift);
+            // The top word is less than 2^shift, so the top n words are less than rhs.
+            let overflow = div::div_rem_in_place(lhs, rhs_normalized, fast_div_rhs_top, memory);
+            debug_assert!(!overflow);
+            let low_bits = shift::shr_in_place(&mut lhs[..n], shift);
+            debug_assert!(low_bits == 0);
+            (UInt::from_words(&lhs[n..]), UInt::from_words(&lhs[..n]))
+        });
+        Some(result)
+    }
+
+    /// Checked division, or `None` if `rhs` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::MAX.checked_div(UInt::MAX), Some(UInt::ONE));
+    /// assert_eq!(UInt::<2>::MAX.checked_div(UInt::ZERO), None);
+    /// ```
+    pub fn checked_div(self, rhs: UInt<WORDS>) -> Option<UInt<WORDS>> {
+        self.checked_div_rem(rhs).map(|(q, _)| q)
+    }
+
+    /// Checked remainder, or `None` if `rhs` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::MAX.checked_rem(UInt::MAX), Some(UInt::ZERO));
+    /// assert_eq!(UInt::<2>::MAX.checked_rem(UInt::ZERO), None);
+    /// ```
+    pub fn checked_rem(self, rhs: UInt<WORDS>) -> Option<UInt<WORDS>> {
+        self.checked_div_rem(rhs).map(|(_, r)| r)
+    }
+
+    /// Checked shift left, or `None` if `n >= BITS`. Bits shifted out are lost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// # use core::convert::TryFrom;
+    /// assert_eq!(UInt::<2>::ONE.checked_shl(1), Some(UInt::try_from(2u8).unwrap()));
+    /// assert_eq!(UInt::<2>::ONE.checked_shl(UInt::<2>::BITS), None);
+    /// ```
+    pub fn checked_shl(self, n: usize) -> Option<UInt<WORDS>> {
+        if n < UInt::<WORDS>::BITS {
+            Some(self.shl_bits(n))
+        } else {
+            None
+        }
+    }
+
+    /// Wrapping shift left by `n % BITS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// # use core::convert::TryFrom;
+    /// assert_eq!(UInt::<2>::ONE.wrapping_shl(UInt::<2>::BITS + 1), UInt::try_from(2u8).unwrap());
+    /// ```
+    pub fn wrapping_shl(self, n: usize) -> UInt<WORDS> {
+        match UInt::<WORDS>::BITS {
+            0 => self,
+            bits => self.shl_bits(n % bits),
+        }
+    }
+
+    /// Checked shift right, or `None` if `n >= BITS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::ONE.checked_shr(1), Some(UInt::ZERO));
+    /// assert_eq!(UInt::<2>::ONE.checked_shr(UInt::<2>::BITS), None);
+    /// ```
+    pub fn checked_shr(self, n: usize) -> Option<UInt<WORDS>> {
+        if n < UInt::<WORDS>::BITS {
+            Some(self.shr_bits(n))
+        } else {
+            None
+        }
+    }
+
+    /// Wrapping shift right by `n % BITS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// # use core::convert::TryFrom;
+    /// let two = UInt::<2>::try_from(2u8).unwrap();
+    /// assert_eq!(two.wrapping_shr(UInt::<2>::BITS + 1), UInt::ONE);
+    /// ```
+    pub fn wrapping_shr(self, n: usize) -> UInt<WORDS> {
+        match UInt::<WORDS>::BITS {
+            0 => self,
+            bits => self.shr_bits(n % bits),
+        }
+    }
+
+    /// Exponentiation, or `None` on overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// # use core::convert::TryFrom;
+    /// let three = UInt::<2>::try_from(3u8).unwrap();
+    /// assert_eq!(three.checked_pow(4), Some(UInt::try_from(81u8).unwrap()));
+    /// assert_eq!(three.checked_pow(1000), None);
+    /// ```
+    pub fn checked_pow(self, exp: usize) -> Option<UInt<WORDS>> {
+        let mut result = UInt::ONE;
+        let mut base = self;
+        let mut exp = exp;
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result = result.checked_mul(base)?;
+            }
+            exp >>= 1;
+            if exp != 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Some(result)
+    }
+
+    /// Wrapping exponentiation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::fixed::UInt;
+    /// assert_eq!(UInt::<2>::MAX.wrapping_pow(3), UInt::MAX);
+    /// ```
+    pub fn wrapping_pow(self, exp: usize) -> UInt<WORDS> {
+        let mut result = UInt::ONE;
+        let mut base = self;
+        let mut exp = exp;
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result = result.wrapping_mul(base);
+            }
+            exp >>= 1;
+            if exp != 0 {
+                base = base.wrapping_mul(base);
+            }
+        }
+        result
+    }
+
+    /// Shift left by `n < BITS` bits.
+    fn shl_bits(self, n: usize) -> UInt<WORDS> {
+        debug_assert!(n < UInt::<WORDS>::BITS);
+        let shift_words = n / WORD_BITS_USIZE;
+        let mut result = UInt::ZERO;
+        result.0[shift_words..].copy_from_slice(&self.0[..WORDS - shift_words]);
+        let _ = shift::shl_in_place(&mut result.0[shift_words..], (n % WORD_BITS_USIZE) as u32);
+        result
+    }
+
+    /// Shift right by `n < BITS` bits.
+    fn shr_bits(self, n: usize) -> UInt<WORDS> {
+        debug_assert!(n < UInt::<WORDS>::BITS);
+        let shift_words = n / WORD_BITS_USIZE;
+        let mut result = UInt::ZERO;
+        result.0[..WORDS - shift_words].copy_from_slice(&self.0[shift_words..]);
+        let _ = shift::shr_in_place(
+            &mut result.0[..WORDS - shift_words],
+            (n % WORD_BITS_USIZE) as u32,
+        );
+        result
+    }
+
+    fn add_impl(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.checked_add(rhs)
+            .unwrap_or_else(|| panic!("UInt addition overflow"))
+    }
+
+    fn sub_impl(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.checked_sub(rhs)
+            .unwrap_or_else(|| panic!("UInt subtraction overflow"))
+    }
+
+    fn mul_impl(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.checked_mul(rhs)
+            .unwrap_or_else(|| panic!("UInt multiplication overflow"))
+    }
+
+    fn div_impl(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.checked_div(rhs)
+            .unwrap_or_else(|| div_ops::panic_divide_by_0())
+    }
+
+    fn rem_impl(self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        self.checked_rem(rhs)
+            .unwrap_or_else(|| div_ops::panic_divide_by_0())
+    }
+
+    fn bitand_impl(mut self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *a &= *b;
+        }
+        self
+    }
+
+    fn bitor_impl(mut self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *a |= *b;
+        }
+        self
+    }
+
+    fn bitxor_impl(mut self, rhs: UInt<WORDS>) -> UInt<WORDS> {
+        for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *a ^= *b;
+        }
+        self
+    }
+
+    pub(crate) fn shl_impl(self, n: usize) -> UInt<WORDS> {
+        self.checked_shl(n)
+            .unwrap_or_else(|| panic!("UInt shift overflow"))
+    }
+
+    pub(crate) fn shr_impl(self, n: usize) -> UInt<WORDS> {
+        self.checked_shr(n)
+            .unwrap_or_else(|| panic!("UInt shift overflow"))
+    }
+}
+
+impl<const WORDS: usize> Not for UInt<WORDS> {
+    type Output = UInt<WORDS>;
+
+    fn not(mut self) -> UInt<WORDS> {
+        for word in self.0.iter_mut() {
+            *word = !*word;
+        }
+        self
+    }
+}
+
+impl<const WORDS: usize> Not for &UInt<WORDS> {
+    type Output = UInt<WORDS>;
+
+    fn not(self) -> UInt<WORDS> {
+        !*self
+    }
+}
+
+/// Implement a binary operator for all combinations of values and references.
+macro_rules! impl_binop {
+    ($t:ident, $trait:ident, $method:ident, $trait_assign:ident, $method_assign:ident, $impl:ident) => {
+        impl<const WORDS: usize> $trait<$t<WORDS>> for $t<WORDS> {
+            type Output = $t<WORDS>;
+
+            fn $method(self, rhs: $t<WORDS>) -> $t<WORDS> {
+                self.$impl(rhs)
+            }
+        }
+
+        impl<const WORDS: usize> $trait<&$t<WORDS>> for $t<WORDS> {
+            type Output = $t<WORDS>;
+
+            fn $method(self, rhs: &$t<WORDS>) -> $t<WORDS> {
+                self.$impl(*rhs)
+            }
+        }
+
+        impl<const WORDS: usize> $trait<$t<WORDS>> for &$t<WORDS> {
+            type Output = $t<WORDS>;
+
+            fn $method(self, rhs: $t<WORDS>) -> $t<WORDS> {
+                (*self).$impl(rhs)
+            }
+        }
+
+        impl<const WORDS: usize> $trait<&$t<WORDS>> for &$t<WORDS> {
+            type Output = $t<WORDS>;
+
+            fn $method(self, rhs: &$t<WORDS>) -> $t<WORDS> {
+                (*self).$impl(*rhs)
+            }
+        }
+
+        impl<const WORDS: usize> $trait_assign<$t<WORDS>> for $t<WORDS> {
+            fn $method_assign(&mut self, rhs: $t<WORDS>) {
+                *self = (*self).$impl(rhs);
+            }
+        }
+
+        impl<const WORDS: usize> $trait_assign<&$t<WORDS>> for $t<WORDS> {
+            fn $method_assign(&mut self, rhs: &$t<WORDS>) {
+                *self = (*self).$impl(*rhs);
+            }
+        }
+    };
+}
+
+/// Implement a shift operator by `usize` for values and references.
+macro_rules! impl_shift {
+    ($t:ident, $trait:ident, $method:ident, $trait_assign:ident, $method_assign:ident, $impl:ident) => {
+        impl<const WORDS: usize> $trait<usize> for $t<WORDS> {
+            type Output = $t<WORDS>;
+
+            fn $method(self, rhs: usize) -> $t<WORDS> {
+                self.$impl(rhs)
+            }
+        }
+
+        impl<const WORDS: usize> $trait<usize> for &$t<WORDS> {
+            type Output = $t<WORDS>;
+
+            fn $method(self, rhs: usize) -> $t<WORDS> {
+                (*self).$impl(rhs)
+            }
+        }
+
+        impl<const WORDS: usize> $trait_assign<usize> for $t<WORDS> {
+            fn $method_assign(&mut self, rhs: usize) {
+                *self = (*self).$impl(rhs);
+            }
+        }
+    };
+}
+
+pub(crate) use {impl_binop, impl_shift};
+
+impl_binop!(UInt, Add, add, AddAssign, add_assign, add_impl);
+impl_binop!(UInt, Sub, sub, SubAssign, sub_assign, sub_impl);
+impl_binop!(UInt, Mul, mul, MulAssign, mul_assign, mul_impl);
+impl_binop!(UInt, Div, div, DivAssign, div_assign, div_impl);
+impl_binop!(UInt, Rem, rem, RemAssign, rem_assign, rem_impl);
+impl_binop!(
+    UInt,
+    BitAnd,
+    bitand,
+    BitAndAssign,
+    bitand_assign,
+    bitand_impl
+);
+impl_binop!(UInt, BitOr, bitor, BitOrAssign, bitor_assign, bitor_impl);
+impl_binop!(
+    UInt,
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    bitxor_impl
+);
+impl_shift!(UInt, Shl, shl, ShlAssign, shl_assign, shl_impl);
+impl_shift!(UInt, Shr, shr, ShrAssign, shr_assign, shr_impl);
@@ -41,6 +41,17 @@ impl<'a> DigitWriter<'a> {
         Ok(())
     }
 
+    /// The case used for letters.
+    pub(crate) fn digit_case(&self) -> DigitCase {
+        self.digit_case
+    }
+
+    /// Write digits that are already converted to ASCII, after the buffered digits.
+    pub(crate) fn write_ascii(&mut self, s: &str) -> fmt::Result {
+        self.flush()?;
+        self.writer.write_str(s)
+    }
+
     /// Must call flush to make sure all the data is written.
     pub(crate) fn flush(&mut self) -> fmt::Result {
         let buffer_len_rounded = math::round_up(self.buffer_len, arch::digits::DIGIT_CHUNK_LEN);
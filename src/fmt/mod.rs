@@ -5,6 +5,7 @@ use crate::{
     radix::{self, Digit, DigitCase},
     sign::Sign::{self, *},
     ubig::UBig,
+    ubig_ref::{IBigRef, UBigRef},
 };
 use core::fmt::{
     self, Alignment, Binary, Debug, Display, Formatter, LowerHex, Octal, UpperHex, Write,
@@ -19,7 +20,7 @@ impl Display for UBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: Positive,
-            magnitude: self,
+            magnitude: UBigRef::from(self),
             radix: 10,
             prefix: "",
             digit_case: DigitCase::NoLetters,
@@ -38,7 +39,7 @@ impl Binary for UBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: Positive,
-            magnitude: self,
+            magnitude: UBigRef::from(self),
             radix: 2,
             prefix: if f.alternate() { "0b" } else { "" },
             digit_case: DigitCase::NoLetters,
@@ -51,7 +52,7 @@ impl Octal for UBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: Positive,
-            magnitude: self,
+            magnitude: UBigRef::from(self),
             radix: 8,
             prefix: if f.alternate() { "0o" } else { "" },
             digit_case: DigitCase::NoLetters,
@@ -64,7 +65,7 @@ impl LowerHex for UBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: Positive,
-            magnitude: self,
+            magnitude: UBigRef::from(self),
             radix: 16,
             prefix: if f.alternate() { "0x" } else { "" },
             digit_case: DigitCase::Lower,
@@ -77,7 +78,7 @@ impl UpperHex for UBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: Positive,
-            magnitude: self,
+            magnitude: UBigRef::from(self),
             radix: 16,
             prefix: if f.alternate() { "0x" } else { "" },
             digit_case: DigitCase::Upper,
@@ -90,7 +91,7 @@ impl Display for IBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: self.sign(),
-            magnitude: self.magnitude(),
+            magnitude: UBigRef::from(self.magnitude()),
             radix: 10,
             prefix: "",
             digit_case: DigitCase::NoLetters,
@@ -109,7 +110,7 @@ impl Binary for IBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: self.sign(),
-            magnitude: self.magnitude(),
+            magnitude: UBigRef::from(self.magnitude()),
             radix: 2,
             prefix: if f.alternate() { "0b" } else { "" },
             digit_case: DigitCase::NoLetters,
@@ -122,7 +123,7 @@ impl Octal for IBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: self.sign(),
-            magnitude: self.magnitude(),
+            magnitude: UBigRef::from(self.magnitude()),
             radix: 8,
             prefix: if f.alternate() { "0o" } else { "" },
             digit_case: DigitCase::NoLetters,
@@ -135,7 +136,7 @@ impl LowerHex for IBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: self.sign(),
-            magnitude: self.magnitude(),
+            magnitude: UBigRef::from(self.magnitude()),
             radix: 16,
             prefix: if f.alternate() { "0x" } else { "" },
             digit_case: DigitCase::Lower,
@@ -148,7 +149,7 @@ impl UpperHex for IBig {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         InRadixFull {
             sign: self.sign(),
-            magnitude: self.magnitude(),
+            magnitude: UBigRef::from(self.magnitude()),
             radix: 16,
             prefix: if f.alternate() { "0x" } else { "" },
             digit_case: DigitCase::Upper,
@@ -175,7 +176,7 @@ impl UBig {
         radix::check_radix_valid(radix);
         InRadix {
             sign: Positive,
-            magnitude: self,
+            magnitude: UBigRef::from(self),
             radix,
         }
     }
@@ -196,6 +197,96 @@ impl IBig {
     /// assert_eq!(format!("{:010}", ibig!(-35).in_radix(36)), "-00000000z");
     /// ```
     pub fn in_radix(&self, radix: u32) -> InRadix {
+        radix::check_radix_valid(radix);
+        InRadix {
+            sign: self.sign(),
+            magnitude: UBigRef::from(self.magnitude()),
+            radix,
+        }
+    }
+}
+
+/// Implement a formatting trait for [IBigRef] and [UBigRef].
+macro_rules! impl_fmt_ref {
+    ($trait:ident, $radix:expr, $prefix:expr, $digit_case:expr) => {
+        impl $trait for IBigRef<'_> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                InRadixFull {
+                    sign: self.sign(),
+                    magnitude: self.magnitude(),
+                    radix: $radix,
+                    prefix: if f.alternate() { $prefix } else { "" },
+                    digit_case: $digit_case,
+                }
+                .fmt(f)
+            }
+        }
+
+        impl $trait for UBigRef<'_> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                $trait::fmt(&IBigRef::from(*self), f)
+            }
+        }
+    };
+}
+
+impl_fmt_ref!(Display, 10, "", DigitCase::NoLetters);
+impl_fmt_ref!(Binary, 2, "0b", DigitCase::NoLetters);
+impl_fmt_ref!(Octal, 8, "0o", DigitCase::NoLetters);
+impl_fmt_ref!(LowerHex, 16, "0x", DigitCase::Lower);
+impl_fmt_ref!(UpperHex, 16, "0x", DigitCase::Upper);
+
+impl Debug for UBigRef<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Debug for IBigRef<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<'a> UBigRef<'a> {
+    /// Representation in a given radix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36 inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBigRef};
+    /// let a = ubig!(83);
+    /// assert_eq!(format!("{}", UBigRef::from(&a).in_radix(3)), "10002");
+    /// ```
+    pub fn in_radix(self, radix: u32) -> InRadix<'a> {
+        radix::check_radix_valid(radix);
+        InRadix {
+            sign: Positive,
+            magnitude: self,
+            radix,
+        }
+    }
+}
+
+impl<'a> IBigRef<'a> {
+    /// Representation in a given radix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36 inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, IBigRef};
+    /// let a = ibig!(-83);
+    /// assert_eq!(format!("{}", IBigRef::from(&a).in_radix(3)), "-10002");
+    /// ```
+    pub fn in_radix(self, radix: u32) -> InRadix<'a> {
         radix::check_radix_valid(radix);
         InRadix {
             sign: self.sign(),
@@ -205,7 +296,7 @@ impl IBig {
     }
 }
 
-/// Representation of a [UBig] or [IBig] in any radix between 2 and 36 inclusive.
+/// Representation of a [UBig], [IBig], [UBigRef] or [IBigRef] in any radix between 2 and 36 inclusive.
 ///
 /// This can be used to format a number in a non-standard radix.
 ///
@@ -224,14 +315,14 @@ impl IBig {
 /// ```
 pub struct InRadix<'a> {
     sign: Sign,
-    magnitude: &'a UBig,
+    magnitude: UBigRef<'a>,
     radix: Digit,
 }
 
 /// Representation in a given radix with a prefix and digit case.
 struct InRadixFull<'a> {
     sign: Sign,
-    magnitude: &'a UBig,
+    magnitude: UBigRef<'a>,
     radix: Digit,
     prefix: &'static str,
     digit_case: DigitCase,
@@ -5,34 +5,38 @@ use crate::{
     div,
     fmt::{digit_writer::DigitWriter, InRadixFull, PreparedForFormatting},
     ops::DivRem,
-    radix::{self, Digit},
-    ubig::{Repr::*, UBig},
+    parallel,
+    radix::{self, Digit, DigitCase},
+    thresholds::{self, MAX_FMT_CHUNK},
+    ubig::UBig,
+    ubig_ref::{RefRepr::*, UBigRef},
 };
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use core::{
     fmt::{self, Formatter},
     mem,
 };
 
-/// Format in chunks of CHUNK_LEN * digits_per_word.
-const CHUNK_LEN: usize = 16;
+// Format in chunks of `chunk_len * digits_per_word` digits, `chunk_len` being the `fmt_chunk`
+// threshold, at most MAX_FMT_CHUNK.
 
 impl InRadixFull<'_> {
     pub(crate) fn fmt_non_power_two(&self, f: &mut Formatter) -> fmt::Result {
         debug_assert!(radix::is_radix_valid(self.radix) && !self.radix.is_power_of_two());
         match self.magnitude.repr() {
             Small(word) => {
-                let mut prepared = PreparedWord::new(*word, self.radix, 1);
+                let mut prepared = PreparedWord::new(word, self.radix, 1);
                 self.format_prepared(f, &mut prepared)
             }
-            Large(buffer) => {
+            Large(words) => {
                 let radix_info = radix::radix_info(self.radix);
-                let max_digits = buffer.len() * (radix_info.digits_per_word + 1);
-                if max_digits <= CHUNK_LEN * radix_info.digits_per_word {
-                    let mut prepared = PreparedMedium::new(self.magnitude, self.radix);
+                let max_digits = words.len() * (radix_info.digits_per_word + 1);
+                let chunk_len = thresholds::current().fmt_chunk;
+                if max_digits <= chunk_len * radix_info.digits_per_word {
+                    let mut prepared = PreparedMedium::new(words, self.radix);
                     self.format_prepared(f, &mut prepared)
                 } else {
-                    let mut prepared = PreparedLarge::new(self.magnitude, self.radix);
+                    let mut prepared = PreparedLarge::new(self.magnitude, self.radix, chunk_len);
                     self.format_prepared(f, &mut prepared)
                 }
             }
@@ -81,24 +85,24 @@ impl PreparedForFormatting for PreparedWord {
 }
 
 /// A medium number prepared for formatting.
-/// Must have no more than CHUNK_LEN * digits_per_word digits.
+/// Must have no more than MAX_FMT_CHUNK * digits_per_word digits.
 struct PreparedMedium {
     top_group: PreparedWord,
     // Little endian in groups of digits_per_word.
-    low_groups: [Word; CHUNK_LEN],
+    low_groups: [Word; MAX_FMT_CHUNK],
     num_low_groups: usize,
     radix: Digit,
 }
 
 impl PreparedMedium {
     /// Prepare a medium number for formatting.
-    fn new(number: &UBig, radix: Digit) -> PreparedMedium {
+    fn new(words: &[Word], radix: Digit) -> PreparedMedium {
         debug_assert!(radix::is_radix_valid(radix) && !radix.is_power_of_two());
         let radix_info = radix::radix_info(radix);
 
-        let (mut buffer, mut buffer_len) = ubig_to_chunk_buffer(number);
+        let (mut buffer, mut buffer_len) = words_to_chunk_buffer(words);
 
-        let mut low_groups = [0; CHUNK_LEN];
+        let mut low_groups = [0; MAX_FMT_CHUNK];
         let mut num_low_groups = 0;
 
         while buffer_len > 1 {
@@ -147,9 +151,11 @@ impl PreparedForFormatting for PreparedMedium {
 /// A large number prepared for formatting.
 struct PreparedLarge {
     top_chunk: PreparedMedium,
-    // radix^((digits_per_word * CHUNK_LEN) << i)
+    // Number of words per chunk.
+    chunk_len: usize,
+    // radix^((digits_per_word * chunk_len) << i)
     radix_powers: Vec<UBig>,
-    // little endian chunks: (i, (digits_per_word * CHUNK_LEN)<<i digit number)
+    // little endian chunks: (i, (digits_per_word * chunk_len)<<i digit number)
     // decreasing in size, so there is a logarithmic number of them
     big_chunks: Vec<(usize, UBig)>,
     radix: Digit,
@@ -157,16 +163,18 @@ struct PreparedLarge {
 
 impl PreparedLarge {
     /// Prepare a medium number for formatting in a non-power-of-2 radix.
-    fn new(number: &UBig, radix: Digit) -> PreparedLarge {
+    fn new(number: UBigRef, radix: Digit, chunk_len: usize) -> PreparedLarge {
         debug_assert!(radix::is_radix_valid(radix) && !radix.is_power_of_two());
+        debug_assert!(chunk_len <= MAX_FMT_CHUNK);
         let radix_info = radix::radix_info(radix);
 
         let mut radix_powers = Vec::new();
         let mut big_chunks = Vec::new();
-        let chunk_power = UBig::from_word(radix_info.range_per_word).pow(CHUNK_LEN);
-        if chunk_power > *number {
+        let chunk_power = UBig::from_word(radix_info.range_per_word).pow(chunk_len);
+        if chunk_power > number {
             return PreparedLarge {
-                top_chunk: PreparedMedium::new(number, radix),
+                top_chunk: PreparedMedium::new(number.as_words(), radix),
+                chunk_len,
                 radix_powers,
                 big_chunks,
                 radix,
@@ -177,11 +185,11 @@ impl PreparedLarge {
         loop {
             let prev = radix_powers.last().unwrap();
             // Avoid multiplication if we know prev * prev > number just by looking at lengths.
-            if 2 * prev.len() - 1 > number.len() {
+            if 2 * prev.len() - 1 > number.as_words().len() {
                 break;
             }
             let new = prev * prev;
-            if new > *number {
+            if new > number {
                 break;
             }
             radix_powers.push(new);
@@ -190,7 +198,7 @@ impl PreparedLarge {
         let mut power_iter = radix_powers.iter().enumerate().rev();
         let mut x = {
             let (i, p) = power_iter.next().unwrap();
-            let (q, r) = number.div_rem(p);
+            let (q, r) = number.div_rem(UBigRef::from(p));
             big_chunks.push((i, r));
             q
         };
@@ -203,17 +211,21 @@ impl PreparedLarge {
         }
 
         PreparedLarge {
-            top_chunk: PreparedMedium::new(&x, radix),
+            top_chunk: PreparedMedium::new(x.as_words(), radix),
+            chunk_len,
             radix_powers,
             big_chunks,
             radix,
         }
     }
 
-    /// Write (digits_per_word * CHUNK_LEN) << i digits.
+    /// Write (digits_per_word * chunk_len) << i digits.
     fn write_big_chunk(&self, digit_writer: &mut DigitWriter, i: usize, x: UBig) -> fmt::Result {
         if i == 0 {
             self.write_chunk(digit_writer, x)
+        } else if parallel::is_worthwhile(self.radix_powers[i - 1].len()) {
+            let digits = self.big_chunk_to_string(digit_writer.digit_case(), i, x)?;
+            digit_writer.write_ascii(&digits)
         } else {
             let (q, r) = x.div_rem(&self.radix_powers[i - 1]);
             self.write_big_chunk(digit_writer, i - 1, q)?;
@@ -221,12 +233,42 @@ impl PreparedLarge {
         }
     }
 
-    /// Write digits_per_word * CHUNK_LEN digits.
+    /// Convert (digits_per_word * chunk_len) << i digits to ASCII, converting the halves in
+    /// parallel.
+    fn big_chunk_to_string(
+        &self,
+        digit_case: DigitCase,
+        i: usize,
+        x: UBig,
+    ) -> Result<String, fmt::Error> {
+        if i == 0 || !parallel::is_worthwhile(self.radix_powers[i - 1].len()) {
+            let radix_info = radix::radix_info(self.radix);
+            let mut digits =
+                String::with_capacity((radix_info.digits_per_word * self.chunk_len) << i);
+            let mut digit_writer = DigitWriter::new(&mut digits, digit_case);
+            self.write_big_chunk(&mut digit_writer, i, x)?;
+            digit_writer.flush()?;
+            Ok(digits)
+        } else {
+            let (q, r) = x.div_rem(&self.radix_powers[i - 1]);
+            let (hi, lo) = parallel::join(
+                true,
+                || self.big_chunk_to_string(digit_case, i - 1, q),
+                || self.big_chunk_to_string(digit_case, i - 1, r),
+            );
+            let mut digits = hi?;
+            digits.push_str(&lo?);
+            Ok(digits)
+        }
+    }
+
+    /// Write digits_per_word * chunk_len digits.
     fn write_chunk(&self, digit_writer: &mut DigitWriter, x: UBig) -> fmt::Result {
         let radix_info = radix::radix_info(self.radix);
-        let (mut buffer, mut buffer_len) = ubig_to_chunk_buffer(&x);
+        let (mut buffer, mut buffer_len) = words_to_chunk_buffer(x.as_words());
 
-        let mut groups = [0; CHUNK_LEN];
+        let mut groups = [0; MAX_FMT_CHUNK];
+        let groups = &mut groups[..self.chunk_len];
 
         for group in groups.iter_mut() {
             *group = div::fast_div_by_word_in_place(
@@ -254,7 +296,7 @@ impl PreparedForFormatting for PreparedLarge {
         let mut num_digits = self.top_chunk.width();
         let radix_info = radix::radix_info(self.radix);
         for (i, _) in &self.big_chunks {
-            num_digits += (radix_info.digits_per_word * CHUNK_LEN) << i;
+            num_digits += (radix_info.digits_per_word * self.chunk_len) << i;
         }
         num_digits
     }
@@ -270,9 +312,8 @@ impl PreparedForFormatting for PreparedLarge {
     }
 }
 
-fn ubig_to_chunk_buffer(x: &UBig) -> ([Word; CHUNK_LEN], usize) {
-    let mut buffer = [0; CHUNK_LEN];
-    let words = x.as_words();
+fn words_to_chunk_buffer(words: &[Word]) -> ([Word; MAX_FMT_CHUNK], usize) {
+    let mut buffer = [0; MAX_FMT_CHUNK];
     let buffer_len = words.len();
     buffer[..buffer_len].copy_from_slice(words);
     (buffer, buffer_len)
@@ -6,7 +6,7 @@ use crate::{
     math,
     primitive::{WORD_BITS, WORD_BITS_USIZE},
     radix::{self, Digit},
-    ubig::Repr::*,
+    ubig_ref::RefRepr::*,
 };
 use core::fmt::{self, Formatter};
 
@@ -16,11 +16,11 @@ impl InRadixFull<'_> {
         debug_assert!(radix::is_radix_valid(self.radix) && self.radix.is_power_of_two());
         match self.magnitude.repr() {
             Small(word) => {
-                let mut prepared = PreparedWord::new(*word, self.radix);
+                let mut prepared = PreparedWord::new(word, self.radix);
                 self.format_prepared(f, &mut prepared)
             }
-            Large(buffer) => {
-                let mut prepared = PreparedLarge::new(buffer, self.radix);
+            Large(words) => {
+                let mut prepared = PreparedLarge::new(words, self.radix);
                 self.format_prepared(f, &mut prepared)
             }
         }
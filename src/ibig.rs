@@ -1,9 +1,14 @@
 //! Signed big integer.
 
 use crate::{
+    error::AllocError,
     sign::Sign::{self, *},
     ubig::UBig,
 };
+use core::mem;
+
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Allocator;
 
 /// Signed big integer.
 ///
@@ -21,7 +26,7 @@ use crate::{
 /// assert_eq!(b, d);
 /// # Ok::<(), ParseError>(())
 /// ```
-#[derive(Clone, Eq, Hash, PartialEq)]
+#[derive(Eq, Hash, PartialEq)]
 pub struct IBig {
     sign: Sign,
     magnitude: UBig,
@@ -46,4 +51,73 @@ impl IBig {
     pub(crate) fn into_sign_magnitude(self) -> (Sign, UBig) {
         (self.sign, self.magnitude)
     }
+
+    /// Zero, with memory for larger values from `allocator`.
+    ///
+    /// The number keeps using `allocator` as it is changed in place, the same way as
+    /// [UBig::new_in].
+    ///
+    /// Requires the `allocator_api` feature and a nightly compiler. Like `UBig`, `IBig` is larger
+    /// with the feature: 64 rather than 32 bytes on 64-bit targets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(allocator_api)]
+    /// # use ibig::{ibig, IBig};
+    /// # use std::alloc::System;
+    /// let mut a = IBig::new_in(System);
+    /// a -= ibig!(1) << 1000;
+    /// a *= ibig!(1) << 1000;
+    /// assert_eq!(a, ibig!(-1) << 2000);
+    /// ```
+    #[cfg(feature = "allocator_api")]
+    pub fn new_in<A: Allocator + Send + Sync + 'static>(allocator: A) -> IBig {
+        IBig::from(UBig::new_in(allocator))
+    }
+
+    /// Replace with `f(self)`, keeping the allocator of `self`.
+    pub(crate) fn update<F>(&mut self, f: F)
+    where
+        F: FnOnce(IBig) -> IBig,
+    {
+        let allocator = self.magnitude.allocator().clone();
+        let (sign, magnitude) = f(mem::take(self)).into_sign_magnitude();
+        *self = IBig {
+            sign,
+            magnitude: magnitude.into_allocator(allocator),
+        };
+    }
+
+    /// Clone, or fail if memory can't be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{error::AllocError, ibig};
+    /// let a = ibig!(-1) << 1000;
+    /// assert_eq!(a.try_clone()?, a);
+    /// # Ok::<(), AllocError>(())
+    /// ```
+    pub fn try_clone(&self) -> Result<IBig, AllocError> {
+        Ok(IBig::from_sign_magnitude(
+            self.sign,
+            self.magnitude.try_clone()?,
+        ))
+    }
+}
+
+impl Clone for IBig {
+    fn clone(&self) -> IBig {
+        IBig {
+            sign: self.sign,
+            magnitude: self.magnitude.clone(),
+        }
+    }
+
+    /// Keeps the allocator of `self`.
+    fn clone_from(&mut self, source: &IBig) {
+        self.sign = source.sign;
+        self.magnitude.clone_from(&source.magnitude);
+    }
 }
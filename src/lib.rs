@@ -50,38 +50,65 @@
 //! ```
 
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 extern crate alloc;
 
-pub use crate::{ibig::IBig, ubig::UBig};
+pub use crate::{
+    arch::word::Word,
+    divisor::Divisor,
+    ibig::IBig,
+    mul_ops::dot,
+    product::{product_tree, remainder_tree},
+    scratch::Scratch,
+    thresholds::ThresholdConfig,
+    ubig::UBig,
+    ubig_ref::{IBigRef, UBigRef},
+};
 
 mod add;
 mod add_ops;
+mod allocator;
 mod arch;
 mod bits;
 mod buffer;
+mod checked;
 mod cmp;
 mod convert;
 mod div;
+mod div_exact;
 mod div_ops;
+mod divisor;
 pub mod error;
+mod factor;
+mod factorial;
 mod fast_divide;
+mod fibonacci;
+pub mod fixed;
 pub mod fmt;
 mod ibig;
+mod log;
 mod math;
 mod memory;
 pub mod modular;
 mod mul;
 mod mul_ops;
 pub mod ops;
+mod parallel;
 mod parse;
 mod pow;
 mod primitive;
+mod product;
 mod radix;
+mod ref_ops;
+mod scratch;
 mod shift;
 mod shift_ops;
 mod sign;
+mod thresholds;
+mod totient;
 mod ubig;
+mod ubig_ref;
 
 #[macro_use]
 mod macros;
@@ -0,0 +1,262 @@
+//! Logarithms.
+
+use crate::{
+    arch::word::Word,
+    ibig::IBig,
+    math,
+    primitive::WORD_BITS_USIZE,
+    radix::{self, Digit},
+    sign::Sign::*,
+    ubig::{Repr::*, UBig},
+};
+use core::convert::TryFrom;
+
+impl UBig {
+    /// Integer logarithm: the largest `k` such that `base^k <= self`.
+    ///
+    /// Returns `None` if `self` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(0).ilog(&ubig!(3)), None);
+    /// assert_eq!(ubig!(80).ilog(&ubig!(3)), Some(3));
+    /// assert_eq!(ubig!(81).ilog(&ubig!(3)), Some(4));
+    /// assert_eq!(ubig!(1000).ilog(&ubig!(1001)), Some(0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is less than 2.
+    pub fn ilog(&self, base: &UBig) -> Option<usize> {
+        if *base < UBig::from_word(2) {
+            panic!("Invalid logarithm base: {}", base);
+        }
+        if *self == UBig::from_word(0) {
+            return None;
+        }
+        if base.is_power_of_two() {
+            return Some((self.bit_len() - 1) / (base.bit_len() - 1));
+        }
+
+        // floor(log(floor(x / base^k))) = floor(log(x)) - k, so we can repeatedly divide by
+        // base^k for a lower estimate k. The remaining value shrinks geometrically.
+        let mut x = self.clone();
+        let mut log = 0;
+        loop {
+            let estimate = ilog_lower_estimate(&x, base);
+            if estimate == 0 {
+                break;
+            }
+            x /= base.pow(estimate);
+            log += estimate;
+        }
+        while x >= *base {
+            x /= base;
+            log += 1;
+        }
+        Some(log)
+    }
+
+    /// Integer logarithm base 2: the largest `k` such that `2^k <= self`.
+    ///
+    /// Returns `None` if `self` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(0).ilog2(), None);
+    /// assert_eq!(ubig!(1).ilog2(), Some(0));
+    /// assert_eq!(ubig!(1000).ilog2(), Some(9));
+    /// ```
+    pub fn ilog2(&self) -> Option<usize> {
+        self.bit_len().checked_sub(1)
+    }
+
+    /// Integer logarithm base 10: the largest `k` such that `10^k <= self`.
+    ///
+    /// Returns `None` if `self` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(0).ilog10(), None);
+    /// assert_eq!(ubig!(999).ilog10(), Some(2));
+    /// assert_eq!(ubig!(1000).ilog10(), Some(3));
+    /// ```
+    pub fn ilog10(&self) -> Option<usize> {
+        self.ilog(&UBig::from_word(10))
+    }
+
+    /// Approximate logarithm base 2.
+    ///
+    /// Returns negative infinity for zero. Works for values too large to convert to `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(0).log2_f64(), f64::NEG_INFINITY);
+    /// assert_eq!(ubig!(1024).log2_f64(), 10.0);
+    /// assert!(((ubig!(3) << 5000).log2_f64() - 5001.5849625).abs() < 1e-6);
+    /// ```
+    pub fn log2_f64(&self) -> f64 {
+        if *self == UBig::from_word(0) {
+            return f64::NEG_INFINITY;
+        }
+        let (exponent, mantissa) = self.log_split();
+        exponent as f64 + math::ln_f64(mantissa) / core::f64::consts::LN_2
+    }
+
+    /// Approximate natural logarithm.
+    ///
+    /// Returns negative infinity for zero. Works for values too large to convert to `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(0).ln_f64(), f64::NEG_INFINITY);
+    /// assert_eq!(ubig!(1).ln_f64(), 0.0);
+    /// assert!(((ubig!(1) << 10000).ln_f64() - 6931.4718056).abs() < 1e-6);
+    /// ```
+    pub fn ln_f64(&self) -> f64 {
+        if *self == UBig::from_word(0) {
+            return f64::NEG_INFINITY;
+        }
+        let (exponent, mantissa) = self.log_split();
+        exponent as f64 * core::f64::consts::LN_2 + math::ln_f64(mantissa)
+    }
+
+    /// Split non-zero `self` into `2^exponent * mantissa`, with `mantissa` in `[1, 2)`.
+    ///
+    /// The mantissa is rounded down to 53 bits.
+    fn log_split(&self) -> (usize, f64) {
+        let exponent = self.bit_len() - 1;
+        let mantissa_bits = exponent.min(52);
+        let top = u64::try_from(self >> (exponent - mantissa_bits)).unwrap();
+        // 2^-mantissa_bits
+        let scale = f64::from_bits((1023 - mantissa_bits as u64) << 52);
+        (exponent, top as f64 * scale)
+    }
+}
+
+/// A lower bound on `floor(log_base(x))`, for non-zero `x` and `base` not a power of 2.
+fn ilog_lower_estimate(x: &UBig, base: &UBig) -> usize {
+    let bits = x.bit_len() - 1;
+    match base.repr() {
+        Small(word) if *word <= radix::MAX_RADIX as Word => {
+            // base^digits_per_word < 2^WORD_BITS, so log2(base) < WORD_BITS / digits_per_word.
+            let digits_per_word = radix::radix_info(*word as Digit).digits_per_word;
+            bits / WORD_BITS_USIZE * digits_per_word
+                + bits % WORD_BITS_USIZE * digits_per_word / WORD_BITS_USIZE
+        }
+        // log2(base) < bit_len(base)
+        _ => bits / base.bit_len(),
+    }
+}
+
+impl IBig {
+    /// Integer logarithm: the largest `k` such that `base^k <= self`.
+    ///
+    /// Returns `None` if `self` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, ubig};
+    /// assert_eq!(ibig!(81).ilog(&ubig!(3)), Some(4));
+    /// assert_eq!(ibig!(0).ilog(&ubig!(3)), None);
+    /// assert_eq!(ibig!(-81).ilog(&ubig!(3)), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is less than 2.
+    pub fn ilog(&self, base: &UBig) -> Option<usize> {
+        match self.sign() {
+            Positive => self.magnitude().ilog(base),
+            Negative => {
+                if *base < UBig::from_word(2) {
+                    panic!("Invalid logarithm base: {}", base);
+                }
+                None
+            }
+        }
+    }
+
+    /// Integer logarithm base 2: the largest `k` such that `2^k <= self`.
+    ///
+    /// Returns `None` if `self` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert_eq!(ibig!(1000).ilog2(), Some(9));
+    /// assert_eq!(ibig!(-1000).ilog2(), None);
+    /// ```
+    pub fn ilog2(&self) -> Option<usize> {
+        match self.sign() {
+            Positive => self.magnitude().ilog2(),
+            Negative => None,
+        }
+    }
+
+    /// Integer logarithm base 10: the largest `k` such that `10^k <= self`.
+    ///
+    /// Returns `None` if `self` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert_eq!(ibig!(1000).ilog10(), Some(3));
+    /// assert_eq!(ibig!(-1000).ilog10(), None);
+    /// ```
+    pub fn ilog10(&self) -> Option<usize> {
+        match self.sign() {
+            Positive => self.magnitude().ilog10(),
+            Negative => None,
+        }
+    }
+
+    /// Approximate logarithm base 2.
+    ///
+    /// Returns negative infinity for zero and NaN for negative values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert_eq!(ibig!(1024).log2_f64(), 10.0);
+    /// assert!(ibig!(-1024).log2_f64().is_nan());
+    /// ```
+    pub fn log2_f64(&self) -> f64 {
+        match self.sign() {
+            Positive => self.magnitude().log2_f64(),
+            Negative => f64::NAN,
+        }
+    }
+
+    /// Approximate natural logarithm.
+    ///
+    /// Returns negative infinity for zero and NaN for negative values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert_eq!(ibig!(1).ln_f64(), 0.0);
+    /// assert!(ibig!(-1).ln_f64().is_nan());
+    /// ```
+    pub fn ln_f64(&self) -> f64 {
+        match self.sign() {
+            Positive => self.magnitude().ln_f64(),
+            Negative => f64::NAN,
+        }
+    }
+}
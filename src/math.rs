@@ -86,6 +86,44 @@ pub(crate) const fn const_min_usize(a: usize, b: usize) -> usize {
     }
 }
 
+/// Multiplicative inverse of odd x modulo 2^WORD_BITS.
+pub(crate) fn inverse_word_2adic(x: Word) -> Word {
+    debug_assert!(x & 1 == 1);
+    // x * x = 1 mod 8, so x is correct to 3 bits.
+    // Each Newton step y = y * (2 - x * y) doubles the number of correct bits.
+    let mut y = x;
+    let mut correct_bits = 3;
+    while correct_bits < Word::BIT_SIZE {
+        y = y.wrapping_mul((2 as Word).wrapping_sub(x.wrapping_mul(y)));
+        correct_bits *= 2;
+    }
+    y
+}
+
+/// Natural logarithm of x in `[1, 2]`.
+///
+/// Available without `std`.
+pub(crate) fn ln_f64(mut x: f64) -> f64 {
+    use core::f64::consts::{LN_2, SQRT_2};
+
+    debug_assert!((1.0..=2.0).contains(&x));
+    let mut res = 0.0;
+    if x > SQRT_2 {
+        x /= 2.0;
+        res = LN_2;
+    }
+    // ln(x) = 2 atanh(z) = 2 (z + z^3/3 + z^5/5 + ...), where z = (x-1)/(x+1), |z| < 0.18
+    let z = (x - 1.0) / (x + 1.0);
+    let z2 = z * z;
+    let mut term = z;
+    let mut sum = 0.0;
+    for k in 0..12 {
+        sum += term / f64::from(2 * k + 1);
+        term *= z2;
+    }
+    res + 2.0 * sum
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +167,21 @@ mod tests {
         assert_eq!(ones::<u32>(5), 0b11111);
         assert_eq!(ones::<u32>(32), u32::MAX);
     }
+
+    #[test]
+    fn test_ln_f64() {
+        use core::f64::consts::LN_2;
+
+        assert_eq!(ln_f64(1.0), 0.0);
+        assert!((ln_f64(2.0) - LN_2).abs() < 1e-15);
+        assert!((ln_f64(1.5) - 0.4054651081081644).abs() < 1e-15);
+        assert!((ln_f64(1.9) - 0.6418538861723947).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_inverse_word_2adic() {
+        for x in [1, 3, 5, 0x1235, Word::MAX / 3, Word::MAX].iter() {
+            assert_eq!(x.wrapping_mul(inverse_word_2adic(*x)), 1);
+        }
+    }
 }
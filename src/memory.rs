@@ -1,12 +1,27 @@
 //! Memory allocation.
 
+use crate::{error::AllocError, ubig::UBig};
 use alloc::alloc::Layout;
-use core::{marker::PhantomData, mem, slice};
+use core::{marker::PhantomData, slice};
 
-/// Chunk of memory directly allocated from the global allocator.
-pub(crate) struct MemoryAllocation {
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::{Allocator, Global};
+
+/// Allocator of temporary memory: the global allocator, or with the `allocator_api` feature the
+/// custom allocator of a [Scratch](crate::Scratch).
+#[derive(Clone, Copy)]
+pub(crate) struct TempAllocator<'a> {
+    #[cfg(feature = "allocator_api")]
+    allocator: &'a (dyn Allocator + Send + Sync),
+    #[cfg(not(feature = "allocator_api"))]
+    phantom_data: PhantomData<&'a ()>,
+}
+
+/// Chunk of memory directly allocated from an allocator.
+pub(crate) struct MemoryAllocation<'a> {
     layout: Layout,
     start: *mut u8,
+    allocator: TempAllocator<'a>,
 }
 
 /// Chunk of memory.
@@ -15,47 +30,152 @@ pub(crate) struct Memory<'a> {
     start: *mut u8,
     /// End pointer.
     end: *mut u8,
+    /// Allocator for memory needed separately, such as on other threads.
+    allocator: TempAllocator<'a>,
     /// Logically, Memory contains a reference to some data with lifetime 'a.
     phantom_data: PhantomData<&'a mut ()>,
 }
 
-impl MemoryAllocation {
+// Safe because Memory is like a mutable reference to the memory in `[start, end)`, which it uses
+// exclusively, and a shared reference to a thread-safe allocator.
+unsafe impl Send for Memory<'_> {}
+
+impl TempAllocator<'static> {
+    /// The global allocator.
+    pub(crate) fn global() -> TempAllocator<'static> {
+        TempAllocator {
+            #[cfg(feature = "allocator_api")]
+            allocator: &Global,
+            #[cfg(not(feature = "allocator_api"))]
+            phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<'a> TempAllocator<'a> {
+    /// Custom allocator.
+    #[cfg(feature = "allocator_api")]
+    pub(crate) fn new(allocator: &'a (dyn Allocator + Send + Sync)) -> TempAllocator<'a> {
+        TempAllocator { allocator }
+    }
+
+    /// Allocate memory with non-zero size, or fail if the allocator can't provide it.
+    pub(crate) fn allocate(self, layout: Layout) -> Result<*mut u8, AllocError> {
+        debug_assert!(layout.size() != 0);
+
+        #[cfg(feature = "allocator_api")]
+        let ptr = match self.allocator.allocate(layout) {
+            Ok(ptr) => ptr.as_ptr() as *mut u8,
+            Err(_) => core::ptr::null_mut(),
+        };
+        #[cfg(not(feature = "allocator_api"))]
+        // Safe because size is non-zero.
+        let ptr = unsafe { alloc::alloc::alloc(layout) };
+
+        if ptr.is_null() {
+            Err(AllocError::OutOfMemory)
+        } else {
+            Ok(ptr)
+        }
+    }
+
+    /// Free memory.
+    ///
+    /// # Safety
+    ///
+    /// The memory must have been allocated by `allocate` with the same layout.
+    pub(crate) unsafe fn deallocate(self, start: *mut u8, layout: Layout) {
+        #[cfg(feature = "allocator_api")]
+        self.allocator
+            .deallocate(core::ptr::NonNull::new_unchecked(start), layout);
+        #[cfg(not(feature = "allocator_api"))]
+        alloc::alloc::dealloc(start, layout);
+    }
+}
+
+impl MemoryAllocation<'static> {
     /// Allocate memory.
-    pub(crate) fn new(layout: Layout) -> MemoryAllocation {
+    pub(crate) fn new(layout: Layout) -> MemoryAllocation<'static> {
+        MemoryAllocation::try_new(layout).unwrap_or_else(|_| panic_out_of_memory())
+    }
+
+    /// Allocate memory, or fail if the allocator can't provide it.
+    pub(crate) fn try_new(layout: Layout) -> Result<MemoryAllocation<'static>, AllocError> {
+        MemoryAllocation::try_new_in(layout, TempAllocator::global())
+    }
+}
+
+impl<'a> MemoryAllocation<'a> {
+    /// Allocate memory from `allocator`, or fail if it can't provide it.
+    fn try_new_in(
+        layout: Layout,
+        allocator: TempAllocator<'a>,
+    ) -> Result<MemoryAllocation<'a>, AllocError> {
         let start = if layout.size() == 0 {
             // We should use layout.dangling(), but that is unstable.
             layout.align() as *mut u8
         } else if layout.size() > isize::MAX as usize {
-            panic_out_of_memory()
+            return Err(AllocError::OutOfMemory);
         } else {
-            // Safe because size is non-zero.
-            let ptr = unsafe { alloc::alloc::alloc(layout) };
-            if ptr.is_null() {
-                panic_out_of_memory();
-            }
-            ptr
+            allocator.allocate(layout)?
         };
 
-        MemoryAllocation { layout, start }
+        Ok(MemoryAllocation {
+            layout,
+            start,
+            allocator,
+        })
     }
 
     /// Get memory.
-    pub(crate) fn memory(&mut self) -> Memory {
+    pub(crate) fn memory(&mut self) -> Memory<'_> {
         Memory {
             start: self.start,
             end: self.start.wrapping_add(self.layout.size()),
+            allocator: self.allocator,
             phantom_data: PhantomData,
         }
     }
 }
 
-impl Drop for MemoryAllocation {
+impl Drop for MemoryAllocation<'_> {
     fn drop(&mut self) {
         if self.layout.size() != 0 {
             // Safe because the memory was allocated with the same layout.
-            unsafe { alloc::alloc::dealloc(self.start, self.layout) };
+            unsafe { self.allocator.deallocate(self.start, self.layout) };
+        }
+    }
+}
+
+impl<'a> Memory<'a> {
+    /// Memory in `[start, start + size)`, with `allocator` for memory needed separately.
+    ///
+    /// # Safety
+    ///
+    /// The memory must be valid for writes and not used by anything else for the lifetime `'a`.
+    pub(crate) unsafe fn from_raw_parts(
+        start: *mut u8,
+        size: usize,
+        allocator: TempAllocator<'a>,
+    ) -> Memory<'a> {
+        Memory {
+            start,
+            end: start.wrapping_add(size),
+            allocator,
+            phantom_data: PhantomData,
         }
     }
+
+    /// Allocate new memory for `layout` from the same allocator, or fail if the allocator can't
+    /// provide it.
+    ///
+    /// For computations that need memory separately from the rest, such as on other threads.
+    pub(crate) fn try_new_allocation(
+        &self,
+        layout: Layout,
+    ) -> Result<MemoryAllocation<'a>, AllocError> {
+        MemoryAllocation::try_new_in(layout, self.allocator)
+    }
 }
 
 impl Memory<'_> {
@@ -126,6 +246,31 @@ impl Memory<'_> {
         })
     }
 
+    /// Allocate a chunk of memory for `layout`, to be used separately from the rest, such as on
+    /// another thread.
+    ///
+    /// Returns the chunk and the remaining chunk of memory.
+    ///
+    /// The original memory is not usable until both the new chunks are dropped.
+    pub(crate) fn allocate_memory(&mut self, layout: Layout) -> (Memory<'_>, Memory<'_>) {
+        let (start, end) = self
+            .try_find_memory(layout)
+            .unwrap_or_else(|| panic_allocated_too_little());
+        let memory = Memory {
+            start,
+            end,
+            allocator: self.allocator,
+            phantom_data: PhantomData,
+        };
+        let new_memory = Memory {
+            start: end,
+            end: self.end,
+            allocator: self.allocator,
+            phantom_data: PhantomData,
+        };
+        (memory, new_memory)
+    }
+
     fn allocate_slice_initialize<T, F>(&mut self, n: usize, init: F) -> (&mut [T], Memory)
     where
         F: FnOnce(*mut T),
@@ -141,6 +286,7 @@ impl Memory<'_> {
         let new_memory = Memory {
             start: slice_end,
             end: self.end,
+            allocator: self.allocator,
             phantom_data: PhantomData,
         };
 
@@ -148,15 +294,20 @@ impl Memory<'_> {
     }
 
     fn try_find_memory_for_slice<T>(&self, n: usize) -> Option<(*mut T, *mut u8)> {
+        let layout = Layout::array::<T>(n).ok()?;
+        let (start, end) = self.try_find_memory(layout)?;
+        Some((start as *mut T, end))
+    }
+
+    fn try_find_memory(&self, layout: Layout) -> Option<(*mut u8, *mut u8)> {
         let start = self.start as usize;
         let end = self.end as usize;
 
-        let padding = start.wrapping_neg() & (mem::align_of::<T>() - 1);
-        let slice_start = start.checked_add(padding)?;
-        let size = n.checked_mul(mem::size_of::<T>())?;
-        let slice_end = slice_start.checked_add(size)?;
-        if slice_end <= end {
-            Some((slice_start as *mut T, slice_end as *mut u8))
+        let padding = start.wrapping_neg() & (layout.align() - 1);
+        let chunk_start = start.checked_add(padding)?;
+        let chunk_end = chunk_start.checked_add(layout.size())?;
+        if chunk_end <= end {
+            Some((chunk_start as *mut u8, chunk_end as *mut u8))
         } else {
             None
         }
@@ -176,6 +327,11 @@ pub(crate) fn add_layout(a: Layout, b: Layout) -> Layout {
     layout
 }
 
+/// Layout of n consecutive chunks of memory for `layout`.
+pub(crate) fn repeat_layout(layout: Layout, n: usize) -> Layout {
+    (0..n).fold(zero_layout(), |total, _| add_layout(total, layout))
+}
+
 pub(crate) fn max_layout(a: Layout, b: Layout) -> Layout {
     Layout::from_size_align(a.size().max(b.size()), a.align().max(b.align()))
         .unwrap_or_else(|_| panic_out_of_memory())
@@ -185,6 +341,13 @@ pub(crate) fn panic_out_of_memory() -> ! {
     panic!("out of memory")
 }
 
+pub(crate) fn panic_alloc_error(err: AllocError) -> ! {
+    match err {
+        AllocError::Capacity(_) => UBig::panic_number_too_large(),
+        AllocError::OutOfMemory => panic_out_of_memory(),
+    }
+}
+
 fn panic_allocated_too_little() -> ! {
     panic!("internal error: not enough memory allocated")
 }
@@ -222,6 +385,26 @@ mod tests {
         let _ = new_memory.allocate_slice_fill::<u32>(2, 4);
     }
 
+    #[test]
+    fn test_allocate_memory() {
+        let mut scratchpad = MemoryAllocation::new(Layout::from_size_align(16, 4).unwrap());
+        let mut memory = scratchpad.memory();
+        let (a, mut memory) = memory.allocate_slice_fill::<u8>(1, 3);
+        let (mut chunk, mut rest) = memory.allocate_memory(Layout::from_size_align(8, 4).unwrap());
+        let (b, _) = chunk.allocate_slice_fill::<u32>(2, 4);
+        let (c, _) = rest.allocate_slice_fill::<u32>(1, 5);
+        assert_eq!(a, &[3]);
+        assert_eq!(b, &[4, 4]);
+        assert_eq!(c, &[5]);
+    }
+
+    #[test]
+    fn test_repeat_layout() {
+        let layout = repeat_layout(Layout::from_size_align(5, 4).unwrap(), 3);
+        assert_eq!(layout.size(), 21);
+        assert_eq!(layout.align(), 4);
+    }
+
     #[test]
     fn test_add_layout() {
         let layout = add_layout(
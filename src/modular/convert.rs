@@ -78,7 +78,7 @@ impl ModuloRingLarge {
     pub(crate) fn modulus(&self) -> UBig {
         let normalized_modulus = self.normalized_modulus();
         let mut buffer = Buffer::allocate(normalized_modulus.len());
-        buffer.extend(normalized_modulus);
+        buffer.extend_from_slice(normalized_modulus);
         let low_bits = shift::shr_in_place(&mut buffer, self.shift());
         assert!(low_bits == 0);
         buffer.into()
@@ -114,7 +114,7 @@ impl ModuloLarge<'_> {
     pub(crate) fn residue(&self) -> UBig {
         let words = self.normalized_value();
         let mut buffer = Buffer::allocate(words.len());
-        buffer.extend(words);
+        buffer.extend_from_slice(words);
         let low_bits = shift::shr_in_place(&mut buffer, self.ring().shift());
         assert!(low_bits == 0);
         buffer.into()
@@ -0,0 +1,74 @@
+//! Fibonacci and Lucas numbers in a modular ring.
+
+use crate::{
+    modular::{modulo::Modulo, modulo_ring::ModuloRing},
+    ubig::UBig,
+};
+
+impl ModuloRing {
+    /// Fibonacci number `F(n)` in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig};
+    /// let ring = ModuloRing::new(&ubig!(1000000007));
+    /// assert_eq!(ring.fibonacci(&ubig!(10)), ring.from(55));
+    /// assert_eq!(ring.fibonacci(&ubig!(1000000000000)), ring.from(730695249));
+    /// ```
+    pub fn fibonacci(&self, n: &UBig) -> Modulo<'_> {
+        self.fibonacci2(n).0
+    }
+
+    /// Lucas number `L(n)` in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig};
+    /// let ring = ModuloRing::new(&ubig!(100));
+    /// assert_eq!(ring.lucas(&ubig!(10)), ring.from(23));
+    /// ```
+    pub fn lucas(&self, n: &UBig) -> Modulo<'_> {
+        // L(n) = F(n) + 2F(n-1)
+        let (f, g) = self.fibonacci2(n);
+        f + &g + &g
+    }
+
+    /// `(F(n), F(n-1))` in the ring.
+    fn fibonacci2(&self, n: &UBig) -> (Modulo<'_>, Modulo<'_>) {
+        if *n == UBig::from_word(0) {
+            return (self.from(0), self.from(1));
+        }
+        let two = self.from(2);
+        // Invariant: (f, g) = (F(k), F(k-1)) where k is the top bits of n.
+        let mut f = self.from(1);
+        let mut g = self.from(0);
+        let mut k_odd = true;
+        for bit in (0..n.bit_len() - 1).rev() {
+            // F(2k-1) = F(k)^2 + F(k-1)^2
+            // F(2k+1) = 4F(k)^2 - F(k-1)^2 + 2(-1)^k
+            // F(2k) = F(2k+1) - F(2k-1)
+            let f_sqr = f.square();
+            let g_sqr = g.square();
+            let f_2k_minus_1 = &f_sqr + &g_sqr;
+            let f_sqr_2 = &f_sqr + &f_sqr;
+            let mut f_2k_plus_1 = &f_sqr_2 + &f_sqr_2 - g_sqr;
+            if k_odd {
+                f_2k_plus_1 -= &two;
+            } else {
+                f_2k_plus_1 += &two;
+            }
+            let f_2k = &f_2k_plus_1 - &f_2k_minus_1;
+            k_odd = n.bit(bit);
+            if k_odd {
+                f = f_2k_plus_1;
+                g = f_2k;
+            } else {
+                f = f_2k;
+                g = f_2k_minus_1;
+            }
+        }
+        (f, g)
+    }
+}
@@ -22,8 +22,10 @@ pub use modulo_ring::ModuloRing;
 mod add;
 mod cmp;
 pub(crate) mod convert;
+mod fibonacci;
 mod fmt;
 pub(crate) mod modulo;
 pub(crate) mod modulo_ring;
 mod mul;
+mod order;
 mod pow;
@@ -72,6 +72,23 @@ impl<'a> MulAssign<&Modulo<'a>> for Modulo<'a> {
     }
 }
 
+impl<'a> Modulo<'a> {
+    /// self * self
+    pub(crate) fn square(&self) -> Modulo<'a> {
+        let mut res = self.clone();
+        match res.repr_mut() {
+            ModuloRepr::Small(res_small) => res_small.square_in_place(),
+            ModuloRepr::Large(res_large) => {
+                let memory_requirement = res_large.ring().mul_memory_requirement();
+                let mut allocation = MemoryAllocation::new(memory_requirement);
+                let mut memory = allocation.memory();
+                res_large.square_in_place(&mut memory);
+            }
+        }
+        res
+    }
+}
+
 impl ModuloRingSmall {
     pub(crate) const fn mul_normalized(&self, a: Word, b: Word) -> Word {
         let product = extend_word(a >> self.shift()) * extend_word(b);
@@ -118,17 +135,36 @@ impl ModuloRingLarge {
         b: &[Word],
         memory: &'a mut Memory,
     ) -> &'a [Word] {
-        let modulus = self.normalized_modulus();
-        let n = modulus.len();
+        let n = self.normalized_modulus().len();
         debug_assert!(a.len() == n && b.len() == n);
 
         let (product, mut memory) = memory.allocate_slice_fill::<Word>(2 * n, 0);
         let overflow = mul::add_signed_mul_same_len(product, Positive, a, b, &mut memory);
         assert_eq!(overflow, 0);
+        self.reduce_normalized_product(product, &mut memory)
+    }
+
+    /// Returns a * a allocated in memory.
+    pub(crate) fn sqr_normalized<'a>(&self, a: &[Word], memory: &'a mut Memory) -> &'a [Word] {
+        let n = self.normalized_modulus().len();
+        debug_assert!(a.len() == n);
+
+        let (product, mut memory) = memory.allocate_slice_fill::<Word>(2 * n, 0);
+        mul::sqr(product, a, &mut memory);
+        self.reduce_normalized_product(product, &mut memory)
+    }
+
+    /// Reduces a product of two normalized values in place, returning the normalized remainder.
+    fn reduce_normalized_product<'a>(
+        &self,
+        product: &'a mut [Word],
+        memory: &mut Memory,
+    ) -> &'a [Word] {
+        let modulus = self.normalized_modulus();
         shift::shr_in_place(product, self.shift());
 
-        let _overflow = div::div_rem_in_place(product, modulus, *self.fast_div_top(), &mut memory);
-        &product[..n]
+        let _overflow = div::div_rem_in_place(product, modulus, *self.fast_div_top(), memory);
+        &product[..modulus.len()]
     }
 }
 
@@ -141,7 +177,7 @@ impl<'a> ModuloLarge<'a> {
     /// self *= self
     pub(crate) fn square_in_place(&mut self, memory: &mut Memory) {
         self.modify_normalized_value(|words, ring| {
-            words.copy_from_slice(ring.mul_normalized(words, words, memory));
+            words.copy_from_slice(ring.sqr_normalized(words, memory));
         });
     }
 
@@ -0,0 +1,147 @@
+//! Multiplicative order and primitive roots.
+
+use crate::{
+    arch::word::Word,
+    factor,
+    modular::{
+        modulo::{Modulo, ModuloRepr},
+        modulo_ring::ModuloRing,
+    },
+    totient,
+    ubig::UBig,
+};
+
+impl Modulo<'_> {
+    /// Multiplicative order: the smallest `k > 0` such that `self^k = 1`.
+    ///
+    /// Returns `None` if `self` is not invertible, i.e. not coprime to the modulus.
+    ///
+    /// Requires factorizing the modulus and the Carmichael function of the modulus, which may be
+    /// slow. If the factorization of a multiple of the order is already known, use
+    /// [order_with_factors](Modulo::order_with_factors).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig};
+    /// let ring = ModuloRing::new(&ubig!(10));
+    /// assert_eq!(ring.from(3).order(), Some(ubig!(4)));
+    /// assert_eq!(ring.from(9).order(), Some(ubig!(2)));
+    /// assert_eq!(ring.from(4).order(), None);
+    /// ```
+    pub fn order(&self) -> Option<UBig> {
+        let n_factors = factor::factorize(&self.modulus());
+        let group_order_factors = totient::carmichael_lambda_factors(&n_factors);
+        self.order_with_factors(&group_order_factors)
+    }
+
+    /// Multiplicative order given the factorization of a multiple of it.
+    ///
+    /// `group_order_factors` is a list of `(prime, exponent)` pairs. Their product must be a
+    /// multiple of the order, such as the Euler totient or the Carmichael function of the
+    /// modulus.
+    ///
+    /// Returns `None` if `self` raised to that product is not 1. This happens when `self` is not
+    /// invertible or the factorization is not a multiple of the order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig};
+    /// // 2^64 + 1 = 274177 * 67280421310721
+    /// let p = ubig!(274177);
+    /// let ring = ModuloRing::new(&p);
+    /// // p - 1 = 2^8 * 3^2 * 7 * 17
+    /// let factors = [(ubig!(2), 8), (ubig!(3), 2), (ubig!(7), 1), (ubig!(17), 1)];
+    /// assert_eq!(ring.from(2).order_with_factors(&factors), Some(ubig!(128)));
+    /// ```
+    pub fn order_with_factors(&self, group_order_factors: &[(UBig, usize)]) -> Option<UBig> {
+        let mut order = factor::product(group_order_factors);
+        if !self.pow(&order).is_one() {
+            return None;
+        }
+        for (p, e) in group_order_factors {
+            for _ in 0..*e {
+                let reduced = order.div_exact(p);
+                if !self.pow(&reduced).is_one() {
+                    break;
+                }
+                order = reduced;
+            }
+        }
+        Some(order)
+    }
+
+    /// Is the value 1?
+    fn is_one(&self) -> bool {
+        match self.repr() {
+            ModuloRepr::Small(self_small) => {
+                self_small.residue() == 1 % self_small.ring().modulus()
+            }
+            ModuloRepr::Large(self_large) => {
+                let words = self_large.normalized_value();
+                words[0] == 1 << self_large.ring().shift() && words[1..].iter().all(|w| *w == 0)
+            }
+        }
+    }
+
+    /// The modulus of the ring.
+    fn modulus(&self) -> UBig {
+        match self.repr() {
+            ModuloRepr::Small(self_small) => UBig::from_word(self_small.ring().modulus()),
+            ModuloRepr::Large(self_large) => self_large.ring().modulus(),
+        }
+    }
+}
+
+impl ModuloRing {
+    /// A primitive root: a generator of the multiplicative group of the ring.
+    ///
+    /// A primitive root exists if and only if the modulus is 1, 2, 4, `p^k` or `2p^k`, where `p`
+    /// is an odd prime. Otherwise returns `None`.
+    ///
+    /// Returns the smallest primitive root.
+    ///
+    /// Requires factorizing the modulus and the Euler totient of the modulus, which may be slow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig};
+    /// let ring = ModuloRing::new(&ubig!(998244353));
+    /// assert_eq!(ring.primitive_root(), Some(ring.from(3)));
+    /// let ring = ModuloRing::new(&ubig!(2).pow(5));
+    /// assert_eq!(ring.primitive_root(), None);
+    /// ```
+    pub fn primitive_root(&self) -> Option<Modulo<'_>> {
+        let n_factors = factor::factorize(&self.modulus());
+        let two = UBig::from_word(2);
+        let (num_odd, twos) = n_factors.iter().fold((0, 0), |(num_odd, twos), (p, e)| {
+            if *p == two {
+                (num_odd, *e)
+            } else {
+                (num_odd + 1, twos)
+            }
+        });
+        let cyclic = match num_odd {
+            0 => twos <= 2,
+            1 => twos <= 1,
+            _ => false,
+        };
+        if !cyclic {
+            return None;
+        }
+
+        // For cyclic groups lambda(n) = phi(n).
+        let group_order_factors = totient::euler_phi_factors(&n_factors);
+        let group_order = factor::product(&group_order_factors);
+        let mut candidate: Word = 1;
+        loop {
+            let root = self.from(candidate);
+            if root.order_with_factors(&group_order_factors).as_ref() == Some(&group_order) {
+                return Some(root);
+            }
+            candidate += 1;
+        }
+    }
+}
@@ -1,16 +1,16 @@
 use crate::{
     arch::word::Word,
-    math,
-    memory::{self, MemoryAllocation},
+    math, memory,
     modular::{
         modulo::{Modulo, ModuloLarge, ModuloRepr, ModuloSmall},
         modulo_ring::ModuloRingSmall,
     },
     primitive::{double_word, split_double_word, PrimitiveUnsigned, WORD_BITS, WORD_BITS_USIZE},
+    scratch::Scratch,
     ubig::{Repr::*, UBig},
 };
 
-impl Modulo<'_> {
+impl<'a> Modulo<'a> {
     /// Exponentiation.
     ///
     /// # Examples
@@ -24,10 +24,33 @@ impl Modulo<'_> {
     /// let a = ring.from(123);
     /// assert_eq!(a.pow(&(p - ubig!(1))), ring.from(1));
     /// ```
-    pub fn pow(&self, exp: &UBig) -> Modulo {
+    pub fn pow(&self, exp: &UBig) -> Modulo<'a> {
         match self.repr() {
             ModuloRepr::Small(self_small) => self_small.pow(exp).into(),
-            ModuloRepr::Large(self_large) => self_large.pow(exp).into(),
+            ModuloRepr::Large(self_large) => self_large.pow(exp, &mut Scratch::new()).into(),
+        }
+    }
+
+    /// Exponentiation using `scratch` for temporary memory.
+    ///
+    /// Same as [pow](Modulo::pow), but reuses the memory in `scratch` across calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig, Scratch};
+    /// let p = ubig!(2).pow(607) - ubig!(1);
+    /// let ring = ModuloRing::new(&p);
+    /// let mut scratch = Scratch::new();
+    /// for a in 2..5 {
+    ///     let a = ring.from(a);
+    ///     assert_eq!(a.pow_with_scratch(&p, &mut scratch), a);
+    /// }
+    /// ```
+    pub fn pow_with_scratch(&self, exp: &UBig, scratch: &mut Scratch) -> Modulo<'a> {
+        match self.repr() {
+            ModuloRepr::Small(self_small) => self_small.pow(exp).into(),
+            ModuloRepr::Large(self_large) => self_large.pow(exp, scratch).into(),
         }
     }
 }
@@ -49,9 +72,9 @@ impl ModuloRingSmall {
     }
 }
 
-impl ModuloSmall<'_> {
+impl<'a> ModuloSmall<'a> {
     /// Exponentiation.
-    fn pow(&self, exp: &UBig) -> ModuloSmall {
+    fn pow(&self, exp: &UBig) -> ModuloSmall<'a> {
         match exp.repr() {
             // self^0 == 1
             Small(0) => ModuloSmall::from_ubig(&UBig::from_word(1), self.ring()),
@@ -67,7 +90,7 @@ impl ModuloSmall<'_> {
         }
     }
 
-    fn pow_nontrivial(&self, exp: &UBig) -> ModuloSmall {
+    fn pow_nontrivial(&self, exp: &UBig) -> ModuloSmall<'a> {
         debug_assert!(*exp >= UBig::from_word(3));
 
         let exp_words = exp.as_words();
@@ -84,18 +107,18 @@ impl ModuloSmall<'_> {
     }
 }
 
-impl ModuloLarge<'_> {
-    fn pow(&self, exp: &UBig) -> ModuloLarge {
+impl<'a> ModuloLarge<'a> {
+    fn pow(&self, exp: &UBig, scratch: &mut Scratch) -> ModuloLarge<'a> {
         match exp.repr() {
             // self^0 == 1
             Small(0) => ModuloLarge::from_ubig(UBig::from_word(1), self.ring()),
             // self^1 == self
             Small(1) => self.clone(),
-            _ => self.pow_nontrivial(exp),
+            _ => self.pow_nontrivial(exp, scratch),
         }
     }
 
-    fn pow_nontrivial(&self, exp: &UBig) -> ModuloLarge {
+    fn pow_nontrivial(&self, exp: &UBig, scratch: &mut Scratch) -> ModuloLarge<'a> {
         debug_assert!(*exp >= UBig::from_word(2));
 
         let n = self.ring().normalized_modulus().len();
@@ -110,8 +133,7 @@ impl ModuloLarge<'_> {
             memory::array_layout::<Word>(table_words),
             self.ring().mul_memory_requirement(),
         );
-        let mut allocation = MemoryAllocation::new(memory_requirement);
-        let mut memory = allocation.memory();
+        let mut memory = scratch.memory(memory_requirement);
         let (table, mut memory) = memory.allocate_slice_fill::<Word>(table_words, 0);
 
         // val = self^2
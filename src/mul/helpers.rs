@@ -14,26 +14,48 @@ use crate::{
 ///
 /// Returns carry.
 pub(crate) fn add_signed_mul_split_into_same_len<F>(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+    f_add_signed_mul_same_len: F,
+) -> SignedWord
+where
+    F: Fn(&mut [Word], Sign, &[Word], &[Word], &mut Memory) -> SignedWord,
+{
+    add_signed_mul_split_into_blocks(c, sign, a, b, b.len(), memory, f_add_signed_mul_same_len)
+}
+
+/// c += sign * a * b
+///
+/// Splits a into blocks of block_len >= b.len(), and one final multiply of the rest of a.
+///
+/// Returns carry.
+pub(crate) fn add_signed_mul_split_into_blocks<F>(
     mut c: &mut [Word],
     sign: Sign,
     mut a: &[Word],
     b: &[Word],
+    block_len: usize,
     memory: &mut Memory,
-    f_add_signed_mul_same_len: F,
+    f_add_signed_mul_block: F,
 ) -> SignedWord
 where
     F: Fn(&mut [Word], Sign, &[Word], &[Word], &mut Memory) -> SignedWord,
 {
+    debug_assert!(c.len() == a.len() + b.len() && block_len >= b.len());
+
     let mut carry: SignedWord = 0;
     let n = b.len();
     let mut carry_n: SignedWord = 0; // at c[n]
-    while a.len() >= n {
-        let (a_lo, a_hi) = a.split_at(n);
+    while a.len() >= block_len {
+        let (a_lo, a_hi) = a.split_at(block_len);
         // Propagate carry.
-        carry_n = add::add_signed_word_in_place(&mut c[n..2 * n], carry_n);
-        carry_n += f_add_signed_mul_same_len(&mut c[..2 * n], sign, a_lo, b, memory);
+        carry_n = add::add_signed_word_in_place(&mut c[n..block_len + n], carry_n);
+        carry_n += f_add_signed_mul_block(&mut c[..block_len + n], sign, a_lo, b, memory);
         a = a_hi;
-        c = &mut c[n..];
+        c = &mut c[block_len..];
     }
     carry += add::add_signed_word_in_place(&mut c[n..], carry_n);
     carry += mul::add_signed_mul(c, sign, b, a, memory);
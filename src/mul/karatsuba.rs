@@ -6,6 +6,7 @@ use crate::{
     math,
     memory::{self, Memory},
     mul::{self, helpers},
+    parallel,
     sign::Sign::{self, *},
 };
 use alloc::alloc::Layout;
@@ -69,6 +70,16 @@ pub(crate) fn add_signed_mul_same_len(
     debug_assert!(n >= MIN_LEN);
 
     let mid = (n + 1) / 2;
+    if parallel::is_worthwhile(mid) {
+        let layout = memory::add_layout(
+            memory::array_layout::<Word>(2 * n + 4 * mid),
+            memory::repeat_layout(mul::memory_requirement_exact(mid), 3),
+        );
+        // If the memory can't be allocated, multiply serially instead.
+        if let Ok(mut allocation) = memory.try_new_allocation(layout) {
+            return add_signed_mul_same_len_parallel(c, sign, a, b, &mut allocation.memory());
+        }
+    }
 
     let (a_lo, a_hi) = a.split_at(mid);
     let (b_lo, b_hi) = b.split_at(mid);
@@ -119,3 +130,171 @@ pub(crate) fn add_signed_mul_same_len(
     assert!(carry.abs() <= 1);
     carry
 }
+
+/// c += sign * a * b
+/// Karatsuba method, with the three half-size products computed in parallel.
+///
+/// memory is separate from the caller's memory, and holds the temporary values and the memory
+/// for each product.
+///
+/// Returns carry.
+#[must_use]
+fn add_signed_mul_same_len_parallel(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let n = a.len();
+    let mid = math::ceil_div(n, 2);
+
+    let (a_lo, a_hi) = a.split_at(mid);
+    let (b_lo, b_hi) = b.split_at(mid);
+
+    // c_lo = a_lo * b_lo
+    // c_hi = a_hi * b_hi
+    // c_mid = (a_lo - a_hi) * (b_lo - b_hi)
+    let (c_lo, mut memory) = memory.allocate_slice_fill::<Word>(2 * mid, 0);
+    let (c_hi, mut memory) = memory.allocate_slice_fill::<Word>(2 * (n - mid), 0);
+    let (c_mid, mut memory) = memory.allocate_slice_fill::<Word>(2 * mid, 0);
+    let (a_diff, mut memory) = memory.allocate_slice_copy(a_lo);
+    let mut diff_sign = add::sub_in_place_with_sign(a_diff, a_hi);
+    let (b_diff, mut memory) = memory.allocate_slice_copy(b_lo);
+    diff_sign *= add::sub_in_place_with_sign(b_diff, b_hi);
+    let product_layout = mul::memory_requirement_exact(mid);
+    let (mut memory_lo, mut memory) = memory.allocate_memory(product_layout);
+    let (mut memory_hi, mut memory_mid) = memory.allocate_memory(product_layout);
+    parallel::join(
+        true,
+        || mul_same_len(c_lo, a_lo, b_lo, &mut memory_lo),
+        || {
+            parallel::join(
+                true,
+                || mul_same_len(c_hi, a_hi, b_hi, &mut memory_hi),
+                || mul_same_len(c_mid, a_diff, b_diff, &mut memory_mid),
+            )
+        },
+    );
+
+    // c_0 += c_lo
+    // c_1 += c_lo + c_hi - sign(diff) * c_mid
+    // c_2 += c_hi
+    let mut carry = add::add_signed_same_len_in_place(&mut c[2 * mid..], sign, c_hi);
+    let carry_c0 = add::add_signed_same_len_in_place(&mut c[..2 * mid], sign, c_lo);
+    let mut carry_c1 = add::add_signed_same_len_in_place(&mut c[mid..3 * mid], sign, c_lo);
+    carry_c1 += add::add_signed_in_place(&mut c[mid..3 * mid], sign, c_hi);
+    carry_c1 += add::add_signed_same_len_in_place(&mut c[mid..3 * mid], -sign * diff_sign, c_mid);
+
+    // Propagate carries.
+    carry_c1 += add::add_signed_word_in_place(&mut c[2 * mid..3 * mid], carry_c0);
+    carry += add::add_signed_word_in_place(&mut c[3 * mid..], carry_c1);
+
+    assert!(carry.abs() <= 1);
+    carry
+}
+
+/// c = a * b
+///
+/// c must be zero.
+fn mul_same_len(c: &mut [Word], a: &[Word], b: &[Word], memory: &mut Memory) {
+    let overflow = mul::add_signed_mul_same_len(c, Positive, a, b, memory);
+    assert!(overflow == 0);
+}
+
+/// c = a * a
+/// Karatsuba method: O(n^1.59), using 3 half-size squares.
+///
+/// Uses no more temporary memory than multiplication of the same length.
+pub(crate) fn sqr(c: &mut [Word], a: &[Word], memory: &mut Memory) {
+    let n = a.len();
+    debug_assert!(c.len() == 2 * n);
+    debug_assert!(n >= MIN_LEN);
+
+    let mid = math::ceil_div(n, 2);
+    if parallel::is_worthwhile(mid) {
+        let layout = memory::add_layout(
+            memory::array_layout::<Word>(3 * mid),
+            memory::repeat_layout(mul::memory_requirement_exact(mid), 3),
+        );
+        // If the memory can't be allocated, square serially instead.
+        if let Ok(mut allocation) = memory.try_new_allocation(layout) {
+            sqr_parallel(c, a, &mut allocation.memory());
+            return;
+        }
+    }
+
+    let (a_lo, a_hi) = a.split_at(mid);
+    // Result = a_lo^2 + a_hi^2 * Word^(2mid)
+    //        + (a_lo^2 + a_hi^2 - (a_lo-a_hi)^2) * Word^mid
+
+    // t = (a_lo - a_hi)^2
+    // c_0 holds a_lo - a_hi until it is overwritten.
+    let (t, mut memory) = memory.allocate_slice_fill::<Word>(2 * mid, 0);
+    {
+        let a_diff = &mut c[..mid];
+        a_diff.copy_from_slice(a_lo);
+        let _ = add::sub_in_place_with_sign(a_diff, a_hi);
+        mul::sqr(t, a_diff, &mut memory);
+    }
+
+    // c_0 = a_lo^2
+    // c_2 = a_hi^2
+    {
+        let (c_lo, c_hi) = c.split_at_mut(2 * mid);
+        mul::sqr(c_lo, a_lo, &mut memory);
+        mul::sqr(c_hi, a_hi, &mut memory);
+    }
+
+    add_middle_square(c, t, mid);
+}
+
+/// c = a * a
+/// Karatsuba method, with the three half-size squares computed in parallel.
+///
+/// memory is separate from the caller's memory, and holds the temporary values and the memory
+/// for each square.
+fn sqr_parallel(c: &mut [Word], a: &[Word], memory: &mut Memory) {
+    let n = a.len();
+    let mid = math::ceil_div(n, 2);
+
+    let (a_lo, a_hi) = a.split_at(mid);
+
+    // t = (a_lo - a_hi)^2
+    // c_0 = a_lo^2
+    // c_2 = a_hi^2
+    let (t, mut memory) = memory.allocate_slice_fill::<Word>(2 * mid, 0);
+    let (a_diff, mut memory) = memory.allocate_slice_copy(a_lo);
+    let _ = add::sub_in_place_with_sign(a_diff, a_hi);
+    let (c_lo, c_hi) = c.split_at_mut(2 * mid);
+    let square_layout = mul::memory_requirement_exact(mid);
+    let (mut memory_diff, mut memory) = memory.allocate_memory(square_layout);
+    let (mut memory_lo, mut memory_hi) = memory.allocate_memory(square_layout);
+    parallel::join(
+        true,
+        || mul::sqr(t, a_diff, &mut memory_diff),
+        || {
+            parallel::join(
+                true,
+                || mul::sqr(c_lo, a_lo, &mut memory_lo),
+                || mul::sqr(c_hi, a_hi, &mut memory_hi),
+            )
+        },
+    );
+
+    add_middle_square(c, t, mid);
+}
+
+/// c_1 += a_lo^2 + a_hi^2 - t
+///
+/// c_0 = a_lo^2, c_2 = a_hi^2, t = (a_lo - a_hi)^2.
+fn add_middle_square(c: &mut [Word], t: &mut [Word], mid: usize) {
+    // t = a_lo^2 + a_hi^2 - t
+    let mut carry_c1 = -SignedWord::from(add::sub_same_len_in_place_swap(&c[..2 * mid], t));
+    carry_c1 += SignedWord::from(add::add_in_place(t, &c[2 * mid..]));
+
+    // c_1 += t
+    carry_c1 += SignedWord::from(add::add_same_len_in_place(&mut c[mid..3 * mid], t));
+    let overflow = add::add_signed_word_in_place(&mut c[3 * mid..], carry_c1);
+    assert!(overflow == 0);
+}
@@ -2,29 +2,81 @@
 
 use crate::{
     add,
-    arch::word::{SignedWord, Word},
+    arch::{
+        self,
+        word::{SignedWord, Word},
+    },
+    math,
     memory::{self, Memory},
-    primitive::{double_word, extend_word, split_double_word},
+    primitive::{double_word, extend_word, split_double_word, WORD_BITS},
     sign::Sign,
+    thresholds::{self, ThresholdConfig},
 };
 use alloc::alloc::Layout;
 use core::mem;
 use static_assertions::const_assert;
 
-/// If smaller length <= MAX_LEN_SIMPLE, simple multiplication can be used.
-const MAX_LEN_SIMPLE: usize = 24;
-const_assert!(MAX_LEN_SIMPLE <= simple::MAX_SMALLER_LEN);
-const_assert!(MAX_LEN_SIMPLE + 1 >= karatsuba::MIN_LEN);
+// Above the Karatsuba threshold, the algorithm depends on the ratio of the longer length to the
+// smaller length, in units of 1/RATIO_DENOMINATOR.
+const RATIO_DENOMINATOR: usize = 8;
 
-/// If smaller length <= this, Karatsuba multiplication can be used.
-const MAX_LEN_KARATSUBA: usize = 192;
-const_assert!(MAX_LEN_KARATSUBA + 1 >= toom_3::MIN_LEN);
+/// If the ratio < this, balanced Toom-Cook multiplication can be used.
+const MAX_RATIO_BALANCED: usize = 9;
+
+/// If the ratio < this, Toom-43 multiplication can be used.
+const MAX_RATIO_TOOM_43: usize = 11;
+
+/// If the ratio < this, Toom-32 multiplication can be used.
+const MAX_RATIO_TOOM_32: usize = 14;
+
+/// If the ratio < this, Toom-42 multiplication can be used.
+/// Otherwise the longer factor is split into blocks for Toom-42.
+const MAX_RATIO_TOOM_42: usize = 20;
+const_assert!(MAX_RATIO_TOOM_42 >= 2 * RATIO_DENOMINATOR);
+
+/// Use number-theoretic multiplication on `u32` lanes rather than on `Word`s above the Toom-8
+/// threshold.
+///
+/// It is used by default if `Word` is smaller than 64 bits. The configuration option force_ntt set
+/// to "u32" or "word" selects either one regardless of the size of `Word`.
+const NTT_U32: bool = cfg!(force_ntt = "u32") || (!cfg!(force_ntt = "word") && WORD_BITS < 64);
+
+/// Use number-theoretic multiplication on `u32` lanes for a smaller factor of length n?
+///
+/// Factors too long for its transforms use the `Word` transform.
+fn use_ntt_u32(n: usize) -> bool {
+    NTT_U32 && n <= ntt_u32::MAX_LEN
+}
+
+/// Above the Karatsuba threshold, the smaller length must be at least this for the unbalanced
+/// Toom-Cook variants to split the factors into non-empty parts.
+const MIN_LEN_TOOM_COOK: usize = 17;
+
+/// Do the multiplication algorithms support the lengths the thresholds use them for?
+pub(crate) const fn thresholds_valid(config: &ThresholdConfig) -> bool {
+    config.mul_simple <= simple::MAX_SMALLER_LEN
+        && config.mul_simple + 1 >= karatsuba::MIN_LEN
+        && config.mul_karatsuba >= config.mul_simple
+        && config.mul_karatsuba + 1 >= toom_3::MIN_LEN
+        && config.mul_karatsuba + 1 >= MIN_LEN_TOOM_COOK
+        && config.mul_toom_3 >= config.mul_karatsuba
+        && config.mul_toom_3 + 1 >= toom_k::min_len(4)
+        && config.mul_toom_4 >= config.mul_toom_3
+        && config.mul_toom_4 + 1 >= toom_k::min_len(8)
+        && config.mul_toom_8 >= config.mul_toom_4
+}
 
 mod helpers;
 mod karatsuba;
 pub(crate) mod ntt;
+mod ntt_u32;
 mod simple;
+#[cfg(test)]
+pub(crate) mod testing;
+mod toom;
 mod toom_3;
+mod toom_k;
+mod toom_unbalanced;
 
 /// Multiply a word sequence by a `Word` in place.
 ///
@@ -38,15 +90,8 @@ pub(crate) fn mul_word_in_place(words: &mut [Word], rhs: Word) -> Word {
 ///
 /// Returns carry.
 #[must_use]
-pub(crate) fn mul_word_in_place_with_carry(words: &mut [Word], rhs: Word, mut carry: Word) -> Word {
-    for a in words {
-        // a * b + carry <= MAX * MAX + MAX < DoubleWord::MAX
-        let (v_lo, v_hi) =
-            split_double_word(extend_word(*a) * extend_word(rhs) + extend_word(carry));
-        *a = v_lo;
-        carry = v_hi;
-    }
-    carry
+pub(crate) fn mul_word_in_place_with_carry(words: &mut [Word], rhs: Word, carry: Word) -> Word {
+    arch::mul::mul_word_in_place_with_carry(words, rhs, carry)
 }
 
 /// words += mult * rhs
@@ -55,16 +100,7 @@ pub(crate) fn mul_word_in_place_with_carry(words: &mut [Word], rhs: Word, mut ca
 #[must_use]
 fn add_mul_word_same_len_in_place(words: &mut [Word], mult: Word, rhs: &[Word]) -> Word {
     assert!(words.len() == rhs.len());
-    let mut carry: Word = 0;
-    for (a, b) in words.iter_mut().zip(rhs.iter()) {
-        // a + mult * b + carry <= MAX * MAX + 2 * MAX <= DoubleWord::MAX
-        let (v_lo, v_hi) = split_double_word(
-            extend_word(*a) + extend_word(carry) + extend_word(mult) * extend_word(*b),
-        );
-        *a = v_lo;
-        carry = v_hi;
-    }
-    carry
+    arch::mul::add_mul_word_same_len_in_place(words, mult, rhs)
 }
 
 /// words += mult * rhs
@@ -111,15 +147,56 @@ pub(crate) fn sub_mul_word_same_len_in_place(words: &mut [Word], mult: Word, rhs
 ///
 /// n bounds the length of the smaller factor in words.
 pub(crate) fn memory_requirement_up_to(n: usize) -> Layout {
-    if n <= MAX_LEN_SIMPLE {
+    let config = thresholds::current();
+    if n <= config.mul_simple {
         memory::zero_layout()
-    } else if n <= MAX_LEN_KARATSUBA {
+    } else if n <= config.mul_karatsuba {
         karatsuba::memory_requirement_up_to(n)
+    } else if n <= config.mul_toom_8 {
+        let balanced = if n <= config.mul_toom_3 {
+            toom_3::memory_requirement_up_to(n)
+        } else if n <= config.mul_toom_4 {
+            toom_k::memory_requirement_up_to(n, 4)
+        } else {
+            toom_k::memory_requirement_up_to(n, 8)
+        };
+        memory::max_layout(balanced, memory_requirement_unbalanced_up_to(n))
     } else {
-        toom_3::memory_requirement_up_to(n)
+        let ntt = if use_ntt_u32(n) {
+            ntt_u32::memory_requirement_up_to(n)
+        } else if NTT_U32 {
+            // Blocks shorter than n may use the u32 transform.
+            memory::max_layout(
+                ntt_u32::memory_requirement_up_to(ntt_u32::MAX_LEN),
+                ntt::memory_requirement_up_to(n),
+            )
+        } else {
+            ntt::memory_requirement_up_to(n)
+        };
+        // Blocks shorter than n may use Toom-Cook.
+        memory::max_layout(memory_requirement_up_to(config.mul_toom_8), ntt)
     }
 }
 
+/// Temporary scratch space required for unbalanced Toom-Cook multiplication.
+///
+/// n bounds the length of the smaller factor in words.
+fn memory_requirement_unbalanced_up_to(n: usize) -> Layout {
+    use toom_unbalanced::Variant::*;
+
+    [
+        (Toom43, MAX_RATIO_TOOM_43),
+        (Toom32, MAX_RATIO_TOOM_32),
+        (Toom42, MAX_RATIO_TOOM_42),
+    ]
+    .iter()
+    .map(|(variant, max_ratio)| {
+        let max_len = math::ceil_div(max_ratio * n, RATIO_DENOMINATOR);
+        toom_unbalanced::memory_requirement_up_to(*variant, max_len, n)
+    })
+    .fold(memory::zero_layout(), memory::max_layout)
+}
+
 /// Temporary scratch space required for multiplication.
 ///
 /// n is the exact length of the smaller factor in words.
@@ -144,12 +221,95 @@ pub(crate) fn add_signed_mul<'a>(
         mem::swap(&mut a, &mut b);
     }
 
-    if b.len() <= MAX_LEN_SIMPLE {
+    let config = thresholds::current();
+    if b.len() <= config.mul_simple {
         simple::add_signed_mul(c, sign, a, b)
-    } else if b.len() <= MAX_LEN_KARATSUBA {
+    } else if b.len() <= config.mul_karatsuba {
         karatsuba::add_signed_mul(c, sign, a, b, memory)
+    } else if b.len() > config.mul_toom_8 {
+        if use_ntt_u32(b.len()) {
+            ntt_u32::add_signed_mul(c, sign, a, b, memory)
+        } else {
+            ntt::add_signed_mul(c, sign, a, b, memory)
+        }
     } else {
+        match choose_shape(a.len(), b.len()) {
+            Shape::Balanced => add_signed_mul_balanced(c, sign, a, b, memory),
+            Shape::Unbalanced(variant) => {
+                toom_unbalanced::add_signed_mul(c, sign, a, b, memory, variant)
+            }
+            Shape::Blocks => helpers::add_signed_mul_split_into_blocks(
+                c,
+                sign,
+                a,
+                b,
+                2 * b.len(),
+                memory,
+                |c, sign, a, b, memory| {
+                    toom_unbalanced::add_signed_mul(
+                        c,
+                        sign,
+                        a,
+                        b,
+                        memory,
+                        toom_unbalanced::Variant::Toom42,
+                    )
+                },
+            ),
+        }
+    }
+}
+
+/// How to multiply factors longer than the Karatsuba threshold, up to the Toom-8 threshold.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Shape {
+    /// Similar lengths: the balanced Toom-Cook tier for the smaller length.
+    Balanced,
+    /// Somewhat different lengths: an unbalanced Toom-Cook variant.
+    Unbalanced(toom_unbalanced::Variant),
+    /// Very different lengths: split the longer factor into blocks of twice the smaller length
+    /// and use Toom-42 for each block.
+    Blocks,
+}
+
+/// Choose the algorithm by the ratio of the lengths.
+///
+/// a_len >= b_len >= MIN_LEN_TOOM_COOK
+fn choose_shape(a_len: usize, b_len: usize) -> Shape {
+    debug_assert!(a_len >= b_len && b_len >= MIN_LEN_TOOM_COOK);
+
+    let ratio_below = |max_ratio: usize| a_len * RATIO_DENOMINATOR < max_ratio * b_len;
+    if ratio_below(MAX_RATIO_BALANCED) {
+        Shape::Balanced
+    } else if ratio_below(MAX_RATIO_TOOM_43) {
+        Shape::Unbalanced(toom_unbalanced::Variant::Toom43)
+    } else if ratio_below(MAX_RATIO_TOOM_32) {
+        Shape::Unbalanced(toom_unbalanced::Variant::Toom32)
+    } else if ratio_below(MAX_RATIO_TOOM_42) {
+        Shape::Unbalanced(toom_unbalanced::Variant::Toom42)
+    } else {
+        Shape::Blocks
+    }
+}
+
+/// c += sign * a * b, using the balanced Toom-Cook tier for b.len().
+///
+/// Returns carry.
+#[must_use]
+fn add_signed_mul_balanced(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let config = thresholds::current();
+    if b.len() <= config.mul_toom_3 {
         toom_3::add_signed_mul(c, sign, a, b, memory)
+    } else if b.len() <= config.mul_toom_4 {
+        toom_k::add_signed_mul(c, sign, a, b, memory, 4)
+    } else {
+        toom_k::add_signed_mul(c, sign, a, b, memory, 8)
     }
 }
 
@@ -167,11 +327,171 @@ pub(crate) fn add_signed_mul_same_len(
     let n = a.len();
     debug_assert!(b.len() == n && c.len() == 2 * n);
 
-    if n <= MAX_LEN_SIMPLE {
+    let config = thresholds::current();
+    if n <= config.mul_simple {
         simple::add_signed_mul_same_len(c, sign, a, b)
-    } else if n <= MAX_LEN_KARATSUBA {
+    } else if n <= config.mul_karatsuba {
         karatsuba::add_signed_mul_same_len(c, sign, a, b, memory)
-    } else {
+    } else if n <= config.mul_toom_3 {
         toom_3::add_signed_mul_same_len(c, sign, a, b, memory)
+    } else if n <= config.mul_toom_4 {
+        toom_k::add_signed_mul_same_len(c, sign, a, b, memory, 4)
+    } else if n <= config.mul_toom_8 {
+        toom_k::add_signed_mul_same_len(c, sign, a, b, memory, 8)
+    } else if use_ntt_u32(n) {
+        ntt_u32::add_signed_mul_same_len(c, sign, a, b, memory)
+    } else {
+        ntt::add_signed_mul_same_len(c, sign, a, b, memory)
+    }
+}
+
+/// c = a * a
+///
+/// Squaring needs no more temporary memory than multiplication:
+/// `memory_requirement_exact(a.len())` is enough.
+pub(crate) fn sqr(c: &mut [Word], a: &[Word], memory: &mut Memory) {
+    let n = a.len();
+    debug_assert!(c.len() == 2 * n);
+
+    let config = thresholds::current();
+    if n <= config.mul_simple {
+        simple::sqr(c, a)
+    } else if n <= config.mul_karatsuba {
+        karatsuba::sqr(c, a, memory)
+    } else if n <= config.mul_toom_3 {
+        toom_3::sqr(c, a, memory)
+    } else if n <= config.mul_toom_4 {
+        toom_k::sqr(c, a, memory, 4)
+    } else if n <= config.mul_toom_8 {
+        toom_k::sqr(c, a, memory, 8)
+    } else if use_ntt_u32(n) {
+        ntt_u32::sqr(c, a, memory)
+    } else {
+        ntt::sqr(c, a, memory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{memory::MemoryAllocation, sign::Sign::*, ubig::UBig};
+    use alloc::vec::Vec;
+
+    /// Simple linear congruential generator for reproducible lengths and words.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            self.0 >> 16
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn words(&mut self, n: usize) -> Vec<Word> {
+            (0..n).map(|_| self.next() as Word).collect()
+        }
+    }
+
+    #[test]
+    fn test_choose_shape() {
+        let n = thresholds::current().mul_karatsuba + 1;
+        assert_eq!(choose_shape(n, n), Shape::Balanced);
+        assert_eq!(
+            choose_shape(4 * n / 3, n),
+            Shape::Unbalanced(toom_unbalanced::Variant::Toom43)
+        );
+        assert_eq!(
+            choose_shape(3 * n / 2, n),
+            Shape::Unbalanced(toom_unbalanced::Variant::Toom32)
+        );
+        assert_eq!(
+            choose_shape(2 * n, n),
+            Shape::Unbalanced(toom_unbalanced::Variant::Toom42)
+        );
+        assert_eq!(choose_shape(3 * n, n), Shape::Blocks);
+    }
+
+    #[test]
+    fn test_use_ntt_u32() {
+        assert_eq!(use_ntt_u32(ntt_u32::MAX_LEN), NTT_U32);
+        assert!(!use_ntt_u32(ntt_u32::MAX_LEN + 1));
+        // Where the Word transform supports longer factors, the memory requirement covers blocks
+        // using either transform.
+        let n = ntt_u32::MAX_LEN + 1;
+        if n <= UBig::MAX_LEN / 2 {
+            let layout = memory_requirement_up_to(n);
+            assert!(layout.size() >= ntt::memory_requirement_up_to(n).size());
+            if NTT_U32 {
+                let u32_layout = ntt_u32::memory_requirement_up_to(ntt_u32::MAX_LEN);
+                assert!(layout.size() >= u32_layout.size());
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_len_toom_cook() {
+        for b_len in MIN_LEN_TOOM_COOK..200 {
+            for a_len in b_len..3 * b_len {
+                if let Shape::Unbalanced(variant) = choose_shape(a_len, b_len) {
+                    assert!(toom_unbalanced::supports(variant, a_len, b_len));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_karatsuba_toom_3_parallel() {
+        // Long enough to run the recursive multiplications in parallel with the `rayon` feature.
+        let n = 2 * thresholds::current().parallel + 1;
+        // With the default thresholds the halves use Toom-3, which needs more memory.
+        let layout = memory::add_layout(
+            karatsuba::memory_requirement_up_to(n),
+            memory_requirement_up_to(n),
+        );
+        testing::check_mul(n, n, layout, karatsuba::add_signed_mul_same_len);
+        testing::check_sqr(n, layout, karatsuba::sqr);
+        let n = 3 * thresholds::current().parallel + 1;
+        let layout = toom_3::memory_requirement_up_to(n);
+        testing::check_mul(n, n, layout, toom_3::add_signed_mul_same_len);
+        testing::check_sqr(n, layout, toom_3::sqr);
+    }
+
+    #[test]
+    fn test_add_signed_mul_random_lengths() {
+        let mut rng = Lcg(1);
+        for _ in 0..40 {
+            let b_len = thresholds::current().mul_karatsuba + 1 + rng.below(400);
+            let a_len = b_len + rng.below(3 * b_len);
+            let a = rng.words(a_len);
+            let b = rng.words(b_len);
+            let sign = if rng.below(2) == 0 {
+                Positive
+            } else {
+                Negative
+            };
+            let initial = rng.words(a_len + b_len);
+
+            let mut expected = initial.clone();
+            let expected_carry = simple::add_signed_mul(&mut expected, sign, &a, &b);
+
+            let mut allocation = MemoryAllocation::new(memory_requirement_exact(b_len));
+            let mut memory = allocation.memory();
+            let mut c = initial.clone();
+            let carry = add_signed_mul(&mut c, sign, &a, &b, &mut memory);
+            assert_eq!(
+                (c, carry),
+                (expected, expected_carry),
+                "a_len={} b_len={} shape={:?}",
+                a_len,
+                b_len,
+                choose_shape(a_len, b_len)
+            );
+        }
     }
 }
@@ -1,12 +1,27 @@
 //! Number-theoretic multiplication algorithm.
+//!
+//! The product is computed modulo three word-sized primes using number-theoretic transforms of
+//! length 2^order, and recombined using the Chinese remainder theorem.
+//!
+//! Multiplications modulo the primes use Montgomery reduction. The twiddle factors are stored
+//! in Montgomery form, so that multiplying by them gives a plain residue. They are computed once
+//! per multiplication and shared by all its blocks.
 
 use crate::{
+    add,
     arch::{
         ntt::{MAX_ORDER, PRIMES},
-        word::Word,
+        word::{DoubleWord, SignedWord, Word},
     },
+    math,
+    memory::{self, Memory},
     modular::modulo_ring::ModuloRingSmall,
+    parallel,
+    primitive::{double_word, extend_word, split_double_word},
+    sign::Sign,
 };
+use alloc::alloc::Layout;
+use static_assertions::const_assert;
 
 /// The number of prime factors in the ring.
 pub(crate) const NUM_PRIMES: usize = 3;
@@ -26,6 +41,46 @@ const FIELDS: [ModuloRingSmall; NUM_PRIMES] = [
     ModuloRingSmall::new(PRIMES[2].prime),
 ];
 
+/// Montgomery multiplication modulo the primes, with R = 2^WORD_BITS.
+const MONTGOMERY: [Montgomery; NUM_PRIMES] = [
+    Montgomery::new(PRIMES[0].prime),
+    Montgomery::new(PRIMES[1].prime),
+    Montgomery::new(PRIMES[2].prime),
+];
+
+/// Montgomery multiplication modulo an odd prime p.
+///
+/// Residues are normalized to 0..p.
+struct Montgomery {
+    prime: Word,
+    /// 1 / p mod R.
+    inv: Word,
+}
+
+impl Montgomery {
+    const fn new(prime: Word) -> Montgomery {
+        // Newton's method: inv is correct modulo 2^3, and each step doubles the number of
+        // correct bits.
+        let mut inv = prime;
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul((2 as Word).wrapping_sub(prime.wrapping_mul(inv)));
+            i += 1;
+        }
+        Montgomery { prime, inv }
+    }
+
+    /// a * b / R mod p, for a * b < p * R.
+    #[inline]
+    fn mul(&self, a: Word, b: Word) -> Word {
+        let (t_lo, t_hi) = split_double_word(extend_word(a) * extend_word(b));
+        // t - m * p is divisible by R, and lies in (-p * R, p * R).
+        let m = t_lo.wrapping_mul(self.inv);
+        let (_, mp_hi) = split_double_word(extend_word(m) * extend_word(self.prime));
+        sub_normalized(t_hi, mp_hi, self.prime)
+    }
+}
+
 /// An element of the three-prime ring.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct RingElement {
@@ -62,16 +117,6 @@ impl RingElement {
             ],
         }
     }
-
-    const fn inverse(&self) -> RingElement {
-        RingElement {
-            normalized: [
-                FIELDS[0].const_pow_normalized(self.normalized[0], PRIMES[0].prime - 2),
-                FIELDS[1].const_pow_normalized(self.normalized[1], PRIMES[1].prime - 2),
-                FIELDS[2].const_pow_normalized(self.normalized[2], PRIMES[2].prime - 2),
-            ],
-        }
-    }
 }
 
 const MAX_ORDER_ROOT: RingElement = RingElement {
@@ -85,13 +130,8 @@ const MAX_ORDER_ROOT: RingElement = RingElement {
 type RootTable = [RingElement; MAX_ORDER as usize + 1];
 
 /// ROOTS[order]^(2^order) = 1
-#[allow(dead_code)]
 static ROOTS: RootTable = generate_roots(MAX_ORDER_ROOT);
 
-/// INVERSE_ROOTS[order]^(2^order) = 1
-#[allow(dead_code)]
-static INVERSE_ROOTS: RootTable = generate_roots(MAX_ORDER_ROOT.inverse());
-
 const fn generate_roots(max_order_root: RingElement) -> RootTable {
     let mut table = [RingElement::zero(); MAX_ORDER as usize + 1];
     let mut order = MAX_ORDER as usize;
@@ -103,16 +143,460 @@ const fn generate_roots(max_order_root: RingElement) -> RootTable {
     table
 }
 
+// Residues modulo a smaller prime are valid residues modulo a larger prime, which simplifies the
+// Chinese remainder theorem.
+const_assert!(PRIMES[0].prime < PRIMES[1].prime && PRIMES[1].prime < PRIMES[2].prime);
+
+/// 1 / p0 mod p1, normalized.
+const INV_P0_MOD_P1: Word = FIELDS[1].const_pow_normalized(
+    FIELDS[1].normalize_word(PRIMES[0].prime),
+    PRIMES[1].prime - 2,
+);
+
+/// 1 / p0 mod p2, normalized.
+const INV_P0_MOD_P2: Word = FIELDS[2].const_pow_normalized(
+    FIELDS[2].normalize_word(PRIMES[0].prime),
+    PRIMES[2].prime - 2,
+);
+
+/// 1 / p1 mod p2, normalized.
+const INV_P1_MOD_P2: Word = FIELDS[2].const_pow_normalized(
+    FIELDS[2].normalize_word(PRIMES[1].prime),
+    PRIMES[2].prime - 2,
+);
+
+/// p0 * p1
+const P0_P1: DoubleWord = extend_word(PRIMES[0].prime) * extend_word(PRIMES[1].prime);
+
+/// The order of the transform used for factors of length up to n.
+///
+/// The transform length 2^order is at least 2n.
+fn order(n: usize) -> u32 {
+    let order = math::ceil_log_2(2 * n);
+    assert!(order <= MAX_ORDER);
+    order
+}
+
+/// Temporary scratch space required for multiplication.
+///
+/// n bounds the length of the smaller factor in words.
+pub(crate) fn memory_requirement_up_to(n: usize) -> Layout {
+    let transform_len = 1 << order(n);
+    // Twiddle factors and residues for each prime, then for each convolution the transform of b.
+    // The product of a block, of up to transform_len + 1 words, reuses the memory of the
+    // convolutions.
+    memory::array_layout::<Word>((2 * NUM_PRIMES + 1) * transform_len + 1)
+}
+
+/// c += sign * a * b
+///
+/// Splits a into blocks of length `2^order + 1 - b.len()`, whose products with b don't wrap
+/// around in a transform of length 2^order.
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul(
+    mut c: &mut [Word],
+    sign: Sign,
+    mut a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    debug_assert!(a.len() >= b.len() && c.len() == a.len() + b.len());
+
+    let order = order(b.len());
+    let block_len = (1 << order) + 1 - b.len();
+    let (twiddles, mut memory) = allocate_twiddles(order, memory);
+    // Like helpers::add_signed_mul_split_into_blocks, except that the shorter last block also
+    // uses the transform, so that all blocks share the twiddle factors.
+    let n = b.len();
+    let mut carry_n: SignedWord = 0; // at c[n]
+    while a.len() > block_len {
+        let (a_lo, a_hi) = a.split_at(block_len);
+        // Propagate carry.
+        carry_n = add::add_signed_word_in_place(&mut c[n..block_len + n], carry_n);
+        carry_n += add_signed_mul_or_sqr(
+            &mut c[..block_len + n],
+            sign,
+            a_lo,
+            Some(b),
+            twiddles,
+            &mut memory,
+        );
+        a = a_hi;
+        c = &mut c[block_len..];
+    }
+    let carry = add::add_signed_word_in_place(&mut c[n..], carry_n);
+    carry + add_signed_mul_or_sqr(c, sign, a, Some(b), twiddles, &mut memory)
+}
+
+/// c += sign * a * b
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul_same_len(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    debug_assert!(a.len() == b.len() && c.len() == a.len() + b.len());
+
+    let (twiddles, mut memory) = allocate_twiddles(order(a.len()), memory);
+    add_signed_mul_or_sqr(c, sign, a, Some(b), twiddles, &mut memory)
+}
+
+/// c = a * a
+pub(crate) fn sqr(c: &mut [Word], a: &[Word], memory: &mut Memory) {
+    debug_assert!(c.len() == 2 * a.len());
+
+    c.fill(0);
+    let (twiddles, mut memory) = allocate_twiddles(order(a.len()), memory);
+    let carry = add_signed_mul_or_sqr(c, Sign::Positive, a, None, twiddles, &mut memory);
+    debug_assert!(carry == 0);
+}
+
+/// Allocate and compute the twiddle factors for transforms of length 2^order, for each prime
+/// one after another.
+fn allocate_twiddles<'a>(order: u32, memory: &'a mut Memory) -> (&'a [Word], Memory<'a>) {
+    let transform_len = 1 << order;
+    let (twiddles, memory) = memory.allocate_slice_fill::<Word>(NUM_PRIMES * transform_len, 0);
+    for (prime, twiddles) in twiddles.chunks_mut(transform_len).enumerate() {
+        fill_twiddles(twiddles, prime, order);
+    }
+    (twiddles, memory)
+}
+
+/// c += sign * a * b, or c += sign * a * a if b is None.
+///
+/// The product must fit in a transform of the length of the twiddle factors of each prime.
+///
+/// Returns carry.
+#[must_use]
+fn add_signed_mul_or_sqr(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: Option<&[Word]>,
+    twiddles: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let transform_len = twiddles.len() / NUM_PRIMES;
+    let b_len = b.map_or(a.len(), <[Word]>::len);
+    debug_assert!(c.len() == a.len() + b_len && a.len() + b_len <= transform_len + 1);
+
+    let (residues, mut memory) = memory.allocate_slice_fill::<Word>(NUM_PRIMES * transform_len, 0);
+    let convolution_layout = memory::array_layout::<Word>(transform_len);
+    // If the memory for the convolutions in parallel can't be allocated, compute them serially
+    // instead.
+    let parallel_allocation = if parallel::is_worthwhile(transform_len) {
+        let layout = memory::repeat_layout(convolution_layout, NUM_PRIMES);
+        memory.try_new_allocation(layout).ok()
+    } else {
+        None
+    };
+    if let Some(mut allocation) = parallel_allocation {
+        let (residues0, residues12) = residues.split_at_mut(transform_len);
+        let (residues1, residues2) = residues12.split_at_mut(transform_len);
+        let (twiddles0, twiddles12) = twiddles.split_at(transform_len);
+        let (twiddles1, twiddles2) = twiddles12.split_at(transform_len);
+        let mut memory = allocation.memory();
+        let (mut memory0, mut memory) = memory.allocate_memory(convolution_layout);
+        let (mut memory1, mut memory2) = memory.allocate_memory(convolution_layout);
+        parallel::join(
+            true,
+            || convolution(residues0, a, b, 0, twiddles0, &mut memory0),
+            || {
+                parallel::join(
+                    true,
+                    || convolution(residues1, a, b, 1, twiddles1, &mut memory1),
+                    || convolution(residues2, a, b, 2, twiddles2, &mut memory2),
+                )
+            },
+        );
+    } else {
+        for (prime, (residues, twiddles)) in residues
+            .chunks_mut(transform_len)
+            .zip(twiddles.chunks(transform_len))
+            .enumerate()
+        {
+            convolution(residues, a, b, prime, twiddles, &mut memory);
+        }
+    }
+
+    let (product, _) = memory.allocate_slice_fill::<Word>(c.len(), 0);
+    recombine(residues, product);
+    add::add_signed_in_place(c, sign, product)
+}
+
+/// Cyclic convolution of a and b (or a and a) modulo the given prime.
+///
+/// Writes the normalized coefficients of the result into residues, whose length is the transform
+/// length.
+fn convolution(
+    residues: &mut [Word],
+    a: &[Word],
+    b: Option<&[Word]>,
+    prime: usize,
+    twiddles: &[Word],
+    memory: &mut Memory,
+) {
+    let montgomery = &MONTGOMERY[prime];
+    let transform_len = residues.len();
+    debug_assert!(twiddles.len() == transform_len);
+
+    load(residues, a, prime);
+    forward_transform(residues, twiddles, montgomery);
+    match b {
+        Some(b) => {
+            let (b_transform, _) = memory.allocate_slice_fill::<Word>(transform_len, 0);
+            load(b_transform, b, prime);
+            forward_transform(b_transform, twiddles, montgomery);
+            for (x, y) in residues.iter_mut().zip(b_transform.iter()) {
+                *x = montgomery.mul(*x, *y);
+            }
+        }
+        None => {
+            for x in residues.iter_mut() {
+                *x = montgomery.mul(*x, *x);
+            }
+        }
+    }
+    inverse_transform(residues, twiddles, montgomery);
+
+    // Divide by the transform length, and multiply by the R lost in the pointwise products.
+    // 1 / 2^order = p - (p - 1) / 2^order because p - 1 is divisible by 2^order.
+    let p = PRIMES[prime].prime;
+    let inverse_len = p - (p - 1) / transform_len as Word;
+    let scale = to_montgomery(to_montgomery(inverse_len, prime), prime);
+    for x in residues.iter_mut() {
+        *x = montgomery.mul(*x, scale);
+    }
+}
+
+/// x * R mod p, for the given prime.
+fn to_montgomery(x: Word, prime: usize) -> Word {
+    let field = &FIELDS[prime];
+    let shift = field.shift();
+    // (x * 2^shift) * R mod (p * 2^shift) = (x * R mod p) * 2^shift
+    let (_, rem) = field.fast_div().div_rem(double_word(0, x << shift));
+    rem >> shift
+}
+
+/// Twiddle factors for each level of the transform of length 2^order, in Montgomery form, so
+/// that they are read sequentially.
+///
+/// twiddles[half + j] = r^j * R where r is a root of order 2 * half, for
+/// half = 1, 2, 4, ..., 2^order / 2 and j < half.
+fn fill_twiddles(twiddles: &mut [Word], prime: usize, order: u32) {
+    let montgomery = &MONTGOMERY[prime];
+    let n = twiddles.len();
+    debug_assert!(n == 1 << order);
+    let root = to_montgomery(
+        ROOTS[order as usize].normalized[prime] >> FIELDS[prime].shift(),
+        prime,
+    );
+    let mut power = to_montgomery(1, prime);
+    for x in twiddles[n / 2..].iter_mut() {
+        *x = power;
+        power = montgomery.mul(power, root);
+    }
+    for i in (1..n / 2).rev() {
+        twiddles[i] = twiddles[2 * i];
+    }
+}
+
+/// Load words as normalized residues, padded with zeros.
+fn load(residues: &mut [Word], words: &[Word], prime: usize) {
+    let montgomery = &MONTGOMERY[prime];
+    // x * (R mod p) / R = x mod p
+    let one = to_montgomery(1, prime);
+    let (lo, hi) = residues.split_at_mut(words.len());
+    for (x, word) in lo.iter_mut().zip(words.iter()) {
+        *x = montgomery.mul(*word, one);
+    }
+    hi.fill(0);
+}
+
+/// Forward transform, decimation in frequency.
+///
+/// The input is in natural order, the output in bit-reversed order.
+fn forward_transform(values: &mut [Word], twiddles: &[Word], montgomery: &Montgomery) {
+    let p = montgomery.prime;
+    let mut half = values.len() / 2;
+    while half >= 1 {
+        let level_twiddles = &twiddles[half..2 * half];
+        for chunk in values.chunks_mut(2 * half) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            for ((x, y), w) in lo.iter_mut().zip(hi.iter_mut()).zip(level_twiddles) {
+                let (u, v) = (*x, *y);
+                *x = add_normalized(u, v, p);
+                *y = montgomery.mul(sub_normalized(u, v, p), *w);
+            }
+        }
+        half /= 2;
+    }
+}
+
+/// Inverse transform without division by the length, decimation in time.
+///
+/// The input is in bit-reversed order, the output in natural order.
+///
+/// With r a root of order 2 * half, r^half = -1, so the inverse twiddle factor
+/// r^-j = -r^(half-j) is read from the same table in reverse.
+fn inverse_transform(values: &mut [Word], twiddles: &[Word], montgomery: &Montgomery) {
+    let p = montgomery.prime;
+    let mut half = 1;
+    while half < values.len() {
+        let level_twiddles = &twiddles[half..2 * half];
+        for chunk in values.chunks_mut(2 * half) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            let (u, v) = (lo[0], hi[0]);
+            lo[0] = add_normalized(u, v, p);
+            hi[0] = sub_normalized(u, v, p);
+            for ((x, y), w) in lo[1..]
+                .iter_mut()
+                .zip(hi[1..].iter_mut())
+                .zip(level_twiddles[1..].iter().rev())
+            {
+                let u = *x;
+                // v = -y * r^-j
+                let v = montgomery.mul(*y, *w);
+                *x = sub_normalized(u, v, p);
+                *y = add_normalized(u, v, p);
+            }
+        }
+        half *= 2;
+    }
+}
+
+/// (a + b) mod m for normalized values.
+#[inline]
+fn add_normalized(a: Word, b: Word, m: Word) -> Word {
+    wrap_negative(
+        (extend_word(a) + extend_word(b)).wrapping_sub(extend_word(m)),
+        m,
+    )
+}
+
+/// (a - b) mod m for normalized values.
+#[inline]
+fn sub_normalized(a: Word, b: Word, m: Word) -> Word {
+    wrap_negative(extend_word(a).wrapping_sub(extend_word(b)), m)
+}
+
+/// x mod m for x in -m..m, given as a wrapped `DoubleWord`.
+///
+/// The high word is used as a mask rather than a comparison, so that the compiler doesn't turn
+/// it into a branch, which would be unpredictable in the transforms.
+#[inline]
+fn wrap_negative(x: DoubleWord, m: Word) -> Word {
+    let (lo, hi) = split_double_word(x);
+    lo.wrapping_add(m & hi)
+}
+
+/// Combine residues modulo the three primes into the product.
+///
+/// residues contains the coefficients modulo each prime one after another.
+fn recombine(residues: &[Word], product: &mut [Word]) {
+    let transform_len = residues.len() / NUM_PRIMES;
+    debug_assert!(product.len() <= transform_len + 1);
+
+    let (residues0, residues12) = residues.split_at(transform_len);
+    let (residues1, residues2) = residues12.split_at(transform_len);
+    let (p0_p1_lo, p0_p1_hi) = split_double_word(P0_P1);
+    let (field1, field2) = (&FIELDS[1], &FIELDS[2]);
+    let (m1, m2) = (field1.normalized_modulus(), field2.normalized_modulus());
+    let (shift1, shift2) = (field1.shift(), field2.shift());
+
+    // carry < 2^(2*WORD_BITS)
+    let (mut carry_lo, mut carry_hi): (Word, Word) = (0, 0);
+    for (i, out) in product.iter_mut().enumerate() {
+        let (r0, r1, r2) = if i < transform_len {
+            (residues0[i], residues1[i], residues2[i])
+        } else {
+            (0, 0, 0)
+        };
+        // Garner's algorithm: x = r0 + p0 * v1 + p0 * p1 * v2.
+        let v1 = field1.mul_normalized(
+            sub_normalized(r1 << shift1, r0 << shift1, m1),
+            INV_P0_MOD_P1,
+        ) >> shift1;
+        let w2 = field2.mul_normalized(
+            sub_normalized(r2 << shift2, r0 << shift2, m2),
+            INV_P0_MOD_P2,
+        );
+        let v2 =
+            field2.mul_normalized(sub_normalized(w2, v1 << shift2, m2), INV_P1_MOD_P2) >> shift2;
+
+        // x + carry < p0 * p1 * p2 + 2^(2*WORD_BITS) <= 2^(3*WORD_BITS)
+        let (x0, x1) =
+            split_double_word(extend_word(PRIMES[0].prime) * extend_word(v1) + extend_word(r0));
+        let (y0, y1) = split_double_word(extend_word(p0_p1_lo) * extend_word(v2));
+        let (z0, z1) = split_double_word(extend_word(p0_p1_hi) * extend_word(v2));
+        let (s0, s0_carry) =
+            split_double_word(extend_word(x0) + extend_word(y0) + extend_word(carry_lo));
+        let (s1, s1_carry) = split_double_word(
+            extend_word(x1)
+                + extend_word(y1)
+                + extend_word(z0)
+                + extend_word(carry_hi)
+                + extend_word(s0_carry),
+        );
+        *out = s0;
+        carry_lo = s1;
+        carry_hi = z1 + s1_carry;
+    }
+    debug_assert!(carry_lo == 0 && carry_hi == 0);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mul::testing;
 
     #[test]
-    fn test_inverse() {
-        let one: Word = 1;
-        let one = RingElement::from(one);
-        assert_eq!(MAX_ORDER_ROOT.inverse().mul(&MAX_ORDER_ROOT), one);
-        assert_eq!(MAX_ORDER_ROOT.inverse().inverse(), MAX_ORDER_ROOT);
+    fn test_montgomery() {
+        for (prime, montgomery) in MONTGOMERY.iter().enumerate() {
+            let p = montgomery.prime;
+            assert_eq!(p.wrapping_mul(montgomery.inv), 1);
+            let one = to_montgomery(1, prime);
+            for &(a, b) in &[(0, 5), (1, 1), (2, p - 1), (p - 1, p - 1), (p / 3, p / 2)] {
+                let expected = FIELDS[prime].mul_normalized(
+                    FIELDS[prime].normalize_word(a),
+                    FIELDS[prime].normalize_word(b),
+                ) >> FIELDS[prime].shift();
+                assert_eq!(montgomery.mul(a, to_montgomery(b, prime)), expected);
+                assert_eq!(
+                    montgomery.mul(montgomery.mul(a, b), one),
+                    montgomery.mul(a, b)
+                );
+            }
+            assert_eq!(montgomery.mul(Word::MAX, one), Word::MAX % p);
+        }
+    }
+
+    #[test]
+    fn test_twiddles() {
+        let mut twiddles = [0; 16];
+        for prime in 0..NUM_PRIMES {
+            let montgomery = &MONTGOMERY[prime];
+            fill_twiddles(&mut twiddles, prime, 4);
+            // twiddles[8 + 1] is a root r of order 16, so r^8 = -1.
+            let mut power = 1;
+            for _ in 0..8 {
+                power = montgomery.mul(power, twiddles[9]);
+            }
+            assert_eq!(power, PRIMES[prime].prime - 1);
+            // Each level uses the squares of the roots of the next level.
+            assert_eq!(twiddles[1], to_montgomery(1, prime));
+            assert_eq!(twiddles[2..4], [twiddles[8], twiddles[12]]);
+            assert_eq!(
+                twiddles[4..8],
+                [twiddles[8], twiddles[10], twiddles[12], twiddles[14]]
+            );
+        }
     }
 
     #[test]
@@ -121,7 +605,29 @@ mod tests {
         let one = RingElement::from(one);
         assert_eq!(ROOTS[0], one);
         assert_ne!(ROOTS[1], one);
-        assert_eq!(INVERSE_ROOTS[0], one);
-        assert_ne!(INVERSE_ROOTS[1], one);
+    }
+
+    fn check_mul(a_len: usize, b_len: usize) {
+        let layout = memory_requirement_up_to(b_len);
+        if a_len == b_len {
+            testing::check_mul(a_len, b_len, layout, add_signed_mul_same_len);
+            testing::check_sqr(a_len, layout, sqr);
+        } else {
+            testing::check_mul(a_len, b_len, layout, add_signed_mul);
+        }
+    }
+
+    #[test]
+    fn test_mul_same_len() {
+        for n in &[16, 100, 255, 256, 257, 1000] {
+            check_mul(*n, *n);
+        }
+    }
+
+    #[test]
+    fn test_mul_unbalanced() {
+        for (a_len, b_len) in &[(300, 200), (313, 200), (626, 200), (1000, 200), (3000, 129)] {
+            check_mul(*a_len, *b_len);
+        }
     }
 }
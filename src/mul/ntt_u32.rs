@@ -0,0 +1,618 @@
+//! Number-theoretic multiplication algorithm with 31-bit primes.
+//!
+//! The factors are split into 32-bit digits whatever the size of `Word`. The product is computed
+//! modulo three 31-bit primes using number-theoretic transforms on `u32` lanes, and
+//! recombined using the Chinese remainder theorem.
+//!
+//! Multiplications by twiddle factors use Shoup's method: with w' = floor(w * 2^32 / p)
+//! precomputed, x * w mod p takes two 32 x 32 -> 64 bit multiplications and no division.
+//! Pointwise products use Montgomery reduction.
+
+use crate::{
+    add,
+    arch::word::{SignedWord, Word},
+    math,
+    memory::{self, Memory},
+    mul::helpers,
+    parallel,
+    primitive::WORD_BITS_USIZE,
+    sign::Sign,
+};
+use alloc::alloc::Layout;
+use static_assertions::const_assert;
+
+/// The number of primes.
+const NUM_PRIMES: usize = 3;
+
+/// Maximum order of the number-theoretic transform.
+///
+/// There are only five primes between 2^30 and 2^31 of the form k * 2^25 + 1, and only one of
+/// the form k * 2^27 + 1.
+const MAX_ORDER: u32 = 25;
+
+/// Bits in a digit.
+const DIGIT_BITS: usize = 32;
+
+/// Maximum length of the smaller factor in words.
+///
+/// Twice as many digits fill the largest transform. Longer factors use the `Word` transform.
+pub(crate) const MAX_LEN: usize = match 1usize.checked_shl(MAX_ORDER - 1) {
+    Some(max_digits) => max_digits.saturating_mul(DIGIT_BITS) / WORD_BITS_USIZE,
+    None => usize::MAX,
+};
+
+/// Fields modulo 31-bit primes of the form k * 2^MAX_ORDER + 1, with roots of order
+/// 2^MAX_ORDER.
+const FIELDS: [Field; NUM_PRIMES] = [
+    Field::new(0x6c000001, 0x5f9ead9e),
+    Field::new(0x78000001, 0x7541f45d),
+    Field::new(0x7e000001, 0x757d36ee),
+];
+
+// Residues modulo a smaller prime are valid residues modulo a larger prime, which simplifies the
+// Chinese remainder theorem. Sums of two residues fit in u32.
+const_assert!(FIELDS[0].prime < FIELDS[1].prime && FIELDS[1].prime < FIELDS[2].prime);
+const_assert!(FIELDS[0].prime > 1 << 30 && FIELDS[2].prime < 1 << 31);
+
+// The coefficients of the product of factors of up to 2^(MAX_ORDER - 1) digits are less than
+// p0 * p1 * p2.
+const_assert!(
+    (1u128 << (MAX_ORDER - 1)) * (u32::MAX as u128) * (u32::MAX as u128)
+        < FIELDS[0].prime as u128 * FIELDS[1].prime as u128 * FIELDS[2].prime as u128
+);
+
+/// 1 / p0 mod p1.
+const INV_P0_MOD_P1: Twiddle = FIELDS[1].twiddle(FIELDS[1].inverse(FIELDS[0].prime));
+
+/// 1 / p0 mod p2.
+const INV_P0_MOD_P2: Twiddle = FIELDS[2].twiddle(FIELDS[2].inverse(FIELDS[0].prime));
+
+/// 1 / p1 mod p2.
+const INV_P1_MOD_P2: Twiddle = FIELDS[2].twiddle(FIELDS[2].inverse(FIELDS[1].prime));
+
+/// p0 * p1
+const P0_P1: u64 = FIELDS[0].prime as u64 * FIELDS[1].prime as u64;
+
+/// Arithmetic modulo a prime p < 2^31.
+///
+/// Values are normalized to 0..p.
+struct Field {
+    prime: u32,
+    /// -1 / p mod 2^32, for Montgomery reduction.
+    neg_inv: u32,
+    /// A root of order 2^MAX_ORDER.
+    max_order_root: u32,
+}
+
+/// A value with its Shoup quotient, for fast multiplication by it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Twiddle {
+    value: u32,
+    /// floor(value * 2^32 / p)
+    quotient: u32,
+}
+
+impl Field {
+    const fn new(prime: u32, max_order_root: u32) -> Field {
+        // Newton's method: inv is correct modulo 2^3, and each step doubles the number of
+        // correct bits.
+        let mut inv = prime;
+        let mut i = 0;
+        while i < 4 {
+            inv = inv.wrapping_mul(2u32.wrapping_sub(prime.wrapping_mul(inv)));
+            i += 1;
+        }
+        Field {
+            prime,
+            neg_inv: inv.wrapping_neg(),
+            max_order_root,
+        }
+    }
+
+    /// a * b mod p, using division.
+    const fn mul(&self, a: u32, b: u32) -> u32 {
+        (a as u64 * b as u64 % self.prime as u64) as u32
+    }
+
+    /// a^exp mod p, using division.
+    const fn pow(&self, a: u32, mut exp: u32) -> u32 {
+        let mut result = 1;
+        let mut base = a;
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// 1 / a mod p.
+    const fn inverse(&self, a: u32) -> u32 {
+        self.pow(a, self.prime - 2)
+    }
+
+    /// Prepare a normalized value for multiplication by it.
+    const fn twiddle(&self, value: u32) -> Twiddle {
+        Twiddle {
+            value,
+            quotient: (((value as u64) << 32) / self.prime as u64) as u32,
+        }
+    }
+
+    /// A root of order 2^order.
+    fn root(&self, order: u32) -> u32 {
+        self.pow(self.max_order_root, 1 << (MAX_ORDER - order))
+    }
+
+    /// (a + b) mod p
+    #[inline]
+    fn add(&self, a: u32, b: u32) -> u32 {
+        let sum = a + b;
+        if sum >= self.prime {
+            sum - self.prime
+        } else {
+            sum
+        }
+    }
+
+    /// (a - b) mod p
+    #[inline]
+    fn sub(&self, a: u32, b: u32) -> u32 {
+        if a >= b {
+            a - b
+        } else {
+            a.wrapping_sub(b).wrapping_add(self.prime)
+        }
+    }
+
+    /// x * w mod p using Shoup's method. x can be any u32, not necessarily normalized.
+    #[inline]
+    fn mul_twiddle(&self, x: u32, w: Twiddle) -> u32 {
+        let q = ((x as u64 * w.quotient as u64) >> 32) as u32;
+        // The exact difference is in 0..2p, so the wrapping arithmetic is exact.
+        let r = x
+            .wrapping_mul(w.value)
+            .wrapping_sub(q.wrapping_mul(self.prime));
+        if r >= self.prime {
+            r - self.prime
+        } else {
+            r
+        }
+    }
+
+    /// a * b / 2^32 mod p using Montgomery reduction.
+    #[inline]
+    fn mul_montgomery(&self, a: u32, b: u32) -> u32 {
+        let t = a as u64 * b as u64;
+        let m = (t as u32).wrapping_mul(self.neg_inv);
+        // t + m * p < 2^62 + 2^63, and it is divisible by 2^32.
+        let r = ((t + m as u64 * self.prime as u64) >> 32) as u32;
+        if r >= self.prime {
+            r - self.prime
+        } else {
+            r
+        }
+    }
+}
+
+/// Number of digits in a number of len words.
+fn num_digits(len: usize) -> usize {
+    math::ceil_div(len * WORD_BITS_USIZE, DIGIT_BITS)
+}
+
+/// Digit i of words.
+#[inline]
+fn digit(words: &[Word], i: usize) -> u32 {
+    if WORD_BITS_USIZE >= DIGIT_BITS {
+        let bit = i * DIGIT_BITS;
+        low_u32(words[bit / WORD_BITS_USIZE] >> (bit % WORD_BITS_USIZE))
+    } else {
+        // Two words per digit.
+        let lo = low_u32(words[2 * i]);
+        let hi = words.get(2 * i + 1).map_or(0, |word| low_u32(*word));
+        lo | hi << 16
+    }
+}
+
+/// The low 32 bits of a word.
+// Word may be u32.
+#[allow(clippy::unnecessary_cast)]
+#[inline]
+fn low_u32(word: Word) -> u32 {
+    word as u32
+}
+
+/// Set digit i of words, which is zero.
+#[inline]
+fn set_digit(words: &mut [Word], i: usize, digit: u32) {
+    if WORD_BITS_USIZE >= DIGIT_BITS {
+        let bit = i * DIGIT_BITS;
+        words[bit / WORD_BITS_USIZE] |= (digit as Word) << (bit % WORD_BITS_USIZE);
+    } else {
+        words[2 * i] = digit as Word;
+        match words.get_mut(2 * i + 1) {
+            Some(word) => *word = (digit >> 16) as Word,
+            None => debug_assert!(digit >> 16 == 0),
+        }
+    }
+}
+
+/// The order of the transform used for factors of length up to n.
+///
+/// The transform length 2^order is at least twice the number of digits.
+fn order(n: usize) -> u32 {
+    let order = math::ceil_log_2(2 * num_digits(n));
+    assert!(order <= MAX_ORDER);
+    order
+}
+
+/// Temporary scratch space required for a convolution.
+fn memory_requirement_convolution(transform_len: usize) -> Layout {
+    // Twiddle factors and inverse twiddle factors, then the transform of b.
+    memory::add_layout(
+        memory::array_layout::<Twiddle>(2 * transform_len),
+        memory::array_layout::<u32>(transform_len),
+    )
+}
+
+/// Temporary scratch space required for multiplication.
+///
+/// n bounds the length of the smaller factor in words.
+pub(crate) fn memory_requirement_up_to(n: usize) -> Layout {
+    let transform_len = 1 << order(n);
+    // Residues for each prime, then the convolutions. The product of a block, of up to
+    // transform_len + 1 digits, reuses the memory of the convolutions.
+    let max_product_len = (transform_len + 1) * DIGIT_BITS / WORD_BITS_USIZE + 1;
+    memory::add_layout(
+        memory::array_layout::<u32>(NUM_PRIMES * transform_len),
+        memory::max_layout(
+            memory_requirement_convolution(transform_len),
+            memory::array_layout::<Word>(max_product_len),
+        ),
+    )
+}
+
+/// c += sign * a * b
+///
+/// Splits a into blocks whose products with b have at most `2^order + 1` digits, so that they
+/// don't wrap around in a transform of length 2^order.
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    debug_assert!(a.len() >= b.len() && c.len() == a.len() + b.len());
+
+    let order = order(b.len());
+    let block_len = ((1 << order) + 1 - num_digits(b.len())) * DIGIT_BITS / WORD_BITS_USIZE;
+    if a.len() <= block_len {
+        add_signed_mul_or_sqr(c, sign, a, Some(b), memory, order)
+    } else {
+        helpers::add_signed_mul_split_into_blocks(
+            c,
+            sign,
+            a,
+            b,
+            block_len,
+            memory,
+            |c, sign, a, b, memory| add_signed_mul_or_sqr(c, sign, a, Some(b), memory, order),
+        )
+    }
+}
+
+/// c += sign * a * b
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul_same_len(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    debug_assert!(a.len() == b.len() && c.len() == a.len() + b.len());
+
+    add_signed_mul_or_sqr(c, sign, a, Some(b), memory, order(a.len()))
+}
+
+/// c = a * a
+pub(crate) fn sqr(c: &mut [Word], a: &[Word], memory: &mut Memory) {
+    debug_assert!(c.len() == 2 * a.len());
+
+    c.fill(0);
+    let carry = add_signed_mul_or_sqr(c, Sign::Positive, a, None, memory, order(a.len()));
+    debug_assert!(carry == 0);
+}
+
+/// c += sign * a * b, or c += sign * a * a if b is None.
+///
+/// The product must fit in a transform of length 2^order.
+///
+/// Returns carry.
+#[must_use]
+fn add_signed_mul_or_sqr(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: Option<&[Word]>,
+    memory: &mut Memory,
+    order: u32,
+) -> SignedWord {
+    let transform_len = 1 << order;
+    let b_len = b.map_or(a.len(), <[Word]>::len);
+    debug_assert!(
+        c.len() == a.len() + b_len && num_digits(a.len()) + num_digits(b_len) <= transform_len + 1
+    );
+
+    let (residues, mut memory) = memory.allocate_slice_fill::<u32>(NUM_PRIMES * transform_len, 0);
+    let convolution_layout = memory_requirement_convolution(transform_len);
+    // If the memory for the convolutions in parallel can't be allocated, compute them serially
+    // instead.
+    let parallel_allocation = if parallel::is_worthwhile(transform_len) {
+        let layout = memory::repeat_layout(convolution_layout, NUM_PRIMES);
+        memory.try_new_allocation(layout).ok()
+    } else {
+        None
+    };
+    if let Some(mut allocation) = parallel_allocation {
+        let (residues0, residues12) = residues.split_at_mut(transform_len);
+        let (residues1, residues2) = residues12.split_at_mut(transform_len);
+        let mut memory = allocation.memory();
+        let (mut memory0, mut memory) = memory.allocate_memory(convolution_layout);
+        let (mut memory1, mut memory2) = memory.allocate_memory(convolution_layout);
+        parallel::join(
+            true,
+            || convolution(residues0, a, b, &FIELDS[0], order, &mut memory0),
+            || {
+                parallel::join(
+                    true,
+                    || convolution(residues1, a, b, &FIELDS[1], order, &mut memory1),
+                    || convolution(residues2, a, b, &FIELDS[2], order, &mut memory2),
+                )
+            },
+        );
+    } else {
+        for (field, residues) in FIELDS.iter().zip(residues.chunks_mut(transform_len)) {
+            convolution(residues, a, b, field, order, &mut memory);
+        }
+    }
+
+    let (product, _) = memory.allocate_slice_fill::<Word>(c.len(), 0);
+    recombine(residues, product);
+    add::add_signed_in_place(c, sign, product)
+}
+
+/// Cyclic convolution of the digits of a and b (or a and a) modulo the prime.
+///
+/// Writes the normalized coefficients of the result into residues, whose length is the transform
+/// length.
+fn convolution(
+    residues: &mut [u32],
+    a: &[Word],
+    b: Option<&[Word]>,
+    field: &Field,
+    order: u32,
+    memory: &mut Memory,
+) {
+    let transform_len = residues.len();
+    debug_assert!(transform_len == 1 << order);
+
+    let zero = field.twiddle(0);
+    let (twiddles, mut memory) = memory.allocate_slice_fill::<Twiddle>(transform_len, zero);
+    let (inverse_twiddles, mut memory) = memory.allocate_slice_fill::<Twiddle>(transform_len, zero);
+    let root = field.root(order);
+    fill_twiddles(twiddles, field, root);
+    fill_twiddles(inverse_twiddles, field, field.inverse(root));
+
+    load(residues, a, field);
+    forward_transform(residues, twiddles, field);
+    match b {
+        Some(b) => {
+            let (b_transform, _) = memory.allocate_slice_fill::<u32>(transform_len, 0);
+            load(b_transform, b, field);
+            forward_transform(b_transform, twiddles, field);
+            for (x, y) in residues.iter_mut().zip(b_transform.iter()) {
+                *x = field.mul_montgomery(*x, *y);
+            }
+        }
+        None => {
+            for x in residues.iter_mut() {
+                *x = field.mul_montgomery(*x, *x);
+            }
+        }
+    }
+    inverse_transform(residues, inverse_twiddles, field);
+
+    // Divide by the transform length, and multiply by the 2^32 lost in Montgomery reduction.
+    let r = ((1u64 << 32) % field.prime as u64) as u32;
+    let scale = field.twiddle(field.mul(r, field.inverse(transform_len as u32)));
+    for x in residues.iter_mut() {
+        *x = field.mul_twiddle(*x, scale);
+    }
+}
+
+/// Twiddle factors for each level of the transform, so that they are read sequentially.
+///
+/// twiddles[half + j] = r^j where r is a root of order 2 * half, for half = 1, 2, 4, ..., n / 2
+/// and j < half. root is a root of order n.
+fn fill_twiddles(twiddles: &mut [Twiddle], field: &Field, root: u32) {
+    let n = twiddles.len();
+    let root = field.twiddle(root);
+    let mut power = 1;
+    for x in twiddles[n / 2..].iter_mut() {
+        *x = field.twiddle(power);
+        power = field.mul_twiddle(power, root);
+    }
+    for i in (1..n / 2).rev() {
+        twiddles[i] = twiddles[2 * i];
+    }
+}
+
+/// Load the digits of words as normalized residues, padded with zeros.
+fn load(residues: &mut [u32], words: &[Word], field: &Field) {
+    let one = field.twiddle(1);
+    let (lo, hi) = residues.split_at_mut(num_digits(words.len()));
+    for (i, x) in lo.iter_mut().enumerate() {
+        *x = field.mul_twiddle(digit(words, i), one);
+    }
+    hi.fill(0);
+}
+
+/// Forward transform, decimation in frequency.
+///
+/// The input is in natural order, the output in bit-reversed order.
+fn forward_transform(values: &mut [u32], twiddles: &[Twiddle], field: &Field) {
+    let mut half = values.len() / 2;
+    while half >= 1 {
+        let level_twiddles = &twiddles[half..2 * half];
+        for chunk in values.chunks_mut(2 * half) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            for ((x, y), w) in lo.iter_mut().zip(hi.iter_mut()).zip(level_twiddles) {
+                let (u, v) = (*x, *y);
+                *x = field.add(u, v);
+                *y = field.mul_twiddle(field.sub(u, v), *w);
+            }
+        }
+        half /= 2;
+    }
+}
+
+/// Inverse transform without division by the length, decimation in time.
+///
+/// The input is in bit-reversed order, the output in natural order.
+fn inverse_transform(values: &mut [u32], inverse_twiddles: &[Twiddle], field: &Field) {
+    let mut half = 1;
+    while half < values.len() {
+        let level_twiddles = &inverse_twiddles[half..2 * half];
+        for chunk in values.chunks_mut(2 * half) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            for ((x, y), w) in lo.iter_mut().zip(hi.iter_mut()).zip(level_twiddles) {
+                let u = *x;
+                let v = field.mul_twiddle(*y, *w);
+                *x = field.add(u, v);
+                *y = field.sub(u, v);
+            }
+        }
+        half *= 2;
+    }
+}
+
+/// Combine residues modulo the three primes into the product.
+///
+/// residues contains the coefficients modulo each prime one after another.
+fn recombine(residues: &[u32], product: &mut [Word]) {
+    let transform_len = residues.len() / NUM_PRIMES;
+    let num_digits = num_digits(product.len());
+    debug_assert!(num_digits <= transform_len + 1);
+
+    let (residues0, residues12) = residues.split_at(transform_len);
+    let (residues1, residues2) = residues12.split_at(transform_len);
+    let (field1, field2) = (&FIELDS[1], &FIELDS[2]);
+    let (p0_p1_lo, p0_p1_hi) = (P0_P1 as u32 as u64, P0_P1 >> 32);
+
+    // carry < 2^62
+    let mut carry: u64 = 0;
+    for i in 0..num_digits {
+        let (r0, r1, r2) = if i < transform_len {
+            (residues0[i], residues1[i], residues2[i])
+        } else {
+            (0, 0, 0)
+        };
+        // Garner's algorithm: x = r0 + p0 * v1 + p0 * p1 * v2.
+        let v1 = field1.mul_twiddle(field1.sub(r1, r0), INV_P0_MOD_P1);
+        let w2 = field2.mul_twiddle(field2.sub(r2, r0), INV_P0_MOD_P2);
+        let v2 = field2.mul_twiddle(field2.sub(w2, v1), INV_P1_MOD_P2);
+
+        // x + carry = x_lo + x_hi * 2^32 with x_lo, x_hi < 2^63.
+        let x0 = r0 as u64 + FIELDS[0].prime as u64 * v1 as u64;
+        let x1_lo = p0_p1_lo * v2 as u64;
+        let x1_hi = p0_p1_hi * v2 as u64;
+        let x_lo = (x0 as u32 as u64) + (x1_lo as u32 as u64) + carry;
+        let x_hi = (x0 >> 32) + (x1_lo >> 32) + x1_hi;
+        set_digit(product, i, x_lo as u32);
+        carry = (x_lo >> 32) + x_hi;
+    }
+    debug_assert!(carry == 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mul::testing;
+
+    #[test]
+    fn test_fields() {
+        for field in &FIELDS {
+            assert_eq!(field.prime.wrapping_mul(field.neg_inv), u32::MAX);
+            assert_eq!(field.pow(field.max_order_root, 1 << MAX_ORDER), 1);
+            assert_ne!(field.pow(field.max_order_root, 1 << (MAX_ORDER - 1)), 1);
+            for &(a, b) in &[
+                (0, 5),
+                (1, 1),
+                (field.prime - 1, field.prime - 1),
+                (12345, 6789),
+            ] {
+                assert_eq!(field.mul_twiddle(a, field.twiddle(b)), field.mul(a, b));
+                assert_eq!(
+                    field.mul_montgomery(a, b),
+                    field.mul(
+                        field.mul(a, b),
+                        field.inverse(((1u64 << 32) % field.prime as u64) as u32)
+                    )
+                );
+            }
+            assert_eq!(
+                field.mul_twiddle(u32::MAX, field.twiddle(1)),
+                u32::MAX % field.prime
+            );
+        }
+    }
+
+    #[test]
+    fn test_max_len() {
+        assert_eq!(order(MAX_LEN), MAX_ORDER);
+        assert!(math::ceil_log_2(2 * num_digits(MAX_LEN + 1)) > MAX_ORDER);
+    }
+
+    #[test]
+    fn test_digits() {
+        let words = testing::pseudo_random_words(6, 1);
+        let mut copy = alloc::vec![0; words.len()];
+        for i in 0..num_digits(words.len()) {
+            set_digit(&mut copy, i, digit(&words, i));
+        }
+        assert_eq!(copy, words);
+    }
+
+    fn check_mul(a_len: usize, b_len: usize) {
+        let layout = memory_requirement_up_to(b_len);
+        if a_len == b_len {
+            testing::check_mul(a_len, b_len, layout, add_signed_mul_same_len);
+            testing::check_sqr(a_len, layout, sqr);
+        } else {
+            testing::check_mul(a_len, b_len, layout, add_signed_mul);
+        }
+    }
+
+    #[test]
+    fn test_mul_same_len() {
+        for n in &[16, 17, 100, 255, 256, 257, 1000] {
+            check_mul(*n, *n);
+        }
+    }
+
+    #[test]
+    fn test_mul_unbalanced() {
+        for (a_len, b_len) in &[(300, 200), (313, 200), (626, 200), (1000, 200), (3000, 129)] {
+            check_mul(*a_len, *b_len);
+        }
+    }
+}
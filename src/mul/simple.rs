@@ -6,6 +6,8 @@ use crate::{
         word::{SignedWord, Word},
     },
     mul::{self, helpers},
+    primitive::{extend_word, split_double_word},
+    shift,
     sign::Sign::{self, *},
 };
 
@@ -88,3 +90,33 @@ fn sub_mul_chunk(c: &mut [Word], a: &[Word], b: &[Word]) -> bool {
     }
     borrow
 }
+
+/// c = a * a
+/// Simple method: O(a.len()^2), computing each cross product once.
+pub(crate) fn sqr(c: &mut [Word], a: &[Word]) {
+    let n = a.len();
+    debug_assert!(c.len() == 2 * n);
+
+    // c = sum a_i * a_j for i < j
+    c.fill(0);
+    for (i, m) in a.iter().enumerate() {
+        let rest = &a[i + 1..];
+        c[i + n] = mul::add_mul_word_same_len_in_place(&mut c[2 * i + 1..i + n], *m, rest);
+    }
+
+    // c *= 2
+    let overflow = shift::shl_in_place(c, 1);
+    debug_assert!(overflow == 0);
+
+    // c += sum a_i^2
+    let mut carry = false;
+    for (i, m) in a.iter().enumerate() {
+        let (sqr_lo, sqr_hi) = split_double_word(extend_word(*m) * extend_word(*m));
+        let (sum_lo, carry_lo) = arch::add::add_with_carry(c[2 * i], sqr_lo, carry);
+        let (sum_hi, carry_hi) = arch::add::add_with_carry(c[2 * i + 1], sqr_hi, carry_lo);
+        c[2 * i] = sum_lo;
+        c[2 * i + 1] = sum_hi;
+        carry = carry_hi;
+    }
+    debug_assert!(!carry);
+}
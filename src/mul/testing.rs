@@ -0,0 +1,91 @@
+//! Test factors and checks for the multiplication algorithms.
+
+use crate::{
+    add,
+    arch::word::{SignedWord, Word},
+    memory::{Memory, MemoryAllocation},
+    mul::simple,
+    primitive::WORD_BITS,
+    sign::Sign::{self, *},
+};
+use alloc::{alloc::Layout, vec, vec::Vec};
+
+/// Odd multipliers for pseudo-random words: the top bits of 64-bit constants.
+const MULTIPLIER_0: Word = (0x9e37_79b9_7f4a_7c15_u64 >> (64 - WORD_BITS)) as Word | 1;
+const MULTIPLIER_1: Word = (0x2545_f491_4f6c_dd1d_u64 >> (64 - WORD_BITS)) as Word | 1;
+
+/// Simple pseudo-random words.
+pub(crate) fn pseudo_random_words(n: usize, seed: Word) -> Vec<Word> {
+    (0..n)
+        .map(|i| {
+            (i as Word)
+                .wrapping_mul(MULTIPLIER_0)
+                .wrapping_add(seed)
+                .wrapping_mul(MULTIPLIER_1)
+        })
+        .collect()
+}
+
+/// Simple pseudo-random words, and all ones for the largest intermediate values.
+pub(crate) fn test_factors(n: usize, seed: Word) -> Vec<Vec<Word>> {
+    vec![pseudo_random_words(n, seed), vec![Word::MAX; n]]
+}
+
+/// c += sign * a * b
+/// Simple method for any lengths, with b split into parts that simple multiplication supports.
+///
+/// Returns carry.
+fn simple_add_signed_mul(c: &mut [Word], sign: Sign, a: &[Word], b: &[Word]) -> SignedWord {
+    debug_assert!(a.len() >= b.len() && c.len() == a.len() + b.len());
+    let mut carry = 0;
+    for (i, b_part) in b.chunks(simple::MAX_SMALLER_LEN).enumerate() {
+        let c = &mut c[i * simple::MAX_SMALLER_LEN..];
+        let (c_lo, c_hi) = c.split_at_mut(a.len() + b_part.len());
+        let carry_part = simple::add_signed_mul(c_lo, sign, a, b_part);
+        carry += add::add_signed_word_in_place(c_hi, carry_part);
+    }
+    carry
+}
+
+/// Check `add_signed_mul` against simple multiplication, for factors of lengths a_len >= b_len.
+pub(crate) fn check_mul<F>(a_len: usize, b_len: usize, layout: Layout, add_signed_mul: F)
+where
+    F: Fn(&mut [Word], Sign, &[Word], &[Word], &mut Memory) -> SignedWord,
+{
+    let mut allocation = MemoryAllocation::new(layout);
+    let mut memory = allocation.memory();
+    for a in &test_factors(a_len, 1) {
+        for b in &test_factors(b_len, 2) {
+            for sign in &[Positive, Negative] {
+                let initial: Vec<Word> = (0..a_len + b_len).map(|i| i as Word).collect();
+                let mut expected = initial.clone();
+                let expected_carry = simple_add_signed_mul(&mut expected, *sign, a, b);
+                let mut c = initial;
+                let carry = add_signed_mul(&mut c, *sign, a, b, &mut memory);
+                assert_eq!(
+                    (c, carry),
+                    (expected, expected_carry),
+                    "a_len={} b_len={}",
+                    a_len,
+                    b_len
+                );
+            }
+        }
+    }
+}
+
+/// Check `sqr` against simple multiplication, for factors of length n.
+pub(crate) fn check_sqr<F>(n: usize, layout: Layout, sqr: F)
+where
+    F: Fn(&mut [Word], &[Word], &mut Memory),
+{
+    let mut allocation = MemoryAllocation::new(layout);
+    let mut memory = allocation.memory();
+    for a in &test_factors(n, 1) {
+        let mut expected = vec![0; 2 * n];
+        let _ = simple_add_signed_mul(&mut expected, Positive, a, a);
+        let mut c = vec![Word::MAX; 2 * n];
+        sqr(&mut c, a, &mut memory);
+        assert_eq!(c, expected, "n={}", n);
+    }
+}
@@ -0,0 +1,506 @@
+//! Toom-Cook multiplication with any number of parts.
+//!
+//! Used for the tiers above Toom-3, for unbalanced factors, and for Toom-3 when the recursive
+//! multiplications run in parallel. The cost of a generic interpolation is negligible compared to
+//! the recursive multiplications.
+
+use crate::{
+    add,
+    arch::word::{SignedWord, Word},
+    div, math,
+    memory::{self, Memory},
+    mul, parallel,
+    primitive::WORD_BITS_USIZE,
+    sign::Sign::{self, *},
+};
+use alloc::alloc::Layout;
+
+// Split a into ka parts and b into kb parts, all of length nk, except for shorter last parts.
+// The last parts must be non-empty. For balanced factors ka = kb = k, nk = ceil(n/k) and the
+// last part has length s = n - (k-1) * nk, so s >= 1 holds if n > (k-1)^2.
+//
+// Let d = ka + kb - 2 be the degree of the product. We evaluate the polynomials A(x) and B(x)
+// at d+1 points: 0, 1, -1, 2, -2, ..., infinity.
+// Multiplying, this gives us values of V(x) = A(x)*B(x) at the same points, using d+1
+// recursive multiplications.
+//
+// Interpolation:
+// 1. Subtract V(inf) * x^d from all finite values, which leaves a polynomial W(x) of
+//    degree d-1 known at d points.
+// 2. Compute the Newton divided differences of W in place. These are integers because
+//    W has integer coefficients and the points are integers.
+// 3. Convert from the Newton basis to coefficients.
+//
+// Intermediate values are signed, stored in two's complement. For ka, kb <= 8 they are bounded
+// in absolute value by 2^64 * Word^(2nk), so 128 extra bits are plenty.
+
+/// Extra bits of room in interpolated values, beyond 2 parts.
+const VALUE_EXTRA_BITS: usize = 128;
+
+/// Length of a part when splitting n words into k parts.
+fn part_len(n: usize, k: usize) -> usize {
+    math::ceil_div(n, k)
+}
+
+/// Length of the parts when splitting a into ka parts and b into kb parts.
+fn unbalanced_part_len(a_len: usize, b_len: usize, ka: usize, kb: usize) -> usize {
+    part_len(a_len, ka).max(part_len(b_len, kb))
+}
+
+/// Largest absolute value of the finite points, given the number of finite points.
+fn max_point(num_points: usize) -> usize {
+    num_points / 2
+}
+
+/// Length of a number split into k parts of nk words evaluated at any of the points.
+fn eval_len(nk: usize, k: usize, max_point: usize) -> usize {
+    // |A(x)| < Word^nk * (1 + max_point + ... + max_point^(k-1))
+    let max_multiplier: u64 = (0..k as u32).map(|i| (max_point as u64).pow(i)).sum();
+    let extra_bits = 64 - max_multiplier.leading_zeros() as usize;
+    nk + math::ceil_div(extra_bits, WORD_BITS_USIZE)
+}
+
+/// Length of values during interpolation.
+fn value_len(nk: usize) -> usize {
+    2 * nk + VALUE_EXTRA_BITS / WORD_BITS_USIZE + 1
+}
+
+/// The i-th finite evaluation point: 0, 1, -1, 2, -2, ...
+fn point(i: usize) -> SignedWord {
+    if i % 2 == 1 {
+        (i / 2 + 1) as SignedWord
+    } else {
+        -((i / 2) as SignedWord)
+    }
+}
+
+/// Temporary memory required for balanced multiplication.
+///
+/// n bounds the length of the smaller factor in words.
+pub(crate) fn memory_requirement_up_to(n: usize, k: usize) -> Layout {
+    memory_requirement_parts(part_len(n, k), k, k)
+}
+
+/// Temporary memory required for multiplication with a split into ka parts and b into kb parts.
+///
+/// a_len and b_len bound the lengths of the factors in words.
+pub(crate) fn memory_requirement_unbalanced_up_to(
+    a_len: usize,
+    b_len: usize,
+    ka: usize,
+    kb: usize,
+) -> Layout {
+    memory_requirement_parts(unbalanced_part_len(a_len, b_len, ka, kb), ka, kb)
+}
+
+/// Temporary memory required for multiplication with parts of length nk.
+fn memory_requirement_parts(nk: usize, ka: usize, kb: usize) -> Layout {
+    // In each level of recursion we use:
+    // values: (d+1) * value_len
+    // temporary value: value_len
+    // evaluations: 3 * eval_len per factor
+    let num_points = ka + kb - 2;
+    let a_eval_len = eval_len(nk, ka, max_point(num_points));
+    let b_eval_len = eval_len(nk, kb, max_point(num_points));
+    let num_words = (num_points + 2) * value_len(nk) + 3 * (a_eval_len + b_eval_len);
+    memory::add_layout(
+        memory::array_layout::<Word>(num_words),
+        mul::memory_requirement_up_to(a_eval_len.max(b_eval_len)),
+    )
+}
+
+/// Can a be split into ka parts and b into kb parts of the same length, with non-empty last
+/// parts?
+pub(crate) fn can_split(a_len: usize, b_len: usize, ka: usize, kb: usize) -> bool {
+    let nk = unbalanced_part_len(a_len, b_len, ka, kb);
+    a_len > (ka - 1) * nk && b_len > (kb - 1) * nk
+}
+
+/// c += sign * a * b
+/// Toom-Cook-k method: O(n^log_k(2k-1)).
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul_same_len(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+    k: usize,
+) -> SignedWord {
+    debug_assert!(b.len() == a.len());
+    add_signed_mul_or_sqr(c, sign, a, Some(b), memory, k, k)
+}
+
+/// c += sign * a * b
+/// Unbalanced Toom-Cook method, splitting a into ka parts and b into kb parts.
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul_unbalanced(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+    ka: usize,
+    kb: usize,
+) -> SignedWord {
+    add_signed_mul_or_sqr(c, sign, a, Some(b), memory, ka, kb)
+}
+
+/// c = a * a
+/// Toom-Cook-k method: O(n^log_k(2k-1)), evaluating only one polynomial.
+pub(crate) fn sqr(c: &mut [Word], a: &[Word], memory: &mut Memory, k: usize) {
+    c.fill(0);
+    let overflow = add_signed_mul_or_sqr(c, Positive, a, None, memory, k, k);
+    assert!(overflow == 0);
+}
+
+/// c += sign * a * b, or c += sign * a * a if b is None.
+///
+/// Returns carry.
+fn add_signed_mul_or_sqr(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: Option<&[Word]>,
+    memory: &mut Memory,
+    ka: usize,
+    kb: usize,
+) -> SignedWord {
+    let b_len = b.map_or(a.len(), |b| b.len());
+    debug_assert!(c.len() == a.len() + b_len);
+    debug_assert!(can_split(a.len(), b_len, ka, kb));
+
+    let nk = unbalanced_part_len(a.len(), b_len, ka, kb);
+    // Number of finite points.
+    let num_points = ka + kb - 2;
+    let max_point = max_point(num_points);
+    let value_len = value_len(nk);
+
+    // values[i] = V(point(i)) for i < num_points
+    // values[num_points] = V(inf)
+    let (values, mut memory) = memory.allocate_slice_fill::<Word>((num_points + 1) * value_len, 0);
+    let split = Split {
+        a,
+        b,
+        nk,
+        ka,
+        kb,
+        num_points,
+        a_eval_len: eval_len(nk, ka, max_point),
+        b_eval_len: eval_len(nk, kb, max_point),
+    };
+    // If the memory for computing the values in parallel can't be allocated, compute them
+    // serially instead.
+    let parallel_allocation = if parallel::is_worthwhile(nk) {
+        let layout = memory::repeat_layout(split.memory_requirement(), num_points + 1);
+        memory.try_new_allocation(layout).ok()
+    } else {
+        None
+    };
+    match parallel_allocation {
+        Some(mut allocation) => {
+            split.values_parallel(values, 0, value_len, &mut allocation.memory());
+        }
+        None => {
+            for (i, value) in values.chunks_mut(value_len).enumerate() {
+                split.value(i, value, &mut memory);
+            }
+        }
+    }
+
+    interpolate(values, num_points, value_len, &mut memory);
+
+    // c += sign * sum_i values[i] * Word^(i*nk)
+    let mut carry: SignedWord = 0;
+    for (i, value) in values.chunks(value_len).enumerate() {
+        let offset = i * nk;
+        let len = value_len.min(c.len() - offset);
+        debug_assert!(value[len..].iter().all(|word| *word == 0));
+        carry += add::add_signed_in_place(&mut c[offset..], sign, &value[..len]);
+    }
+    assert!(carry.abs() <= 1);
+    carry
+}
+
+/// Factors split into parts, for computing the values of V.
+struct Split<'a> {
+    a: &'a [Word],
+    /// None for squaring.
+    b: Option<&'a [Word]>,
+    nk: usize,
+    ka: usize,
+    kb: usize,
+    num_points: usize,
+    a_eval_len: usize,
+    b_eval_len: usize,
+}
+
+impl Split<'_> {
+    /// Temporary memory required by `value`.
+    fn memory_requirement(&self) -> Layout {
+        memory::add_layout(
+            memory::array_layout::<Word>(3 * (self.a_eval_len + self.b_eval_len)),
+            mul::memory_requirement_up_to(self.a_eval_len.max(self.b_eval_len)),
+        )
+    }
+
+    /// value = V(point(i)), or V(inf) if i == num_points.
+    ///
+    /// value must be zero.
+    fn value(&self, i: usize, value: &mut [Word], memory: &mut Memory) {
+        let (a, b, nk) = (self.a, self.b, self.nk);
+        if i == 0 {
+            // V(0) = a0 * b0
+            let a0 = part(a, nk, 0);
+            let b0 = b.map(|b| part(b, nk, 0));
+            mul_or_sqr(&mut value[..2 * nk], a0, b0, memory);
+        } else if i == self.num_points {
+            // V(inf) = a_(ka-1) * b_(kb-1)
+            let a_top = part(a, nk, self.ka - 1);
+            let b_top = b.map(|b| part(b, nk, self.kb - 1));
+            let top_len = a_top.len() + b_top.map_or(a_top.len(), |b_top| b_top.len());
+            mul_or_sqr(&mut value[..top_len], a_top, b_top, memory);
+        } else {
+            // V(x) = A(x) * B(x)
+            let (a_eval_len, b_eval_len) = (self.a_eval_len, self.b_eval_len);
+            let (a_evals, mut memory) = memory.allocate_slice_fill::<Word>(3 * a_eval_len, 0);
+            let (b_evals, mut memory) = memory.allocate_slice_fill::<Word>(3 * b_eval_len, 0);
+            let x = point(i);
+            let mut neg_sign = evaluate(a_evals, a, nk, self.ka, x.unsigned_abs());
+            if let Some(b) = b {
+                neg_sign *= evaluate(b_evals, b, nk, self.kb, x.unsigned_abs());
+            }
+            // evals = [A(|x|), |A(-|x|)|, temporary]
+            let offset = if x > 0 { 0 } else { 1 };
+            let a_eval = &a_evals[offset * a_eval_len..(offset + 1) * a_eval_len];
+            let b_eval = b.map(|_| &b_evals[offset * b_eval_len..(offset + 1) * b_eval_len]);
+            mul_or_sqr(
+                &mut value[..a_eval_len + b_eval_len],
+                a_eval,
+                b_eval,
+                &mut memory,
+            );
+            if x < 0 && b.is_some() && neg_sign == Negative {
+                add::negate_in_place(value);
+            }
+        }
+    }
+
+    /// values[j] = V(point(first + j)), or V(inf), computed in parallel.
+    ///
+    /// memory holds the memory for computing each value.
+    fn values_parallel(
+        &self,
+        values: &mut [Word],
+        first: usize,
+        value_len: usize,
+        memory: &mut Memory,
+    ) {
+        let count = values.len() / value_len;
+        if count == 1 {
+            self.value(first, values, memory);
+        } else {
+            let mid = count / 2;
+            let (lo, hi) = values.split_at_mut(mid * value_len);
+            let (mut memory_lo, mut memory_hi) =
+                memory.allocate_memory(memory::repeat_layout(self.memory_requirement(), mid));
+            parallel::join(
+                true,
+                || self.values_parallel(lo, first, value_len, &mut memory_lo),
+                || self.values_parallel(hi, first + mid, value_len, &mut memory_hi),
+            );
+        }
+    }
+}
+
+/// The i-th part of words split into parts of length nk.
+fn part(words: &[Word], nk: usize, i: usize) -> &[Word] {
+    &words[i * nk..((i + 1) * nk).min(words.len())]
+}
+
+/// c = a * b, or c = a * a if b is None.
+///
+/// c must be zero.
+fn mul_or_sqr(c: &mut [Word], a: &[Word], b: Option<&[Word]>, memory: &mut Memory) {
+    match b {
+        Some(b) => {
+            let overflow = mul::add_signed_mul(c, Positive, a, b, memory);
+            assert!(overflow == 0);
+        }
+        None => mul::sqr(c, a, memory),
+    }
+}
+
+/// Evaluate words split into k parts of length nk at x and -x.
+///
+/// evals = [A(x), |A(-x)|, temporary]
+///
+/// Returns the sign of A(-x).
+fn evaluate(evals: &mut [Word], words: &[Word], nk: usize, k: usize, x: Word) -> Sign {
+    let eval_len = evals.len() / 3;
+    let (pos, rest) = evals.split_at_mut(eval_len);
+    let (even, odd) = rest.split_at_mut(eval_len);
+    // even = sum a_(2i) x^(2i)
+    // odd = sum a_(2i+1) x^(2i+1)
+    evaluate_every_other(even, words, nk, k, 0, x * x);
+    evaluate_every_other(odd, words, nk, k, 1, x * x);
+    let overflow = mul::mul_word_in_place(odd, x);
+    assert!(overflow == 0);
+    // A(x) = even + odd
+    // A(-x) = even - odd
+    pos.copy_from_slice(even);
+    let overflow = add::add_same_len_in_place(pos, odd);
+    assert!(!overflow);
+    add::sub_in_place_with_sign(even, odd)
+}
+
+/// out = sum a_(first + 2i) * x2^i
+fn evaluate_every_other(
+    out: &mut [Word],
+    words: &[Word],
+    nk: usize,
+    k: usize,
+    first: usize,
+    x2: Word,
+) {
+    out.fill(0);
+    // Horner's method.
+    for i in (first..k).step_by(2).rev() {
+        let overflow = mul::mul_word_in_place(out, x2);
+        assert!(overflow == 0);
+        let overflow = add::add_in_place(out, part(words, nk, i));
+        assert!(!overflow);
+    }
+}
+
+/// Convert values at points 0, 1, -1, ..., infinity into coefficients, in place.
+fn interpolate(values: &mut [Word], num_points: usize, value_len: usize, memory: &mut Memory) {
+    let (finite, v_inf) = values.split_at_mut(num_points * value_len);
+
+    // values[i] -= V(inf) * point(i)^num_points
+    {
+        let (temp, _) = memory.allocate_slice_fill::<Word>(value_len, 0);
+        for (i, value) in finite.chunks_mut(value_len).enumerate().skip(1) {
+            let x = point(i).unsigned_abs();
+            // point(i)^num_points is negative for negative points and odd num_points.
+            let negative = point(i) < 0 && num_points % 2 == 1;
+            match x.checked_pow(num_points as u32) {
+                Some(mult) if !negative => {
+                    let _ = mul::sub_mul_word_same_len_in_place(value, mult, v_inf);
+                }
+                Some(mult) => {
+                    let _ = mul::add_mul_word_same_len_in_place(value, mult, v_inf);
+                }
+                None => {
+                    temp.copy_from_slice(v_inf);
+                    for _ in 0..num_points {
+                        let overflow = mul::mul_word_in_place(temp, x);
+                        debug_assert!(overflow == 0);
+                    }
+                    if negative {
+                        let _ = add::add_same_len_in_place(value, temp);
+                    } else {
+                        let _ = add::sub_same_len_in_place(value, temp);
+                    }
+                }
+            }
+        }
+    }
+
+    // Divided differences.
+    for j in 1..num_points {
+        for i in (j..num_points).rev() {
+            let (lo, hi) = finite.split_at_mut(i * value_len);
+            let value = &mut hi[..value_len];
+            let _ = add::sub_same_len_in_place(value, &lo[(i - 1) * value_len..]);
+            div_exact_signed_in_place(value, point(i) - point(i - j));
+        }
+    }
+
+    // Newton basis to coefficients.
+    // point(0) = 0, so the last step is trivial.
+    for i in (1..num_points - 1).rev() {
+        let x = point(i);
+        for j in i..num_points - 1 {
+            let (lo, hi) = finite.split_at_mut((j + 1) * value_len);
+            let value = &mut lo[j * value_len..];
+            let next = &hi[..value_len];
+            // value -= x * next
+            if x > 0 {
+                let _ = mul::sub_mul_word_same_len_in_place(value, x.unsigned_abs(), next);
+            } else {
+                let _ = mul::add_mul_word_same_len_in_place(value, x.unsigned_abs(), next);
+            }
+        }
+    }
+}
+
+/// words /= rhs in two's complement, where the division is exact.
+fn div_exact_signed_in_place(words: &mut [Word], rhs: SignedWord) {
+    let negative = (*words.last().unwrap() as SignedWord) < 0;
+    if negative {
+        add::negate_in_place(words);
+    }
+    let rem = div::div_by_word_in_place(words, rhs.unsigned_abs());
+    assert_eq!(rem, 0);
+    if negative != (rhs < 0) {
+        add::negate_in_place(words);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mul::testing, thresholds};
+
+    fn check_mul(k: usize, n: usize) {
+        let layout = memory_requirement_up_to(n, k);
+        testing::check_mul(n, n, layout, |c, sign, a, b, memory| {
+            add_signed_mul_same_len(c, sign, a, b, memory, k)
+        });
+        testing::check_sqr(n, layout, |c, a, memory| sqr(c, a, memory, k));
+    }
+
+    fn check_mul_unbalanced(ka: usize, kb: usize, a_len: usize, b_len: usize) {
+        let layout = memory_requirement_unbalanced_up_to(a_len, b_len, ka, kb);
+        testing::check_mul(a_len, b_len, layout, |c, sign, a, b, memory| {
+            add_signed_mul_unbalanced(c, sign, a, b, memory, ka, kb)
+        });
+    }
+
+    #[test]
+    fn test_mul_unbalanced() {
+        for (ka, kb) in &[(3, 2), (4, 2), (4, 3), (5, 2)] {
+            for b_len in &[10, 31, 100, 150] {
+                for a_len in (*b_len..=4 * *b_len).step_by(*b_len / 8 + 1) {
+                    if can_split(a_len, *b_len, *ka, *kb) {
+                        check_mul_unbalanced(*ka, *kb, a_len, *b_len);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_parallel() {
+        // Parts long enough to compute the values in parallel with the `rayon` feature.
+        let len = thresholds::current().parallel;
+        check_mul_unbalanced(3, 2, 3 * len, 2 * len);
+    }
+
+    #[test]
+    fn test_mul() {
+        for k in 3..=8 {
+            let min_len = (k - 1) * (k - 1) + 1;
+            for n in &[min_len, min_len + 1, 2 * min_len + 3, 100, 257] {
+                if *n >= min_len {
+                    check_mul(k, *n);
+                }
+            }
+        }
+    }
+}
@@ -5,12 +5,15 @@ use crate::{
     arch::word::{SignedWord, Word},
     div, math,
     memory::{self, Memory},
-    mul::{self, helpers},
-    shift,
+    mul::{self, helpers, toom},
+    parallel, shift,
     sign::Sign::{self, *},
 };
 use alloc::alloc::Layout;
 
+/// Number of parts the factors are split into.
+const PARTS: usize = 3;
+
 // We must have:
 // 2 * (n+2) <= n
 // i * n3 + 2 <= (i+1) * n3
@@ -51,7 +54,20 @@ pub(crate) fn memory_requirement_up_to(n: usize) -> Layout {
     // Note: the recurence also works when we transition to Karatsuba, because
     // Karatsuba memory requirements are smaller.
     let num_words = 4 * n + 13 * (math::ceil_log_2(n) as usize);
-    memory::array_layout::<Word>(num_words)
+    let layout = memory::array_layout::<Word>(num_words);
+    if is_parallel(n) {
+        memory::max_layout(layout, toom::memory_requirement_up_to(n, PARTS))
+    } else {
+        layout
+    }
+}
+
+/// Should the recursive multiplications for factors of length n run in parallel?
+///
+/// The generic Toom-Cook implementation runs them in parallel, so then it is used instead of the
+/// one below.
+fn is_parallel(n: usize) -> bool {
+    parallel::is_worthwhile(math::ceil_div(n, PARTS))
 }
 
 /// c += sign * a * b
@@ -87,6 +103,10 @@ pub(crate) fn add_signed_mul_same_len(
     debug_assert!(b.len() == n && c.len() == 2 * n);
     debug_assert!(n >= MIN_LEN);
 
+    if is_parallel(n) {
+        return toom::add_signed_mul_same_len(c, sign, a, b, memory, PARTS);
+    }
+
     // Brent, Zimmermann, Modern Computer Arithmetic 0.5.9, Algorithm 1.4.
     //
     // We evaluate the polynomials A(x) = a0 + a1*x + a2*x^2, B(x) = b0 + b1*x + b2*x^2
@@ -105,7 +125,7 @@ pub(crate) fn add_signed_mul_same_len(
     // t2 = (V(1) + V(-1))/2
 
     // Split into 3 parts. Note: a2, b2 may be shorter.
-    let n3 = (n + 2) / 3;
+    let n3 = math::ceil_div(n, 3);
     let n3_short = n - 2 * n3;
 
     let (a0, a12) = a.split_at(n3);
@@ -255,3 +275,137 @@ pub(crate) fn add_signed_mul_same_len(
     assert!(carry.abs() <= 1);
     carry
 }
+
+/// c = a * a
+/// Toom-Cook-3 method: O(n^1.47), using 5 squares of a third of the size.
+///
+/// Uses no more temporary memory than multiplication of the same length.
+pub(crate) fn sqr(c: &mut [Word], a: &[Word], memory: &mut Memory) {
+    let n = a.len();
+    debug_assert!(c.len() == 2 * n);
+    debug_assert!(n >= MIN_LEN);
+
+    if is_parallel(n) {
+        toom::sqr(c, a, memory, PARTS);
+        return;
+    }
+
+    // Same evaluation points and interpolation as `add_signed_mul_same_len`, but there is only
+    // one polynomial to evaluate, and all the values V(x) = A(x)^2 are non-negative.
+    //
+    // c_0 = V(0)
+    // c_1 = V(1) - t1
+    // c_2 = t2 - V(0) - V(inf)
+    // c_3 = t1 - t2
+    // c_4 = V(inf)
+    // where:
+    // t1 = (3V(0) + 2V(-1) + V(2))/6 - 2V(inf)
+    // t2 = (V(1) + V(-1))/2
+    //
+    // Since A(x) has non-negative coefficients, so does A(x)^2.
+
+    // Split into 3 parts. Note: a2 may be shorter.
+    let n3 = math::ceil_div(n, 3);
+
+    let (a0, a12) = a.split_at(n3);
+    let (a1, a2) = a12.split_at(n3);
+
+    // Evaluate at 0 and inf.
+    // c_0 = V(0) = a0^2
+    // c_4 = V(inf) = a2^2
+    {
+        let (c0, c_rest) = c.split_at_mut(2 * n3);
+        let (c_mid, c4) = c_rest.split_at_mut(2 * n3);
+        mul::sqr(c0, a0, memory);
+        c_mid.fill(0);
+        mul::sqr(c4, a2, memory);
+    }
+
+    // t1 = 3V(0)
+    let (t1, mut memory) = memory.allocate_slice_copy_fill(2 * n3 + 2, &c[..2 * n3], 0);
+    t1[2 * n3] = mul::mul_word_in_place(&mut t1[..2 * n3], 3);
+
+    // Evaluate at 2.
+    // a_eval = a0 + 2a1 + 4a2
+    // V(2) = a_eval^2
+    // t1 += V(2)
+    // t1 -= 12V(inf)
+    // Now t1 = 3V(0) + V(2) - 12V(inf)
+    let (a_eval, mut memory) = memory.allocate_slice_copy_fill(n3 + 1, a0, 0);
+    let (c_eval, mut memory) = memory.allocate_slice_fill(2 * (n3 + 1), 0);
+    {
+        a_eval[n3] = mul::add_mul_word_same_len_in_place(&mut a_eval[..n3], 2, a1);
+        a_eval[n3] += mul::add_mul_word_in_place(&mut a_eval[..n3], 4, a2);
+        mul::sqr(c_eval, a_eval, &mut memory);
+        let overflow = add::add_same_len_in_place(t1, c_eval);
+        assert!(!overflow);
+        let value_inf = &c[4 * n3..];
+        let borrow = mul::sub_mul_word_same_len_in_place(&mut t1[..value_inf.len()], 12, value_inf);
+        let overflow = add::sub_word_in_place(&mut t1[value_inf.len()..], borrow);
+        // 3V(0) + V(2) - 12V(inf) is never negative
+        assert!(!overflow);
+    }
+
+    let (t2, mut memory) = memory.allocate_slice_fill(2 * n3 + 2, 0);
+    {
+        // Evaluate at 1.
+        // a02 = a0 + a2
+        // a_eval = a02 + a1
+        // t2 = V(1) = a_eval^2
+        let (a02, mut memory) = memory.allocate_slice_copy_fill(n3 + 1, a0, 0);
+        a02[n3] = Word::from(add::add_in_place(&mut a02[..n3], a2));
+        a_eval.copy_from_slice(a02);
+        a_eval[n3] += Word::from(add::add_same_len_in_place(&mut a_eval[..n3], a1));
+        mul::sqr(t2, a_eval, &mut memory);
+
+        // Evaluate at -1.
+        // a_eval = |a02 - a1|
+        // c_eval = V(-1) = a_eval^2
+        a_eval.copy_from_slice(a02);
+        let _ = add::sub_in_place_with_sign(a_eval, a1);
+        mul::sqr(c_eval, a_eval, &mut memory);
+    }
+
+    // t1 += 2V(-1)
+    // c_eval += V(1)
+    // t1 /= 6
+    // c_eval /= 2
+    // Now t1 and c_eval are t1 and t2 from the formulas above.
+    let overflow = mul::add_mul_word_same_len_in_place(t1, 2, c_eval);
+    assert!(overflow == 0);
+    let overflow = add::add_same_len_in_place(c_eval, t2);
+    assert!(!overflow);
+    let t1_rem = div::div_by_word_in_place(t1, 6);
+    let c_eval_rem = shift::shr_in_place(c_eval, 1);
+    assert_eq!(t1_rem, 0);
+    assert_eq!(c_eval_rem, 0);
+
+    // Coefficients, in place:
+    // t2 = c_1 = V(1) - t1
+    // t1 = c_3 = t1 - t2
+    // c_eval = c_2 = t2 - V(0) - V(inf)
+    let overflow = add::sub_same_len_in_place(t2, t1);
+    assert!(!overflow);
+    let overflow = add::sub_same_len_in_place(t1, c_eval);
+    assert!(!overflow);
+    let overflow = add::sub_in_place(c_eval, &c[..2 * n3]);
+    assert!(!overflow);
+    let overflow = add::sub_in_place(c_eval, &c[4 * n3..]);
+    assert!(!overflow);
+
+    // c_1 += t2
+    // c_2 += c_eval
+    // c_3 += t1
+    let carry_c1 = SignedWord::from(add::add_same_len_in_place(&mut c[n3..3 * n3 + 2], t2)); // at 3*n3+2
+    let mut carry_c2 = SignedWord::from(add::add_same_len_in_place(
+        &mut c[2 * n3..4 * n3 + 2],
+        c_eval,
+    )); // at 4*n3+2
+    let mut carry_c3 = SignedWord::from(add::add_same_len_in_place(&mut c[3 * n3..5 * n3 + 2], t1)); // at 5*n3+2
+
+    // Apply carries.
+    carry_c2 += add::add_signed_word_in_place(&mut c[3 * n3 + 2..4 * n3 + 2], carry_c1);
+    carry_c3 += add::add_signed_word_in_place(&mut c[4 * n3 + 2..5 * n3 + 2], carry_c2);
+    let overflow = add::add_signed_word_in_place(&mut c[5 * n3 + 2..], carry_c3);
+    assert!(overflow == 0);
+}
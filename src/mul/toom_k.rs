@@ -0,0 +1,77 @@
+//! Toom-Cook-k multiplication algorithm for the balanced tiers above Toom-3: Toom-4 and Toom-8.
+
+use crate::{
+    arch::word::{SignedWord, Word},
+    memory::{self, Memory},
+    mul::{helpers, toom, toom_3},
+    sign::Sign,
+};
+use alloc::alloc::Layout;
+
+/// Minimum supported length of the factors split into `parts` parts.
+// All parts must be non-empty, which requires n > (parts-1)^2.
+pub(crate) const fn min_len(parts: usize) -> usize {
+    parts * parts
+}
+
+/// Temporary memory required for multiplication, splitting the factors into `parts` parts.
+///
+/// n bounds the length of the smaller factor in words.
+pub(crate) fn memory_requirement_up_to(n: usize, parts: usize) -> Layout {
+    // Shorter factors may use the previous tier, which can need more memory per word.
+    let previous = if parts <= 4 {
+        toom_3::memory_requirement_up_to(n)
+    } else {
+        memory_requirement_up_to(n, parts / 2)
+    };
+    memory::max_layout(previous, toom::memory_requirement_up_to(n, parts))
+}
+
+/// c += sign * a * b
+/// Toom-Cook-k method with k = parts: O(a.len() * b.len()^(log(2k-1)/log(k) - 1)).
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+    parts: usize,
+) -> SignedWord {
+    debug_assert!(a.len() >= b.len() && b.len() >= min_len(parts) && c.len() == a.len() + b.len());
+
+    helpers::add_signed_mul_split_into_same_len(c, sign, a, b, memory, |c, sign, a, b, memory| {
+        add_signed_mul_same_len(c, sign, a, b, memory, parts)
+    })
+}
+
+/// c += sign * a * b
+/// Toom-Cook-k method with k = parts: O(n^(log(2k-1)/log(k))). That is O(n^1.40) for Toom-4 and
+/// O(n^1.30) for Toom-8.
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul_same_len(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+    parts: usize,
+) -> SignedWord {
+    debug_assert!(b.len() == a.len() && c.len() == 2 * a.len());
+    debug_assert!(a.len() >= min_len(parts));
+
+    toom::add_signed_mul_same_len(c, sign, a, b, memory, parts)
+}
+
+/// c = a * a
+/// Toom-Cook-k method with k = parts: O(n^(log(2k-1)/log(k))).
+pub(crate) fn sqr(c: &mut [Word], a: &[Word], memory: &mut Memory, parts: usize) {
+    debug_assert!(c.len() == 2 * a.len());
+    debug_assert!(a.len() >= min_len(parts));
+
+    toom::sqr(c, a, memory, parts)
+}
@@ -0,0 +1,70 @@
+//! Unbalanced Toom-Cook multiplication algorithms: Toom-32, Toom-42 and Toom-43.
+//!
+//! Toom-ab splits the longer factor into a parts and the shorter factor into b parts of the same
+//! length, which suits factors whose lengths have a ratio of about a/b.
+
+use crate::{
+    arch::word::{SignedWord, Word},
+    memory::Memory,
+    mul::toom,
+    sign::Sign,
+};
+use alloc::alloc::Layout;
+
+/// Unbalanced Toom-Cook variant.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Variant {
+    /// 3 and 2 parts, 4 recursive multiplications. Best for a length ratio of 3/2.
+    Toom32,
+    /// 4 and 2 parts, 5 recursive multiplications. Best for a length ratio of 2.
+    Toom42,
+    /// 4 and 3 parts, 6 recursive multiplications. Best for a length ratio of 4/3.
+    Toom43,
+}
+
+impl Variant {
+    /// Number of parts of the longer and the shorter factor.
+    fn parts(self) -> (usize, usize) {
+        match self {
+            Variant::Toom32 => (3, 2),
+            Variant::Toom42 => (4, 2),
+            Variant::Toom43 => (4, 3),
+        }
+    }
+}
+
+/// Temporary memory required for multiplication.
+///
+/// a_len and b_len bound the lengths of the longer and the shorter factor in words.
+pub(crate) fn memory_requirement_up_to(variant: Variant, a_len: usize, b_len: usize) -> Layout {
+    let (ka, kb) = variant.parts();
+    toom::memory_requirement_unbalanced_up_to(a_len, b_len, ka, kb)
+}
+
+/// Can the variant multiply factors of these lengths?
+///
+/// All parts must be non-empty.
+pub(crate) fn supports(variant: Variant, a_len: usize, b_len: usize) -> bool {
+    let (ka, kb) = variant.parts();
+    toom::can_split(a_len, b_len, ka, kb)
+}
+
+/// c += sign * a * b
+/// Unbalanced Toom-Cook method, for a somewhat longer than b.
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+    variant: Variant,
+) -> SignedWord {
+    debug_assert!(a.len() >= b.len() && c.len() == a.len() + b.len());
+    debug_assert!(supports(variant, a.len(), b.len()));
+
+    let (ka, kb) = variant.parts();
+    toom::add_signed_mul_unbalanced(c, sign, a, b, memory, ka, kb)
+}
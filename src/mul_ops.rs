@@ -1,18 +1,24 @@
 //! Multiplication operators.
 
 use crate::{
+    add,
     arch::word::Word,
     buffer::Buffer,
+    error::AllocError,
     ibig::IBig,
-    memory::MemoryAllocation,
-    mul,
-    primitive::extend_word,
+    memory, mul,
+    primitive::{extend_word, split_double_word},
+    product,
+    scratch::Scratch,
     sign::Sign::{self, *},
     ubig::{Repr::*, UBig},
 };
+use alloc::vec::Vec;
 use core::{
+    iter::Product,
     mem,
     ops::{Mul, MulAssign},
+    ptr,
 };
 
 impl Mul<UBig> for UBig {
@@ -68,16 +74,315 @@ impl Mul<&UBig> for &UBig {
 
 impl MulAssign<UBig> for UBig {
     fn mul_assign(&mut self, rhs: UBig) {
-        *self = mem::take(self) * rhs;
+        self.update(|x| x * rhs);
     }
 }
 
 impl MulAssign<&UBig> for UBig {
     fn mul_assign(&mut self, rhs: &UBig) {
-        *self = mem::take(self) * rhs;
+        self.update(|x| x * rhs);
     }
 }
 
+impl UBig {
+    /// Multiplication that fails instead of panicking or aborting if memory can't be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{error::AllocError, ubig};
+    /// assert_eq!(ubig!(3).try_mul(&ubig!(4))?, ubig!(12));
+    /// # Ok::<(), AllocError>(())
+    /// ```
+    pub fn try_mul(&self, rhs: &UBig) -> Result<UBig, AllocError> {
+        match (self.repr(), rhs.repr()) {
+            (Small(word0), Small(word1)) => UBig::try_mul_word(*word0, *word1),
+            (Small(0), Large(_)) | (Large(_), Small(0)) => Ok(UBig::from_word(0)),
+            (Small(word0), Large(buffer1)) => UBig::try_mul_large_word(buffer1, *word0),
+            (Large(buffer0), Small(word1)) => UBig::try_mul_large_word(buffer0, *word1),
+            (Large(buffer0), Large(buffer1)) => UBig::try_mul_large(buffer0, buffer1),
+        }
+    }
+
+    /// Multiplication into an existing number: `*out = a * b`.
+    ///
+    /// Reuses the memory of `out` when it has enough capacity, and uses `scratch` for temporary
+    /// memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Scratch, UBig};
+    /// let (mut out, mut scratch) = (UBig::default(), Scratch::new());
+    /// UBig::mul_into(&mut out, &ubig!(6), &ubig!(7), &mut scratch);
+    /// assert_eq!(out, ubig!(42));
+    /// ```
+    pub fn mul_into(out: &mut UBig, a: &UBig, b: &UBig, scratch: &mut Scratch) {
+        match (a.repr(), b.repr()) {
+            (Small(_), Small(_)) | (Small(0), _) | (_, Small(0)) => out.assign(a * b),
+            (Small(word), Large(words)) | (Large(words), Small(word)) => {
+                let mut buffer = out.take_buffer(words.len() + 1);
+                buffer.extend_from_slice(&words[..]);
+                let carry = mul::mul_word_in_place(&mut buffer, *word);
+                buffer.push(carry);
+                *out = UBig::from_reused_buffer(buffer);
+            }
+            (Large(words0), Large(words1)) => {
+                let len = words0.len() + words1.len();
+                let mut buffer = out.take_buffer(len);
+                buffer.push_zeros(len);
+                let mut memory = scratch.memory(mul::memory_requirement_exact(
+                    words0.len().min(words1.len()),
+                ));
+                // Square when both operands are the same number. Comparing the values would
+                // cost a scan on every multiplication.
+                if ptr::eq(words0, words1) {
+                    mul::sqr(&mut buffer, words0, &mut memory);
+                } else {
+                    let overflow =
+                        mul::add_signed_mul(&mut buffer, Positive, words0, words1, &mut memory);
+                    assert!(overflow == 0);
+                }
+                *out = UBig::from_reused_buffer(buffer);
+            }
+        }
+    }
+
+    /// Multiplication using `scratch` for temporary memory.
+    ///
+    /// Same as `self * rhs`, but reuses the memory in `scratch` across calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Scratch};
+    /// let mut scratch = Scratch::new();
+    /// let a = ubig!(_0x123456789abcdef0123456789abcdef0);
+    /// assert_eq!(a.mul_with_scratch(&a, &mut scratch), &a * &a);
+    /// ```
+    pub fn mul_with_scratch(&self, rhs: &UBig, scratch: &mut Scratch) -> UBig {
+        match (self.repr(), rhs.repr()) {
+            (Large(buffer0), Large(buffer1)) => {
+                UBig::try_mul_large_with_scratch(buffer0, buffer1, scratch)
+                    .unwrap_or_else(|err| memory::panic_alloc_error(err))
+            }
+            _ => self * rhs,
+        }
+    }
+
+    /// Fused multiply-add: `*self += a * b`.
+    ///
+    /// The product is accumulated directly into `self`, without a temporary for `a * b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// let mut acc = ubig!(10);
+    /// acc.add_mul(&ubig!(6), &ubig!(7));
+    /// assert_eq!(acc, ubig!(52));
+    /// ```
+    pub fn add_mul(&mut self, a: &UBig, b: &UBig) {
+        self.add_mul_with_scratch(a, b, &mut Scratch::new());
+    }
+
+    /// Fused multiply-add using `scratch` for temporary memory.
+    ///
+    /// Same as [add_mul](UBig::add_mul), but reuses the memory in `scratch` across calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Scratch};
+    /// let mut scratch = Scratch::new();
+    /// let mut acc = ubig!(10);
+    /// acc.add_mul_with_scratch(&ubig!(6), &ubig!(7), &mut scratch);
+    /// assert_eq!(acc, ubig!(52));
+    /// ```
+    pub fn add_mul_with_scratch(&mut self, a: &UBig, b: &UBig, scratch: &mut Scratch) {
+        let sign = self.add_signed_mul_with_scratch(Positive, a, b, scratch);
+        debug_assert!(sign == Positive);
+    }
+
+    /// Fused multiply-subtract: `*self -= a * b`.
+    ///
+    /// The product is subtracted directly from `self`, without a temporary for `a * b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// let mut acc = ubig!(52);
+    /// acc.sub_mul(&ubig!(6), &ubig!(7));
+    /// assert_eq!(acc, ubig!(10));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a * b > self`.
+    pub fn sub_mul(&mut self, a: &UBig, b: &UBig) {
+        self.sub_mul_with_scratch(a, b, &mut Scratch::new());
+    }
+
+    /// Fused multiply-subtract using `scratch` for temporary memory.
+    ///
+    /// Same as [sub_mul](UBig::sub_mul), but reuses the memory in `scratch` across calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Scratch};
+    /// let mut scratch = Scratch::new();
+    /// let mut acc = ubig!(52);
+    /// acc.sub_mul_with_scratch(&ubig!(6), &ubig!(7), &mut scratch);
+    /// assert_eq!(acc, ubig!(10));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a * b > self`.
+    pub fn sub_mul_with_scratch(&mut self, a: &UBig, b: &UBig, scratch: &mut Scratch) {
+        if self.add_signed_mul_with_scratch(Negative, a, b, scratch) == Negative {
+            // Like `-=`, leave self unchanged. It is now a * b - self, and subtracting a * b again
+            // gives back the original value.
+            let _sign = self.add_signed_mul_with_scratch(Negative, a, b, scratch);
+            panic!("UBig subtraction overflow");
+        }
+    }
+}
+
+impl IBig {
+    /// Multiplication that fails instead of panicking or aborting if memory can't be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{error::AllocError, ibig};
+    /// assert_eq!(ibig!(-3).try_mul(&ibig!(4))?, ibig!(-12));
+    /// # Ok::<(), AllocError>(())
+    /// ```
+    pub fn try_mul(&self, rhs: &IBig) -> Result<IBig, AllocError> {
+        Ok(IBig::from_sign_magnitude(
+            self.sign() * rhs.sign(),
+            self.magnitude().try_mul(rhs.magnitude())?,
+        ))
+    }
+
+    /// Fused multiply-add: `*self += a * b`.
+    ///
+    /// The product is accumulated directly into `self`, without a temporary for `a * b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// let mut acc = ibig!(10);
+    /// acc.add_mul(&ibig!(-6), &ibig!(7));
+    /// assert_eq!(acc, ibig!(-32));
+    /// ```
+    pub fn add_mul(&mut self, a: &IBig, b: &IBig) {
+        self.add_mul_with_scratch(a, b, &mut Scratch::new());
+    }
+
+    /// Fused multiply-add using `scratch` for temporary memory.
+    ///
+    /// Same as [add_mul](IBig::add_mul), but reuses the memory in `scratch` across calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, Scratch};
+    /// let mut scratch = Scratch::new();
+    /// let mut acc = ibig!(10);
+    /// acc.add_mul_with_scratch(&ibig!(-6), &ibig!(7), &mut scratch);
+    /// assert_eq!(acc, ibig!(-32));
+    /// ```
+    pub fn add_mul_with_scratch(&mut self, a: &IBig, b: &IBig, scratch: &mut Scratch) {
+        self.add_signed_mul_with_scratch(Positive, a, b, scratch);
+    }
+
+    /// Fused multiply-subtract: `*self -= a * b`.
+    ///
+    /// The product is subtracted directly from `self`, without a temporary for `a * b`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// let mut acc = ibig!(10);
+    /// acc.sub_mul(&ibig!(6), &ibig!(7));
+    /// assert_eq!(acc, ibig!(-32));
+    /// ```
+    pub fn sub_mul(&mut self, a: &IBig, b: &IBig) {
+        self.sub_mul_with_scratch(a, b, &mut Scratch::new());
+    }
+
+    /// Fused multiply-subtract using `scratch` for temporary memory.
+    ///
+    /// Same as [sub_mul](IBig::sub_mul), but reuses the memory in `scratch` across calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, Scratch};
+    /// let mut scratch = Scratch::new();
+    /// let mut acc = ibig!(10);
+    /// acc.sub_mul_with_scratch(&ibig!(6), &ibig!(7), &mut scratch);
+    /// assert_eq!(acc, ibig!(-32));
+    /// ```
+    pub fn sub_mul_with_scratch(&mut self, a: &IBig, b: &IBig, scratch: &mut Scratch) {
+        self.add_signed_mul_with_scratch(Negative, a, b, scratch);
+    }
+
+    /// self += sign * a * b
+    fn add_signed_mul_with_scratch(
+        &mut self,
+        sign: Sign,
+        a: &IBig,
+        b: &IBig,
+        scratch: &mut Scratch,
+    ) {
+        let (self_sign, mut magnitude) = mem::take(self).into_sign_magnitude();
+        let magnitude_sign = magnitude.add_signed_mul_with_scratch(
+            self_sign * sign * a.sign() * b.sign(),
+            a.magnitude(),
+            b.magnitude(),
+            scratch,
+        );
+        *self = IBig::from_sign_magnitude(self_sign * magnitude_sign, magnitude);
+    }
+}
+
+/// Dot product: the sum of `a[i] * b[i]`.
+///
+/// The products are accumulated directly into the result, without temporaries, and the scratch
+/// memory for multiplication is reused across the terms.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{dot, ibig};
+/// let a = [ibig!(1), ibig!(-2), ibig!(3)];
+/// let b = [ibig!(4), ibig!(5), ibig!(-6)];
+/// assert_eq!(dot(&a, &b), ibig!(-24));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn dot(a: &[IBig], b: &[IBig]) -> IBig {
+    assert!(
+        a.len() == b.len(),
+        "dot product of slices of different lengths"
+    );
+    let mut scratch = Scratch::new();
+    let mut sum = IBig::default();
+    for (x, y) in a.iter().zip(b) {
+        sum.add_mul_with_scratch(x, y, &mut scratch);
+    }
+    sum
+}
+
 impl Mul<IBig> for IBig {
     type Output = IBig;
 
@@ -118,13 +423,55 @@ impl Mul<&IBig> for &IBig {
 
 impl MulAssign<IBig> for IBig {
     fn mul_assign(&mut self, rhs: IBig) {
-        *self = mem::take(self) * rhs;
+        self.update(|x| x * rhs);
     }
 }
 
 impl MulAssign<&IBig> for IBig {
     fn mul_assign(&mut self, rhs: &IBig) {
-        *self = mem::take(self) * rhs;
+        self.update(|x| x * rhs);
+    }
+}
+
+impl Product<UBig> for UBig {
+    fn product<I: Iterator<Item = UBig>>(iter: I) -> UBig {
+        product::product_balanced(iter.collect())
+    }
+}
+
+impl<'a> Product<&'a UBig> for UBig {
+    fn product<I: Iterator<Item = &'a UBig>>(iter: I) -> UBig {
+        let factors: Vec<&UBig> = iter.collect();
+        product::product_balanced_refs(&factors)
+    }
+}
+
+impl Product<IBig> for IBig {
+    fn product<I: Iterator<Item = IBig>>(iter: I) -> IBig {
+        let mut sign = Positive;
+        let magnitudes = iter
+            .map(|x| {
+                let (x_sign, magnitude) = x.into_sign_magnitude();
+                sign *= x_sign;
+                magnitude
+            })
+            .collect();
+        let magnitude = product::product_balanced(magnitudes);
+        IBig::from_sign_magnitude(sign, magnitude)
+    }
+}
+
+impl<'a> Product<&'a IBig> for IBig {
+    fn product<I: Iterator<Item = &'a IBig>>(iter: I) -> IBig {
+        let mut sign = Positive;
+        let magnitudes: Vec<&UBig> = iter
+            .map(|x| {
+                sign *= x.sign();
+                x.magnitude()
+            })
+            .collect();
+        let magnitude = product::product_balanced_refs(&magnitudes);
+        IBig::from_sign_magnitude(sign, magnitude)
     }
 }
 
@@ -149,12 +496,12 @@ impl MulAssign<Sign> for Sign {
 
 impl UBig {
     /// Multiply two `Word`s.
-    fn mul_word(a: Word, b: Word) -> UBig {
+    pub(crate) fn mul_word(a: Word, b: Word) -> UBig {
         UBig::from(extend_word(a) * extend_word(b))
     }
 
     /// Multiply a large number by a `Word`.
-    fn mul_large_word(mut buffer: Buffer, a: Word) -> UBig {
+    pub(crate) fn mul_large_word(mut buffer: Buffer, a: Word) -> UBig {
         match a {
             0 => UBig::from_word(0),
             1 => buffer.into(),
@@ -169,17 +516,102 @@ impl UBig {
     }
 
     /// Multiply two large numbers.
-    fn mul_large(lhs: &[Word], rhs: &[Word]) -> UBig {
+    pub(crate) fn mul_large(lhs: &[Word], rhs: &[Word]) -> UBig {
+        UBig::try_mul_large(lhs, rhs).unwrap_or_else(|err| memory::panic_alloc_error(err))
+    }
+
+    /// self = |self + sign * a * b|
+    ///
+    /// Returns the sign of `self + sign * a * b`.
+    fn add_signed_mul_with_scratch(
+        &mut self,
+        sign: Sign,
+        a: &UBig,
+        b: &UBig,
+        scratch: &mut Scratch,
+    ) -> Sign {
+        if let (Small(word), Small(a), Small(b)) = (self.repr(), a.repr(), b.repr()) {
+            // No overflow: (2^n - 1) + (2^n - 1)^2 < 2^(2n).
+            let (word, product) = (extend_word(*word), extend_word(*a) * extend_word(*b));
+            let (value, result_sign) = match sign {
+                Positive => (word + product, Positive),
+                Negative if word >= product => (word - product, Positive),
+                Negative => (product - word, Negative),
+            };
+            self.assign(UBig::from(value));
+            return result_sign;
+        }
+        let (a, b) = (a.as_words(), b.as_words());
+        if a.is_empty() || b.is_empty() {
+            return Positive;
+        }
+        let mul_len = a.len() + b.len();
+        // One extra word for the carry.
+        let len = self.len().max(mul_len) + 1;
+        let mut buffer = self.take_words(len);
+        buffer.push_zeros(len - buffer.len());
+
+        let mut memory = scratch.memory(mul::memory_requirement_exact(a.len().min(b.len())));
+        let carry = mul::add_signed_mul(&mut buffer[..mul_len], sign, a, b, &mut memory);
+        let overflow = add::add_signed_word_in_place(&mut buffer[mul_len..], carry);
+        let result_sign = if overflow == 0 {
+            Positive
+        } else {
+            // The result is negative, in two's complement.
+            debug_assert!(overflow == -1);
+            add::negate_in_place(&mut buffer);
+            Negative
+        };
+        *self = buffer.into();
+        result_sign
+    }
+
+    /// Multiply two `Word`s, or fail if memory can't be allocated.
+    fn try_mul_word(a: Word, b: Word) -> Result<UBig, AllocError> {
+        let (lo, hi) = split_double_word(extend_word(a) * extend_word(b));
+        if hi == 0 {
+            return Ok(UBig::from_word(lo));
+        }
+        let mut buffer = Buffer::try_allocate(2)?;
+        buffer.push(lo);
+        buffer.push(hi);
+        UBig::try_from_buffer(buffer)
+    }
+
+    /// Multiply a large number by a `Word`, or fail if memory can't be allocated.
+    fn try_mul_large_word(words: &[Word], a: Word) -> Result<UBig, AllocError> {
+        let mut buffer = Buffer::try_allocate(words.len() + 1)?;
+        buffer.extend_from_slice(words);
+        let carry = mul::mul_word_in_place(&mut buffer, a);
+        buffer.push(carry);
+        UBig::try_from_buffer(buffer)
+    }
+
+    /// Multiply two large numbers, or fail if memory can't be allocated.
+    fn try_mul_large(lhs: &[Word], rhs: &[Word]) -> Result<UBig, AllocError> {
+        UBig::try_mul_large_with_scratch(lhs, rhs, &mut Scratch::new())
+    }
+
+    /// Multiply two large numbers using scratch memory, or fail if memory can't be allocated.
+    fn try_mul_large_with_scratch(
+        lhs: &[Word],
+        rhs: &[Word],
+        scratch: &mut Scratch,
+    ) -> Result<UBig, AllocError> {
         debug_assert!(lhs.len() >= 2 && rhs.len() >= 2);
 
-        let mut buffer = Buffer::allocate(lhs.len() + rhs.len());
+        let mut buffer = Buffer::try_allocate(lhs.len() + rhs.len())?;
         buffer.push_zeros(lhs.len() + rhs.len());
 
-        let mut allocation =
-            MemoryAllocation::new(mul::memory_requirement_exact(lhs.len().min(rhs.len())));
-        let mut memory = allocation.memory();
-        let overflow = mul::add_signed_mul(&mut buffer, Positive, lhs, rhs, &mut memory);
-        assert!(overflow == 0);
-        buffer.into()
+        let mut memory =
+            scratch.try_memory(mul::memory_requirement_exact(lhs.len().min(rhs.len())))?;
+        // Square when both operands are the same number.
+        if ptr::eq(lhs, rhs) {
+            mul::sqr(&mut buffer, lhs, &mut memory);
+        } else {
+            let overflow = mul::add_signed_mul(&mut buffer, Positive, lhs, rhs, &mut memory);
+            assert!(overflow == 0);
+        }
+        UBig::try_from_buffer(buffer)
     }
 }
@@ -109,3 +109,80 @@ pub trait DivRemEuclid<Rhs = Self> {
 
     fn div_rem_euclid(self, rhs: Rhs) -> (Self::OutputDiv, Self::OutputRem);
 }
+
+/// Compute quotient rounded towards negative infinity.
+///
+/// # Example
+/// ```
+/// # use ibig::{ibig, ops::DivFloor};
+/// assert_eq!(ibig!(-23).div_floor(ibig!(10)), ibig!(-3));
+/// assert_eq!(ibig!(-23).div_floor(ibig!(-10)), ibig!(2));
+/// ```
+pub trait DivFloor<Rhs = Self> {
+    type Output;
+
+    fn div_floor(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Compute quotient rounded towards negative infinity and the matching remainder at the same
+/// time.
+///
+/// The remainder has the same sign as the divisor.
+///
+/// # Example
+/// ```
+/// # use ibig::{ibig, ops::DivRemFloor};
+/// assert_eq!(ibig!(-23).div_rem_floor(ibig!(10)), (ibig!(-3), ibig!(7)));
+/// assert_eq!(ibig!(23).div_rem_floor(ibig!(-10)), (ibig!(-3), ibig!(-7)));
+/// ```
+pub trait DivRemFloor<Rhs = Self> {
+    type OutputDiv;
+    type OutputRem;
+
+    fn div_rem_floor(self, rhs: Rhs) -> (Self::OutputDiv, Self::OutputRem);
+}
+
+/// Compute quotient rounded towards positive infinity.
+///
+/// # Example
+/// ```
+/// # use ibig::{ibig, ops::DivCeil};
+/// assert_eq!(ibig!(23).div_ceil(ibig!(10)), ibig!(3));
+/// assert_eq!(ibig!(-23).div_ceil(ibig!(10)), ibig!(-2));
+/// ```
+pub trait DivCeil<Rhs = Self> {
+    type Output;
+
+    fn div_ceil(self, rhs: Rhs) -> Self::Output;
+}
+
+/// How [DivRound] breaks ties, when the exact quotient is halfway between two integers.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TieBreak {
+    /// Round to the even integer.
+    Even,
+    /// Round away from zero.
+    AwayFromZero,
+    /// Round towards zero.
+    TowardZero,
+    /// Round towards positive infinity.
+    Up,
+    /// Round towards negative infinity.
+    Down,
+}
+
+/// Compute quotient rounded to the nearest integer.
+///
+/// # Example
+/// ```
+/// # use ibig::{ibig, ops::{DivRound, TieBreak}};
+/// assert_eq!(ibig!(26).div_round(ibig!(10), TieBreak::Even), ibig!(3));
+/// assert_eq!(ibig!(25).div_round(ibig!(10), TieBreak::Even), ibig!(2));
+/// assert_eq!(ibig!(-25).div_round(ibig!(10), TieBreak::AwayFromZero), ibig!(-3));
+/// assert_eq!(ibig!(-25).div_round(ibig!(10), TieBreak::Up), ibig!(-2));
+/// ```
+pub trait DivRound<Rhs = Self> {
+    type Output;
+
+    fn div_round(self, rhs: Rhs, ties: TieBreak) -> Self::Output;
+}
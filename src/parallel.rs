@@ -0,0 +1,32 @@
+//! Parallel execution of independent sub-computations.
+//!
+//! With the `rayon` feature, large sub-computations run on the rayon thread pool. Otherwise, and
+//! for small sizes, everything runs serially. The results are the same either way.
+
+use crate::thresholds;
+
+/// Should sub-computations on numbers of len words run in parallel?
+#[inline]
+pub(crate) fn is_worthwhile(len: usize) -> bool {
+    cfg!(feature = "rayon") && len >= thresholds::current().parallel
+}
+
+/// Run `a` and `b`, in parallel if `parallel` is true and the `rayon` feature is enabled.
+#[inline]
+pub(crate) fn join<A, B, RA, RB>(parallel: bool, a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    #[cfg(feature = "rayon")]
+    {
+        if parallel {
+            return rayon::join(a, b);
+        }
+    }
+    #[cfg(not(feature = "rayon"))]
+    let _ = parallel;
+    (a(), b())
+}
@@ -1,8 +1,9 @@
 //! Parsing numbers.
 
 use crate::{
-    error::ParseError,
+    error::{ParseError, TryParseError},
     ibig::IBig,
+    memory,
     radix::{self, Digit},
     sign::Sign::*,
     ubig::UBig,
@@ -45,6 +46,29 @@ impl UBig {
     /// # Ok::<(), ParseError>(())
     /// ```
     pub fn from_str_radix(src: &str, radix: u32) -> Result<UBig, ParseError> {
+        panic_on_alloc_error(UBig::try_from_str_radix(src, radix))
+    }
+
+    /// Convert a string in a given base to [UBig], failing instead of panicking or aborting if
+    /// the number is too large or memory can't be allocated.
+    ///
+    /// Same as [UBig::from_str_radix] otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36 inclusive.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ibig::{error::{ParseError, TryParseError}, ubig, UBig};
+    /// assert_eq!(UBig::try_from_str_radix("+7ab", 32)?, ubig!(7499));
+    /// assert_eq!(
+    ///     UBig::try_from_str_radix("7xb", 32),
+    ///     Err(TryParseError::Parse(ParseError::InvalidDigit))
+    /// );
+    /// # Ok::<(), TryParseError>(())
+    /// ```
+    pub fn try_from_str_radix(src: &str, radix: u32) -> Result<UBig, TryParseError> {
         radix::check_radix_valid(radix);
         let src = src.strip_prefix("+").unwrap_or(src);
         UBig::from_str_radix_no_sign(src, radix)
@@ -66,11 +90,11 @@ impl UBig {
     /// ```
     pub fn from_str_with_radix_prefix(src: &str) -> Result<UBig, ParseError> {
         let src = src.strip_prefix("+").unwrap_or(src);
-        UBig::from_str_with_radix_prefix_no_sign(src)
+        panic_on_alloc_error(UBig::from_str_with_radix_prefix_no_sign(src))
     }
 
     /// Convert an unsigned string with an optional radix prefix to [UBig].
-    fn from_str_with_radix_prefix_no_sign(src: &str) -> Result<UBig, ParseError> {
+    fn from_str_with_radix_prefix_no_sign(src: &str) -> Result<UBig, TryParseError> {
         if let Some(bin) = src.strip_prefix("0b") {
             UBig::from_str_radix_no_sign(bin, 2)
         } else if let Some(oct) = src.strip_prefix("0o") {
@@ -83,10 +107,10 @@ impl UBig {
     }
 
     /// Convert an unsigned string to [UBig].
-    fn from_str_radix_no_sign(mut src: &str, radix: Digit) -> Result<UBig, ParseError> {
+    fn from_str_radix_no_sign(mut src: &str, radix: Digit) -> Result<UBig, TryParseError> {
         debug_assert!(radix::is_radix_valid(radix));
         if src.is_empty() {
-            return Err(ParseError::NoDigits);
+            return Err(ParseError::NoDigits.into());
         }
 
         while let Some(src2) = src.strip_prefix("0") {
@@ -117,7 +141,26 @@ impl IBig {
     /// assert_eq!(IBig::from_str_radix("-7ab", 32)?, ibig!(-7499));
     /// # Ok::<(), ParseError>(())
     /// ```
-    pub fn from_str_radix(mut src: &str, radix: u32) -> Result<IBig, ParseError> {
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<IBig, ParseError> {
+        panic_on_alloc_error(IBig::try_from_str_radix(src, radix))
+    }
+
+    /// Convert a string in a given base to [IBig], failing instead of panicking or aborting if
+    /// the number is too large or memory can't be allocated.
+    ///
+    /// Same as [IBig::from_str_radix] otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36 inclusive.
+    ///
+    /// # Examples
+    /// ```
+    /// # use ibig::{error::TryParseError, ibig, IBig};
+    /// assert_eq!(IBig::try_from_str_radix("-7ab", 32)?, ibig!(-7499));
+    /// # Ok::<(), TryParseError>(())
+    /// ```
+    pub fn try_from_str_radix(mut src: &str, radix: u32) -> Result<IBig, TryParseError> {
         radix::check_radix_valid(radix);
         let sign;
         match src.strip_prefix("-") {
@@ -159,7 +202,15 @@ impl IBig {
                 src = src.strip_prefix("+").unwrap_or(src);
             }
         }
-        let mag = UBig::from_str_with_radix_prefix_no_sign(src)?;
+        let mag = panic_on_alloc_error(UBig::from_str_with_radix_prefix_no_sign(src))?;
         Ok(IBig::from_sign_magnitude(sign, mag))
     }
 }
+
+/// Panic if parsing failed because memory couldn't be allocated.
+fn panic_on_alloc_error<T>(result: Result<T, TryParseError>) -> Result<T, ParseError> {
+    result.map_err(|err| match err {
+        TryParseError::Parse(err) => err,
+        TryParseError::Alloc(err) => memory::panic_alloc_error(err),
+    })
+}
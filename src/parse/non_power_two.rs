@@ -3,18 +3,20 @@
 use crate::{
     arch::word::Word,
     buffer::Buffer,
-    error::ParseError,
-    mul,
+    error::{AllocError, ParseError, TryParseError},
+    mul, parallel,
     radix::{self, Digit},
+    scratch::Scratch,
+    sign::Sign::*,
+    thresholds,
     ubig::UBig,
 };
-use alloc::vec;
+use alloc::vec::Vec;
 
-/// Parse in chunks of CHUNK_LEN * digits_per_word.
-const CHUNK_LEN: usize = 256;
+// Parse in chunks of `parse_chunk * digits_per_word` digits, `parse_chunk` being the threshold.
 
 /// Parse an unsigned string to [UBig].
-pub(crate) fn parse(src: &str, radix: Digit) -> Result<UBig, ParseError> {
+pub(crate) fn parse(src: &str, radix: Digit) -> Result<UBig, TryParseError> {
     debug_assert!(radix::is_radix_valid(radix) && !radix.is_power_of_two());
     let radix_info = radix::radix_info(radix);
     let bytes = src.as_bytes();
@@ -22,7 +24,7 @@ pub(crate) fn parse(src: &str, radix: Digit) -> Result<UBig, ParseError> {
     if bytes.len() <= radix_info.digits_per_word {
         let word = parse_word(bytes, radix)?;
         Ok(UBig::from_word(word))
-    } else if bytes.len() <= CHUNK_LEN * radix_info.digits_per_word {
+    } else if bytes.len() <= thresholds::current().parse_chunk * radix_info.digits_per_word {
         parse_chunk(bytes, radix)
     } else {
         parse_large(bytes, radix)
@@ -46,14 +48,13 @@ fn parse_word(src: &[u8], radix: Digit) -> Result<Word, ParseError> {
 
 /// Parse an unsigned string to [UBig].
 ///
-/// The length of input is limited to `CHUNK_LEN * digits_per_word`.
-fn parse_chunk(bytes: &[u8], radix: Digit) -> Result<UBig, ParseError> {
+/// The length of input is limited to `parse_chunk * digits_per_word`.
+fn parse_chunk(bytes: &[u8], radix: Digit) -> Result<UBig, TryParseError> {
     debug_assert!(radix::is_radix_valid(radix) && !radix.is_power_of_two());
     let radix_info = radix::radix_info(radix);
-    debug_assert!(bytes.len() <= CHUNK_LEN * radix_info.digits_per_word);
 
     let groups = bytes.rchunks(radix_info.digits_per_word);
-    let mut buffer = Buffer::allocate(groups.len());
+    let mut buffer = Buffer::try_allocate(groups.len())?;
     for group in groups.rev() {
         let next = parse_word(group, radix)?;
         let carry = mul::mul_word_in_place_with_carry(&mut buffer, radix_info.range_per_word, next);
@@ -61,32 +62,44 @@ fn parse_chunk(bytes: &[u8], radix: Digit) -> Result<UBig, ParseError> {
             buffer.push(carry);
         }
     }
-    Ok(buffer.into())
+    Ok(UBig::try_from_buffer(buffer)?)
 }
 
 /// Parse an unsigned string to [UBig].
 ///
-/// This result will usually not fit in CHUNK_LEN words.
-fn parse_large(bytes: &[u8], radix: Digit) -> Result<UBig, ParseError> {
+/// This result will usually not fit in `parse_chunk` words.
+fn parse_large(bytes: &[u8], radix: Digit) -> Result<UBig, TryParseError> {
     debug_assert!(radix::is_radix_valid(radix) && !radix.is_power_of_two());
     let radix_info = radix::radix_info(radix);
-    let chunk_bytes = CHUNK_LEN * radix_info.digits_per_word;
+    let chunk_len = thresholds::current().parse_chunk;
+    let chunk_bytes = chunk_len * radix_info.digits_per_word;
     assert!(bytes.len() > chunk_bytes);
 
-    // Calculate radix^(CHUNK_LEN<<i).
-    let mut radix_powers = vec![UBig::from_word(radix_info.range_per_word).pow(CHUNK_LEN)];
+    // Calculate radix^(chunk_len<<i).
+    let mut radix_powers = Vec::new();
+    try_push(
+        &mut radix_powers,
+        UBig::from_word(radix_info.range_per_word).try_pow(chunk_len)?,
+    )?;
 
     // while (chunk_bytes << radix_powers.len()) < bytes.len()
     // To avoid overflow:
     while chunk_bytes <= (bytes.len() - 1) >> radix_powers.len() {
         let prev = radix_powers.last().unwrap();
-        let new = prev * prev;
-        radix_powers.push(new);
+        let new = prev.try_mul(prev)?;
+        try_push(&mut radix_powers, new)?;
     }
 
     parse_large_divide_conquer(bytes, radix, chunk_bytes, &radix_powers)
 }
 
+/// Push to a `Vec`, or fail if memory can't be allocated.
+fn try_push(v: &mut Vec<UBig>, x: UBig) -> Result<(), AllocError> {
+    v.try_reserve(1).map_err(|_| AllocError::OutOfMemory)?;
+    v.push(x);
+    Ok(())
+}
+
 /// Convert an unsigned string to [UBig].
 ///
 /// `radix_powers` contains radix^n for n = chunk digits << i
@@ -95,7 +108,7 @@ fn parse_large_divide_conquer(
     radix: Digit,
     chunk_bytes: usize,
     radix_powers: &[UBig],
-) -> Result<UBig, ParseError> {
+) -> Result<UBig, TryParseError> {
     debug_assert!(bytes.len() <= chunk_bytes << radix_powers.len());
 
     match radix_powers.split_last() {
@@ -106,12 +119,33 @@ fn parse_large_divide_conquer(
                 parse_large_divide_conquer(bytes, radix, chunk_bytes, radix_powers)
             } else {
                 let (bytes_hi, bytes_lo) = bytes.split_at(bytes.len() - bytes_lo_len);
-                let res_hi =
-                    parse_large_divide_conquer(bytes_hi, radix, chunk_bytes, radix_powers)?;
-                let res_lo =
-                    parse_large_divide_conquer(bytes_lo, radix, chunk_bytes, radix_powers)?;
-                Ok(res_hi * radix_power + res_lo)
+                let (res_hi, res_lo) = parallel::join(
+                    parallel::is_worthwhile(radix_power.len()),
+                    || parse_large_divide_conquer(bytes_hi, radix, chunk_bytes, radix_powers),
+                    || parse_large_divide_conquer(bytes_lo, radix, chunk_bytes, radix_powers),
+                );
+                Ok(try_mul_add(&res_hi?, radix_power, &res_lo?)?)
             }
         }
     }
 }
+
+/// hi * radix_power + lo, or fails if memory can't be allocated.
+///
+/// lo < radix_power, so the result fits in the length of the product.
+fn try_mul_add(hi: &UBig, radix_power: &UBig, lo: &UBig) -> Result<UBig, AllocError> {
+    let (hi, radix_power) = (hi.as_words(), radix_power.as_words());
+    let len = hi.len() + radix_power.len();
+    let mut buffer = Buffer::try_allocate(len)?;
+    buffer.extend_from_slice(lo.as_words());
+    buffer.push_zeros(len - buffer.len());
+    if !hi.is_empty() {
+        let mut scratch = Scratch::new();
+        let mut memory = scratch.try_memory(mul::memory_requirement_exact(
+            hi.len().min(radix_power.len()),
+        ))?;
+        let overflow = mul::add_signed_mul(&mut buffer, Positive, hi, radix_power, &mut memory);
+        assert!(overflow == 0);
+    }
+    UBig::try_from_buffer(buffer)
+}
@@ -3,14 +3,14 @@
 use crate::{
     arch::word::Word,
     buffer::Buffer,
-    error::ParseError,
+    error::{AllocError, CapacityError, ParseError, TryParseError},
     primitive::{WORD_BITS, WORD_BITS_USIZE},
     radix::{self, Digit},
     ubig::UBig,
 };
 
 /// Parse an unsigned string to [UBig].
-pub(crate) fn parse(src: &str, radix: Digit) -> Result<UBig, ParseError> {
+pub(crate) fn parse(src: &str, radix: Digit) -> Result<UBig, TryParseError> {
     debug_assert!(radix::is_radix_valid(radix) && radix.is_power_of_two());
     let radix_info = radix::radix_info(radix);
 
@@ -43,15 +43,15 @@ fn parse_word(src: &str, radix: Digit) -> Result<Word, ParseError> {
 /// Parse an unsigned string to [UBig].
 ///
 /// The result will usually not fit in a single word.
-fn parse_large(src: &str, radix: Digit) -> Result<UBig, ParseError> {
+fn parse_large(src: &str, radix: Digit) -> Result<UBig, TryParseError> {
     debug_assert!(radix::is_radix_valid(radix) && radix.is_power_of_two());
 
     let log_radix = radix.trailing_zeros();
     let num_bits = src
         .len()
         .checked_mul(log_radix as usize)
-        .unwrap_or_else(|| UBig::panic_number_too_large());
-    let mut buffer = Buffer::allocate((num_bits - 1) / WORD_BITS_USIZE + 1);
+        .ok_or(AllocError::from(CapacityError))?;
+    let mut buffer = Buffer::try_allocate((num_bits - 1) / WORD_BITS_USIZE + 1)?;
     let mut bits = 0;
     let mut word = 0;
     for byte in src.as_bytes().iter().rev() {
@@ -69,5 +69,5 @@ fn parse_large(src: &str, radix: Digit) -> Result<UBig, ParseError> {
     if bits > 0 {
         buffer.push(word);
     }
-    Ok(buffer.into())
+    Ok(UBig::try_from_buffer(buffer)?)
 }
@@ -1,7 +1,9 @@
 //! Exponentiation.
 
 use crate::{
+    error::{AllocError, CapacityError},
     ibig::IBig,
+    memory,
     primitive::PrimitiveUnsigned,
     sign::Sign::*,
     ubig::{Repr::*, UBig},
@@ -17,35 +19,55 @@ impl UBig {
     /// assert_eq!(ubig!(3).pow(3), ubig!(27));
     /// ```
     pub fn pow(&self, exp: usize) -> UBig {
+        self.try_pow(exp)
+            .unwrap_or_else(|err| memory::panic_alloc_error(err))
+    }
+
+    /// Raises self to the power of `exp`, or fails if the result is too large or memory can't
+    /// be allocated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::{error::{AllocError, CapacityError}, ubig, UBig};
+    /// assert_eq!(ubig!(3).try_pow(3)?, ubig!(27));
+    /// assert_eq!(
+    ///     ubig!(3).try_pow(UBig::MAX_BIT_LEN),
+    ///     Err(AllocError::Capacity(CapacityError))
+    /// );
+    /// # Ok::<(), AllocError>(())
+    /// ```
+    pub fn try_pow(&self, exp: usize) -> Result<UBig, AllocError> {
         match exp {
-            0 => return UBig::from_word(1),
-            1 => return self.clone(),
-            2 => return self * self,
+            0 => return Ok(UBig::from_word(1)),
+            1 => return self.try_clone(),
+            2 => return self.try_mul(self),
             _ => {}
         }
         match self.repr() {
-            Small(0) => return UBig::from_word(0),
-            Small(1) => return UBig::from_word(1),
-            Small(2) => {
-                let mut x = UBig::from_word(0);
-                x.set_bit(exp);
-                return x;
-            }
+            Small(0) => return Ok(UBig::from_word(0)),
+            Small(1) => return Ok(UBig::from_word(1)),
+            Small(2) => return UBig::from_word(1).try_shl(exp),
             _ => {}
         }
+        // The result has at least (bit_len - 1) * exp + 1 bits.
+        match (self.bit_len() - 1).checked_mul(exp) {
+            Some(bits) if bits < UBig::MAX_BIT_LEN => {}
+            _ => return Err(CapacityError.into()),
+        }
         let mut p = usize::BIT_SIZE - 2 - exp.leading_zeros();
-        let mut res = self * self;
+        let mut res = self.try_mul(self)?;
         loop {
             if exp & (1 << p) != 0 {
-                res *= self;
+                res = res.try_mul(self)?;
             }
             if p == 0 {
                 break;
             }
             p -= 1;
-            res = &res * &res;
+            res = res.try_mul(&res)?;
         }
-        res
+        Ok(res)
     }
 }
 
@@ -66,4 +88,26 @@ impl IBig {
         };
         IBig::from_sign_magnitude(sign, self.magnitude().pow(exp))
     }
+
+    /// Raises self to the power of `exp`, or fails if the result is too large or memory can't
+    /// be allocated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::{error::AllocError, ibig};
+    /// assert_eq!(ibig!(-3).try_pow(3)?, ibig!(-27));
+    /// # Ok::<(), AllocError>(())
+    /// ```
+    pub fn try_pow(&self, exp: usize) -> Result<IBig, AllocError> {
+        let sign = if self.sign() == Negative && exp % 2 == 1 {
+            Negative
+        } else {
+            Positive
+        };
+        Ok(IBig::from_sign_magnitude(
+            sign,
+            self.magnitude().try_pow(exp)?,
+        ))
+    }
 }
@@ -0,0 +1,116 @@
+//! Balanced products, product trees and remainder trees.
+
+use crate::ubig::UBig;
+use alloc::{vec, vec::Vec};
+use core::borrow::Borrow;
+
+/// Product tree of `factors`.
+///
+/// Level 0 of the tree is `factors`. Each next level contains the products of adjacent pairs in
+/// the previous level, with an unpaired last element carried over as it is. The last level
+/// contains the product of all the factors. The tree is empty if there are no factors.
+///
+/// The products are balanced, so that multiplications of large numbers use the fast
+/// multiplication algorithms.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{product_tree, ubig};
+/// let tree = product_tree(&[ubig!(2), ubig!(3), ubig!(5)]);
+/// assert_eq!(
+///     tree,
+///     [
+///         vec![ubig!(2), ubig!(3), ubig!(5)],
+///         vec![ubig!(6), ubig!(5)],
+///         vec![ubig!(30)],
+///     ]
+/// );
+/// ```
+pub fn product_tree(factors: &[UBig]) -> Vec<Vec<UBig>> {
+    if factors.is_empty() {
+        return Vec::new();
+    }
+    let mut tree = vec![factors.to_vec()];
+    while tree.last().unwrap().len() > 1 {
+        let level = pair_products(tree.last().unwrap());
+        tree.push(level);
+    }
+    tree
+}
+
+/// Remainders of `x` modulo each factor of a product tree.
+///
+/// `tree` is the product tree of the moduli, as returned by [product_tree]. Returns `x % m` for
+/// each modulus `m` in level 0 of the tree.
+///
+/// `x` is reduced modulo the products down the tree, so that large divisions are balanced. This
+/// is much faster than dividing `x` by each modulus when `x` is large and there are many moduli.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{product_tree, remainder_tree, ubig};
+/// let tree = product_tree(&[ubig!(7), ubig!(10), ubig!(13)]);
+/// assert_eq!(
+///     remainder_tree(&ubig!(1000), &tree),
+///     [ubig!(6), ubig!(0), ubig!(12)]
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Panics if a modulus is 0.
+pub fn remainder_tree(x: &UBig, tree: &[Vec<UBig>]) -> Vec<UBig> {
+    let (top, levels) = match tree.split_last() {
+        Some(split) => split,
+        None => return Vec::new(),
+    };
+    let mut remainders: Vec<UBig> = top.iter().map(|m| x % m).collect();
+    for level in levels.iter().rev() {
+        remainders = level
+            .iter()
+            .enumerate()
+            .map(|(i, m)| &remainders[i / 2] % m)
+            .collect();
+    }
+    remainders
+}
+
+/// Product of `factors` using a balanced product tree.
+///
+/// Large factors then meet in the fast multiplication algorithms, rather than each being
+/// multiplied by a growing partial product. The product of no factors is 1.
+pub(crate) fn product_balanced(mut factors: Vec<UBig>) -> UBig {
+    while factors.len() > 1 {
+        let mut iter = factors.into_iter();
+        let mut products = Vec::new();
+        while let Some(a) = iter.next() {
+            products.push(match iter.next() {
+                Some(b) => a * b,
+                None => a,
+            });
+        }
+        factors = products;
+    }
+    factors.pop().unwrap_or_else(|| UBig::from_word(1))
+}
+
+/// Product of `factors` using a balanced product tree.
+///
+/// The product of no factors is 1.
+pub(crate) fn product_balanced_refs(factors: &[&UBig]) -> UBig {
+    product_balanced(pair_products(factors))
+}
+
+/// Products of adjacent pairs, with an unpaired last element carried over.
+fn pair_products<T: Borrow<UBig>>(factors: &[T]) -> Vec<UBig> {
+    factors
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => a.borrow() * b.borrow(),
+            [a] => a.borrow().clone(),
+            _ => unreachable!(),
+        })
+        .collect()
+}
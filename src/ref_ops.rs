@@ -0,0 +1,252 @@
+//! Arithmetic operators on borrowed numbers.
+
+use crate::{
+    buffer::Buffer,
+    ibig::IBig,
+    ops::DivRem,
+    sign::Sign::*,
+    ubig::UBig,
+    ubig_ref::{IBigRef, RefRepr::*, UBigRef},
+};
+use core::ops::{Add, Div, Mul, Neg, Rem, Shl, Shr, Sub};
+
+impl Add<UBigRef<'_>> for UBigRef<'_> {
+    type Output = UBig;
+
+    fn add(self, rhs: UBigRef) -> UBig {
+        match (self.repr(), rhs.repr()) {
+            (Small(word0), Small(word1)) => UBig::add_word(word0, word1),
+            (Small(word0), Large(words1)) => {
+                UBig::add_large_word(UBig::copy_for_add(words1), word0)
+            }
+            (Large(words0), Small(word1)) => {
+                UBig::add_large_word(UBig::copy_for_add(words0), word1)
+            }
+            (Large(words0), Large(words1)) => {
+                if words0.len() >= words1.len() {
+                    UBig::add_large(UBig::copy_for_add(words0), words1)
+                } else {
+                    UBig::add_large(UBig::copy_for_add(words1), words0)
+                }
+            }
+        }
+    }
+}
+
+impl Sub<UBigRef<'_>> for UBigRef<'_> {
+    type Output = UBig;
+
+    fn sub(self, rhs: UBigRef) -> UBig {
+        UBig::from_ibig_after_sub(IBig::sub_ubig_refs(self, rhs))
+    }
+}
+
+impl Mul<UBigRef<'_>> for UBigRef<'_> {
+    type Output = UBig;
+
+    fn mul(self, rhs: UBigRef) -> UBig {
+        match (self.repr(), rhs.repr()) {
+            (Small(word0), Small(word1)) => UBig::mul_word(word0, word1),
+            (Small(word0), Large(words1)) => {
+                UBig::mul_large_word(Buffer::from_words(words1), word0)
+            }
+            (Large(words0), Small(word1)) => {
+                UBig::mul_large_word(Buffer::from_words(words0), word1)
+            }
+            (Large(words0), Large(words1)) => UBig::mul_large(words0, words1),
+        }
+    }
+}
+
+impl Div<UBigRef<'_>> for UBigRef<'_> {
+    type Output = UBig;
+
+    fn div(self, rhs: UBigRef) -> UBig {
+        match (self.repr(), rhs.repr()) {
+            (Small(word0), Small(word1)) => UBig::div_word(word0, word1),
+            (Small(_), Large(_)) => UBig::from_word(0),
+            (Large(words0), Small(word1)) => {
+                UBig::div_large_word(Buffer::from_words(words0), word1)
+            }
+            (Large(words0), Large(words1)) => {
+                if words0.len() >= words1.len() {
+                    UBig::div_large(Buffer::from_words(words0), Buffer::from_words(words1))
+                } else {
+                    UBig::from_word(0)
+                }
+            }
+        }
+    }
+}
+
+impl Rem<UBigRef<'_>> for UBigRef<'_> {
+    type Output = UBig;
+
+    fn rem(self, rhs: UBigRef) -> UBig {
+        match (self.repr(), rhs.repr()) {
+            (Small(word0), Small(word1)) => UBig::rem_word(word0, word1),
+            (Small(word0), Large(_)) => UBig::from_word(word0),
+            (Large(words0), Small(word1)) => UBig::rem_large_word(words0, word1),
+            (Large(words0), Large(words1)) => {
+                if words0.len() >= words1.len() {
+                    UBig::rem_large(Buffer::from_words(words0), Buffer::from_words(words1))
+                } else {
+                    self.to_owned()
+                }
+            }
+        }
+    }
+}
+
+impl DivRem<UBigRef<'_>> for UBigRef<'_> {
+    type OutputDiv = UBig;
+    type OutputRem = UBig;
+
+    fn div_rem(self, rhs: UBigRef) -> (UBig, UBig) {
+        match (self.repr(), rhs.repr()) {
+            (Small(word0), Small(word1)) => UBig::div_rem_word(word0, word1),
+            (Small(word0), Large(_)) => (UBig::from_word(0), UBig::from_word(word0)),
+            (Large(words0), Small(word1)) => {
+                UBig::div_rem_large_word(Buffer::from_words(words0), word1)
+            }
+            (Large(words0), Large(words1)) => {
+                if words0.len() >= words1.len() {
+                    UBig::div_rem_large(Buffer::from_words(words0), Buffer::from_words(words1))
+                } else {
+                    (UBig::from_word(0), self.to_owned())
+                }
+            }
+        }
+    }
+}
+
+impl Shl<usize> for UBigRef<'_> {
+    type Output = UBig;
+
+    fn shl(self, rhs: usize) -> UBig {
+        match self.repr() {
+            Small(0) => UBig::from_word(0),
+            Small(word) => UBig::shl_word(word, rhs),
+            Large(words) => UBig::shl_ref_large(words, rhs),
+        }
+    }
+}
+
+impl Shr<usize> for UBigRef<'_> {
+    type Output = UBig;
+
+    fn shr(self, rhs: usize) -> UBig {
+        match self.repr() {
+            Small(word) => UBig::shr_word(word, rhs),
+            Large(words) => UBig::shr_large_ref(words, rhs),
+        }
+    }
+}
+
+impl Neg for IBigRef<'_> {
+    type Output = IBig;
+
+    fn neg(self) -> IBig {
+        IBig::from_sign_magnitude(-self.sign(), self.magnitude().to_owned())
+    }
+}
+
+impl Add<IBigRef<'_>> for IBigRef<'_> {
+    type Output = IBig;
+
+    fn add(self, rhs: IBigRef) -> IBig {
+        let (sign0, mag0) = (self.sign(), self.magnitude());
+        let (sign1, mag1) = (rhs.sign(), rhs.magnitude());
+        match (sign0, sign1) {
+            (Positive, Positive) => IBig::from(mag0 + mag1),
+            (Positive, Negative) => IBig::sub_ubig_refs(mag0, mag1),
+            (Negative, Positive) => IBig::sub_ubig_refs(mag1, mag0),
+            (Negative, Negative) => -IBig::from(mag0 + mag1),
+        }
+    }
+}
+
+impl Sub<IBigRef<'_>> for IBigRef<'_> {
+    type Output = IBig;
+
+    fn sub(self, rhs: IBigRef) -> IBig {
+        let (sign0, mag0) = (self.sign(), self.magnitude());
+        let (sign1, mag1) = (rhs.sign(), rhs.magnitude());
+        match (sign0, sign1) {
+            (Positive, Positive) => IBig::sub_ubig_refs(mag0, mag1),
+            (Positive, Negative) => IBig::from(mag0 + mag1),
+            (Negative, Positive) => -IBig::from(mag0 + mag1),
+            (Negative, Negative) => IBig::sub_ubig_refs(mag1, mag0),
+        }
+    }
+}
+
+impl Mul<IBigRef<'_>> for IBigRef<'_> {
+    type Output = IBig;
+
+    fn mul(self, rhs: IBigRef) -> IBig {
+        IBig::from_sign_magnitude(self.sign() * rhs.sign(), self.magnitude() * rhs.magnitude())
+    }
+}
+
+impl Div<IBigRef<'_>> for IBigRef<'_> {
+    type Output = IBig;
+
+    fn div(self, rhs: IBigRef) -> IBig {
+        // Truncate towards 0.
+        IBig::from_sign_magnitude(self.sign() * rhs.sign(), self.magnitude() / rhs.magnitude())
+    }
+}
+
+impl Rem<IBigRef<'_>> for IBigRef<'_> {
+    type Output = IBig;
+
+    fn rem(self, rhs: IBigRef) -> IBig {
+        // Remainder with truncating division has same sign as lhs.
+        IBig::from_sign_magnitude(self.sign(), self.magnitude() % rhs.magnitude())
+    }
+}
+
+impl DivRem<IBigRef<'_>> for IBigRef<'_> {
+    type OutputDiv = IBig;
+    type OutputRem = IBig;
+
+    fn div_rem(self, rhs: IBigRef) -> (IBig, IBig) {
+        // Truncate towards 0.
+        let (q, r) = self.magnitude().div_rem(rhs.magnitude());
+        (
+            IBig::from_sign_magnitude(self.sign() * rhs.sign(), q),
+            IBig::from_sign_magnitude(self.sign(), r),
+        )
+    }
+}
+
+impl Shl<usize> for IBigRef<'_> {
+    type Output = IBig;
+
+    fn shl(self, rhs: usize) -> IBig {
+        IBig::from_sign_magnitude(self.sign(), self.magnitude() << rhs)
+    }
+}
+
+impl IBig {
+    /// `lhs - rhs` for borrowed magnitudes.
+    fn sub_ubig_refs(lhs: UBigRef, rhs: UBigRef) -> IBig {
+        match (lhs.repr(), rhs.repr()) {
+            (Small(word0), Small(word1)) => IBig::sub_word_word(word0, word1),
+            (Small(word0), Large(words1)) => {
+                -IBig::sub_large_word(Buffer::from_words(words1), word0)
+            }
+            (Large(words0), Small(word1)) => {
+                IBig::sub_large_word(Buffer::from_words(words0), word1)
+            }
+            (Large(words0), Large(words1)) => {
+                if words0.len() >= words1.len() {
+                    IBig::sub_large(Buffer::from_words(words0), words1)
+                } else {
+                    -IBig::sub_large(Buffer::from_words(words1), words0)
+                }
+            }
+        }
+    }
+}
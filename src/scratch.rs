@@ -0,0 +1,173 @@
+//! Reusable scratch memory.
+
+use crate::{
+    error::AllocError,
+    memory::{self, Memory, TempAllocator},
+};
+use alloc::alloc::Layout;
+use core::fmt::{self, Debug, Formatter};
+
+#[cfg(feature = "allocator_api")]
+use alloc::{
+    alloc::{Allocator, Global},
+    boxed::Box,
+};
+
+/// Reusable scratch memory for arithmetic on large numbers.
+///
+/// Multiplication, division and modular exponentiation of large numbers need temporary memory.
+/// The regular operators allocate it for every operation. In hot loops, keep a `Scratch` and
+/// pass it to [UBig::mul_with_scratch](crate::UBig::mul_with_scratch),
+/// [UBig::div_rem_with_scratch](crate::UBig::div_rem_with_scratch) or
+/// [Modulo::pow_with_scratch](crate::modular::Modulo::pow_with_scratch) instead. The memory
+/// grows as needed and is only released when the `Scratch` is dropped. With the `rayon` feature,
+/// multiplications that run on multiple threads also allocate separate memory for each thread
+/// from the allocator of the `Scratch`.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{ubig, Scratch};
+/// let mut scratch = Scratch::new();
+/// let a = ubig!(_0x123456789abcdef0123456789abcdef0);
+/// let mut x = ubig!(1);
+/// for _ in 0..10 {
+///     x = x.mul_with_scratch(&a, &mut scratch);
+/// }
+/// assert_eq!(x, a.pow(10));
+/// ```
+pub struct Scratch {
+    layout: Layout,
+    start: *mut u8,
+    #[cfg(feature = "allocator_api")]
+    allocator: Box<dyn Allocator + Send + Sync>,
+}
+
+// Safe because Scratch owns its memory exclusively and only hands it out through `&mut self`.
+unsafe impl Send for Scratch {}
+unsafe impl Sync for Scratch {}
+
+impl Scratch {
+    /// Create empty scratch memory. Nothing is allocated until it is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::Scratch;
+    /// let scratch = Scratch::new();
+    /// assert_eq!(scratch.capacity(), 0);
+    /// ```
+    pub fn new() -> Scratch {
+        let layout = memory::zero_layout();
+        Scratch {
+            layout,
+            // We should use layout.dangling(), but that is unstable.
+            start: layout.align() as *mut u8,
+            #[cfg(feature = "allocator_api")]
+            allocator: Box::new(Global),
+        }
+    }
+
+    /// Create empty scratch memory that will be allocated from `allocator`.
+    ///
+    /// Requires the `allocator_api` feature and a nightly compiler.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(allocator_api)]
+    /// # use ibig::{ubig, Scratch};
+    /// # use std::alloc::System;
+    /// let mut scratch = Scratch::new_in(System);
+    /// let a = ubig!(_0x123456789abcdef0123456789abcdef0);
+    /// assert_eq!(a.mul_with_scratch(&a, &mut scratch), &a * &a);
+    /// ```
+    #[cfg(feature = "allocator_api")]
+    pub fn new_in<A: Allocator + Send + Sync + 'static>(allocator: A) -> Scratch {
+        let layout = memory::zero_layout();
+        Scratch {
+            layout,
+            start: layout.align() as *mut u8,
+            allocator: Box::new(allocator),
+        }
+    }
+
+    /// Number of bytes currently allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Scratch};
+    /// let mut scratch = Scratch::new();
+    /// let a = ubig!(1) << 20000;
+    /// let _ = a.mul_with_scratch(&a, &mut scratch);
+    /// assert!(scratch.capacity() > 0);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+
+    /// Get memory for `layout`, growing the allocation if necessary.
+    pub(crate) fn memory(&mut self, layout: Layout) -> Memory<'_> {
+        self.try_memory(layout)
+            .unwrap_or_else(|_| memory::panic_out_of_memory())
+    }
+
+    /// Get memory for `layout`, or fail if the allocation can't be grown.
+    pub(crate) fn try_memory(&mut self, layout: Layout) -> Result<Memory<'_>, AllocError> {
+        if layout.size() > self.layout.size() || layout.align() > self.layout.align() {
+            let layout = memory::max_layout(layout, self.layout);
+            // Release the old memory first so that both are never held at once.
+            self.release();
+            self.start = self.temp_allocator().allocate(layout)?;
+            self.layout = layout;
+        }
+        // Safe because the memory is owned by self, which is mutably borrowed for the
+        // lifetime of the result.
+        let memory = unsafe {
+            Memory::from_raw_parts(self.start, self.layout.size(), self.temp_allocator())
+        };
+        Ok(memory)
+    }
+
+    /// Allocator of the memory.
+    fn temp_allocator(&self) -> TempAllocator<'_> {
+        #[cfg(feature = "allocator_api")]
+        let allocator = TempAllocator::new(&*self.allocator);
+        #[cfg(not(feature = "allocator_api"))]
+        let allocator = TempAllocator::global();
+        allocator
+    }
+
+    /// Free the memory.
+    fn release(&mut self) {
+        if self.layout.size() != 0 {
+            // Safe because the memory was allocated with the same layout.
+            unsafe { self.temp_allocator().deallocate(self.start, self.layout) };
+        }
+        let layout = memory::zero_layout();
+        self.layout = layout;
+        self.start = layout.align() as *mut u8;
+    }
+}
+
+impl Default for Scratch {
+    /// Empty scratch memory.
+    fn default() -> Scratch {
+        Scratch::new()
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl Debug for Scratch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Scratch")
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
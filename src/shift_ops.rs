@@ -3,15 +3,17 @@
 use crate::{
     arch::word::Word,
     buffer::Buffer,
+    error::{AllocError, CapacityError},
     ibig::IBig,
+    memory,
     primitive::{double_word, extend_word, split_double_word, WORD_BITS_USIZE},
     shift,
     sign::Sign::*,
     ubig::{Repr::*, UBig},
 };
 use core::{
-    mem,
     ops::{Shl, ShlAssign, Shr, ShrAssign},
+    slice,
 };
 
 macro_rules! impl_shifts {
@@ -34,13 +36,13 @@ macro_rules! impl_shifts {
 
         impl ShlAssign<usize> for $t {
             fn shl_assign(&mut self, rhs: usize) {
-                *self = mem::take(self) << rhs;
+                self.update(|x| x << rhs);
             }
         }
 
         impl ShlAssign<&usize> for $t {
             fn shl_assign(&mut self, rhs: &usize) {
-                *self = mem::take(self) << rhs;
+                self.update(|x| x << rhs);
             }
         }
 
@@ -62,13 +64,13 @@ macro_rules! impl_shifts {
 
         impl ShrAssign<usize> for $t {
             fn shr_assign(&mut self, rhs: usize) {
-                *self = mem::take(self).shr(rhs);
+                self.update(|x| x.shr(rhs));
             }
         }
 
         impl ShrAssign<&usize> for $t {
             fn shr_assign(&mut self, rhs: &usize) {
-                *self = mem::take(self).shr(rhs);
+                self.update(|x| x.shr(rhs));
             }
         }
     };
@@ -141,6 +143,25 @@ impl Shl<usize> for &IBig {
     }
 }
 
+impl IBig {
+    /// Shift left that fails instead of panicking or aborting if the result is too large or memory
+    /// can't be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{error::AllocError, ibig};
+    /// assert_eq!(ibig!(-3).try_shl(2)?, ibig!(-12));
+    /// # Ok::<(), AllocError>(())
+    /// ```
+    pub fn try_shl(&self, rhs: usize) -> Result<IBig, AllocError> {
+        Ok(IBig::from_sign_magnitude(
+            self.sign(),
+            self.magnitude().try_shl(rhs)?,
+        ))
+    }
+}
+
 impl Shr<usize> for IBig {
     type Output = IBig;
 
@@ -172,8 +193,68 @@ impl Shr<usize> for &IBig {
 }
 
 impl UBig {
+    /// Shift left that fails instead of panicking or aborting if the result is too large or memory
+    /// can't be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{error::{AllocError, CapacityError}, ubig, UBig};
+    /// assert_eq!(ubig!(3).try_shl(2)?, ubig!(12));
+    /// assert_eq!(
+    ///     ubig!(3).try_shl(UBig::MAX_BIT_LEN),
+    ///     Err(AllocError::Capacity(CapacityError))
+    /// );
+    /// # Ok::<(), AllocError>(())
+    /// ```
+    pub fn try_shl(&self, rhs: usize) -> Result<UBig, AllocError> {
+        // Check before allocating up to MAX_LEN words.
+        if *self != UBig::from_word(0) {
+            match self.bit_len().checked_add(rhs) {
+                Some(bits) if bits <= UBig::MAX_BIT_LEN => {}
+                _ => return Err(CapacityError.into()),
+            }
+        }
+        match self.repr() {
+            Small(0) => Ok(UBig::from_word(0)),
+            Small(word) => UBig::try_shl_words(slice::from_ref(word), rhs),
+            Large(buffer) => UBig::try_shl_words(buffer, rhs),
+        }
+    }
+
+    /// Shift left into an existing number: `*out = a << rhs`.
+    ///
+    /// Reuses the memory of `out` when it has enough capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBig};
+    /// let mut out = UBig::default();
+    /// UBig::shl_into(&mut out, &ubig!(3), 100);
+    /// assert_eq!(out, ubig!(3) << 100);
+    /// ```
+    pub fn shl_into(out: &mut UBig, a: &UBig, rhs: usize) {
+        let words = a.as_words();
+        if words.is_empty() {
+            out.assign(UBig::from_word(0));
+            return;
+        }
+        let shift_words = rhs / WORD_BITS_USIZE;
+        let shift_bits = (rhs % WORD_BITS_USIZE) as u32;
+        let len = shift_words
+            .checked_add(words.len() + 1)
+            .unwrap_or_else(|| UBig::panic_number_too_large());
+        let mut buffer = out.take_buffer(len);
+        buffer.push_zeros(shift_words);
+        buffer.extend_from_slice(words);
+        let carry = shift::shl_in_place(&mut buffer[shift_words..], shift_bits);
+        buffer.push(carry);
+        *out = UBig::from_reused_buffer(buffer);
+    }
+
     /// Shift left one non-zero `Word` by `rhs` bits.
-    fn shl_word(word: Word, rhs: usize) -> UBig {
+    pub(crate) fn shl_word(word: Word, rhs: usize) -> UBig {
         debug_assert!(word != 0);
 
         if rhs <= WORD_BITS_USIZE {
@@ -211,20 +292,28 @@ impl UBig {
     }
 
     /// Shift left large number of words by `rhs` bits.
-    fn shl_ref_large(words: &[Word], rhs: usize) -> UBig {
+    pub(crate) fn shl_ref_large(words: &[Word], rhs: usize) -> UBig {
+        UBig::try_shl_words(words, rhs).unwrap_or_else(|err| memory::panic_alloc_error(err))
+    }
+
+    /// Shift left words by `rhs` bits, or fail if memory can't be allocated.
+    fn try_shl_words(words: &[Word], rhs: usize) -> Result<UBig, AllocError> {
         let shift_words = rhs / WORD_BITS_USIZE;
         let shift_bits = (rhs % WORD_BITS_USIZE) as u32;
 
-        let mut buffer = Buffer::allocate(shift_words + words.len() + 1);
+        let len = shift_words
+            .checked_add(words.len() + 1)
+            .ok_or(CapacityError)?;
+        let mut buffer = Buffer::try_allocate(len)?;
         buffer.push_zeros(shift_words);
-        buffer.extend(words);
+        buffer.extend_from_slice(words);
         let carry = shift::shl_in_place(&mut buffer[shift_words..], shift_bits);
         buffer.push(carry);
-        buffer.into()
+        UBig::try_from_buffer(buffer)
     }
 
     /// Shift right one `Word` by `rhs` bits.
-    fn shr_word(word: Word, rhs: usize) -> UBig {
+    pub(crate) fn shr_word(word: Word, rhs: usize) -> UBig {
         let word = if rhs < WORD_BITS_USIZE {
             word >> rhs
         } else {
@@ -246,7 +335,7 @@ impl UBig {
     }
 
     /// Shift right large number of words by `rhs` bits.
-    fn shr_large_ref(words: &[Word], rhs: usize) -> UBig {
+    pub(crate) fn shr_large_ref(words: &[Word], rhs: usize) -> UBig {
         let shift_words = rhs / WORD_BITS_USIZE;
         let shift_bits = (rhs % WORD_BITS_USIZE) as u32;
 
@@ -258,7 +347,7 @@ impl UBig {
             &[lo, hi] => UBig::from(double_word(lo, hi) >> shift_bits),
             _ => {
                 let mut buffer = Buffer::allocate(words.len());
-                buffer.extend(words);
+                buffer.extend_from_slice(words);
                 shift::shr_in_place(&mut buffer, shift_bits);
                 buffer.into()
             }
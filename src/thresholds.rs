@@ -0,0 +1,245 @@
+//! Algorithm thresholds.
+
+use crate::{div, mul};
+use core::fmt::{self, Display, Formatter};
+use static_assertions::const_assert;
+
+/// Lengths in [Word](crate::Word)s at which multiplication, division and radix conversion switch
+/// algorithms, and at which they run in parallel.
+///
+/// The defaults were tuned on one machine. To build the library with thresholds tuned for the
+/// host CPU, run the `tune_thresholds` tool from the `dev-tools` directory of the repository.
+/// It measures the crossovers and writes a thresholds file. Then set the environment variable
+/// `IBIG_THRESHOLDS` to the absolute path of the file when building:
+///
+/// ```text
+/// cargo run --release -p dev-tools --bin tune_thresholds -- thresholds.txt
+/// IBIG_THRESHOLDS=/path/to/thresholds.txt cargo build --release
+/// ```
+///
+/// The variable can also be set in the `[env]` section of `.cargo/config.toml`.
+///
+/// The file contains one `name = value` line per threshold, as written by [Display]. Lines
+/// starting with `#` are comments. Thresholds not in the file keep their default values.
+/// Unknown names and invalid thresholds are compile errors.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::ThresholdConfig;
+/// let config = ThresholdConfig::built_in();
+/// assert!(config.is_valid());
+/// assert!(config.to_string().contains("mul_karatsuba = "));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct ThresholdConfig {
+    /// If the smaller factor has at most this many words, use simple multiplication.
+    pub mul_simple: usize,
+    /// If the smaller factor has at most this many words, use Karatsuba multiplication.
+    pub mul_karatsuba: usize,
+    /// If the smaller factor has at most this many words, use Toom-3 multiplication.
+    pub mul_toom_3: usize,
+    /// If the smaller factor has at most this many words, use Toom-4 multiplication.
+    pub mul_toom_4: usize,
+    /// If the smaller factor has at most this many words, use Toom-8 multiplication.
+    /// Otherwise use number-theoretic multiplication.
+    pub mul_toom_8: usize,
+    /// If the divisor or the quotient has at most this many words, use simple division.
+    pub div_simple: usize,
+    /// If the divisor or the quotient has at most this many words, use divide and conquer
+    /// division. Otherwise use Newton's method.
+    pub div_newton: usize,
+    /// Parse numbers in a non-power-of-two radix in chunks of this many words.
+    pub parse_chunk: usize,
+    /// Format numbers in a non-power-of-two radix in chunks of this many words.
+    /// At most 64.
+    pub fmt_chunk: usize,
+    /// With the `rayon` feature, run independent sub-computations on numbers of at least this
+    /// many words in parallel.
+    pub parallel: usize,
+}
+
+/// Maximum value of `fmt_chunk`. Formatting keeps a chunk in a fixed size array.
+pub(crate) const MAX_FMT_CHUNK: usize = 64;
+
+/// Number of thresholds.
+const NUM_THRESHOLDS: usize = 10;
+
+/// Names of the thresholds in the thresholds file, in the order of `ThresholdConfig::to_array`.
+const NAMES: [&str; NUM_THRESHOLDS] = [
+    "mul_simple",
+    "mul_karatsuba",
+    "mul_toom_3",
+    "mul_toom_4",
+    "mul_toom_8",
+    "div_simple",
+    "div_newton",
+    "parse_chunk",
+    "fmt_chunk",
+    "parallel",
+];
+
+/// Thresholds the library is built with.
+const BUILT_IN: ThresholdConfig = include!(concat!(env!("OUT_DIR"), "/thresholds.rs"));
+const_assert!(BUILT_IN.is_valid());
+
+impl ThresholdConfig {
+    /// Default thresholds.
+    pub const DEFAULT: ThresholdConfig = ThresholdConfig {
+        mul_simple: 24,
+        mul_karatsuba: 192,
+        mul_toom_3: 7000,
+        mul_toom_4: 10000,
+        mul_toom_8: 10000,
+        div_simple: 32,
+        div_newton: 85000,
+        parse_chunk: 256,
+        fmt_chunk: 16,
+        parallel: 500,
+    };
+
+    /// Thresholds the library was built with.
+    ///
+    /// These are the defaults, updated from the file in the `IBIG_THRESHOLDS` environment
+    /// variable at build time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ThresholdConfig;
+    /// // Built without IBIG_THRESHOLDS.
+    /// assert_eq!(ThresholdConfig::built_in(), ThresholdConfig::DEFAULT);
+    /// ```
+    pub const fn built_in() -> ThresholdConfig {
+        BUILT_IN
+    }
+
+    /// Are the thresholds valid?
+    ///
+    /// Each algorithm must support the lengths it is used for, and the multiplication
+    /// thresholds must be non-decreasing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ThresholdConfig;
+    /// let mut config = ThresholdConfig::DEFAULT;
+    /// assert!(config.is_valid());
+    /// config.mul_simple = 0;
+    /// assert!(!config.is_valid());
+    /// ```
+    pub const fn is_valid(&self) -> bool {
+        mul::thresholds_valid(self)
+            && div::thresholds_valid(self)
+            && self.parse_chunk >= 1
+            && self.fmt_chunk >= 1
+            && self.fmt_chunk <= MAX_FMT_CHUNK
+    }
+
+    const fn to_array(self) -> [usize; NUM_THRESHOLDS] {
+        [
+            self.mul_simple,
+            self.mul_karatsuba,
+            self.mul_toom_3,
+            self.mul_toom_4,
+            self.mul_toom_8,
+            self.div_simple,
+            self.div_newton,
+            self.parse_chunk,
+            self.fmt_chunk,
+            self.parallel,
+        ]
+    }
+
+    #[cfg(feature = "tune")]
+    const fn from_array(values: [usize; NUM_THRESHOLDS]) -> ThresholdConfig {
+        ThresholdConfig {
+            mul_simple: values[0],
+            mul_karatsuba: values[1],
+            mul_toom_3: values[2],
+            mul_toom_4: values[3],
+            mul_toom_8: values[4],
+            div_simple: values[5],
+            div_newton: values[6],
+            parse_chunk: values[7],
+            fmt_chunk: values[8],
+            parallel: values[9],
+        }
+    }
+}
+
+impl Default for ThresholdConfig {
+    /// Default thresholds.
+    fn default() -> ThresholdConfig {
+        ThresholdConfig::DEFAULT
+    }
+}
+
+impl Display for ThresholdConfig {
+    /// Format as a thresholds file.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (name, value) in NAMES.iter().zip(self.to_array().iter()) {
+            writeln!(f, "{} = {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Thresholds in effect.
+#[cfg(not(feature = "tune"))]
+#[inline]
+pub(crate) fn current() -> ThresholdConfig {
+    BUILT_IN
+}
+
+#[cfg(feature = "tune")]
+mod tune {
+    use super::*;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    const BUILT_IN_ARRAY: [usize; NUM_THRESHOLDS] = BUILT_IN.to_array();
+
+    static CURRENT: [AtomicUsize; NUM_THRESHOLDS] = [
+        AtomicUsize::new(BUILT_IN_ARRAY[0]),
+        AtomicUsize::new(BUILT_IN_ARRAY[1]),
+        AtomicUsize::new(BUILT_IN_ARRAY[2]),
+        AtomicUsize::new(BUILT_IN_ARRAY[3]),
+        AtomicUsize::new(BUILT_IN_ARRAY[4]),
+        AtomicUsize::new(BUILT_IN_ARRAY[5]),
+        AtomicUsize::new(BUILT_IN_ARRAY[6]),
+        AtomicUsize::new(BUILT_IN_ARRAY[7]),
+        AtomicUsize::new(BUILT_IN_ARRAY[8]),
+        AtomicUsize::new(BUILT_IN_ARRAY[9]),
+    ];
+
+    /// Thresholds in effect.
+    pub(crate) fn current() -> ThresholdConfig {
+        let mut values = [0; NUM_THRESHOLDS];
+        for (value, current) in values.iter_mut().zip(CURRENT.iter()) {
+            *value = current.load(Ordering::Relaxed);
+        }
+        ThresholdConfig::from_array(values)
+    }
+
+    impl ThresholdConfig {
+        /// Use these thresholds from now on, to measure crossovers in the `tune_thresholds` tool.
+        ///
+        /// Only available with the internal `tune` feature, not covered by semver. Must not be
+        /// called while other threads do arithmetic.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the thresholds are not valid.
+        #[doc(hidden)]
+        pub fn set_current_for_tuning(&self) {
+            assert!(self.is_valid(), "invalid thresholds");
+            for (value, current) in self.to_array().iter().zip(CURRENT.iter()) {
+                current.store(*value, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tune")]
+pub(crate) use tune::current;
@@ -0,0 +1,105 @@
+//! Euler's totient and Carmichael's function.
+
+use crate::{
+    factor::{self, Factorization},
+    ubig::UBig,
+};
+
+impl UBig {
+    /// Euler's totient function: the number of integers in `1..=n` coprime to `n`.
+    ///
+    /// This is the size of the multiplicative group of integers modulo `n`.
+    ///
+    /// Requires factorizing `n`, which may be slow if `n` has more than one large prime factor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(1).euler_phi(), ubig!(1));
+    /// assert_eq!(ubig!(36).euler_phi(), ubig!(12));
+    /// assert_eq!(ubig!(101).euler_phi(), ubig!(100));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn euler_phi(&self) -> UBig {
+        if *self == UBig::from_word(0) {
+            panic!("euler_phi(0)")
+        }
+        factor::product(&euler_phi_factors(&factor::factorize(self)))
+    }
+
+    /// Carmichael's function: the smallest `m > 0` such that `a^m = 1 (mod n)` for every `a`
+    /// coprime to `n`.
+    ///
+    /// This is the exponent of the multiplicative group of integers modulo `n`.
+    ///
+    /// Requires factorizing `n`, which may be slow if `n` has more than one large prime factor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(1).carmichael_lambda(), ubig!(1));
+    /// assert_eq!(ubig!(36).carmichael_lambda(), ubig!(6));
+    /// assert_eq!(ubig!(101).carmichael_lambda(), ubig!(100));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn carmichael_lambda(&self) -> UBig {
+        if *self == UBig::from_word(0) {
+            panic!("carmichael_lambda(0)")
+        }
+        factor::product(&carmichael_lambda_factors(&factor::factorize(self)))
+    }
+}
+
+/// Factorization of phi(n) given the factorization of n.
+pub(crate) fn euler_phi_factors(n_factors: &[(UBig, usize)]) -> Factorization {
+    // phi(p^k) = p^(k-1) * (p-1)
+    let mut factors = Factorization::new();
+    for (p, k) in n_factors {
+        if *k > 1 {
+            factors.push((p.clone(), k - 1));
+        }
+        factors.extend(factor::factorize(&(p - UBig::from_word(1))));
+    }
+    factors.sort();
+    factor::merge_sorted(&mut factors);
+    factors
+}
+
+/// Factorization of lambda(n) given the factorization of n.
+pub(crate) fn carmichael_lambda_factors(n_factors: &[(UBig, usize)]) -> Factorization {
+    // lambda(2^k) = 2^(k-2) for k >= 3
+    // lambda(p^k) = phi(p^k) otherwise
+    // lambda(n) = lcm of lambda(p^k)
+    let mut factors = Factorization::new();
+    for (p, k) in n_factors {
+        if *p == UBig::from_word(2) {
+            let exponent = if *k >= 3 { k - 2 } else { k - 1 };
+            if exponent != 0 {
+                factors.push((p.clone(), exponent));
+            }
+        } else {
+            if *k > 1 {
+                factors.push((p.clone(), k - 1));
+            }
+            factors.extend(factor::factorize(&(p - UBig::from_word(1))));
+        }
+    }
+    factors.sort();
+    factors.dedup_by(|next, prev| {
+        if next.0 == prev.0 {
+            prev.1 = prev.1.max(next.1);
+            true
+        } else {
+            false
+        }
+    });
+    factors
+}
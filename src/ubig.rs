@@ -2,12 +2,20 @@
 
 use self::Repr::*;
 use crate::{
+    allocator::WordAllocator,
     arch::{ntt, word::Word},
     buffer::Buffer,
+    error::{AllocError, CapacityError},
     math,
     primitive::WORD_BITS_USIZE,
 };
-use core::slice;
+use core::{
+    hash::{Hash, Hasher},
+    mem, slice,
+};
+
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Allocator;
 
 /// Internal representation of UBig.
 #[derive(Debug, Eq, Hash, PartialEq)]
@@ -39,23 +47,119 @@ pub(crate) enum Repr {
 /// assert_eq!(b, d);
 /// # Ok::<(), ParseError>(())
 /// ```
-#[derive(Eq, Hash, PartialEq)]
-pub struct UBig(Repr);
+pub struct UBig {
+    repr: Repr,
+    /// The allocator for when a `Small` number grows. `Large` numbers use the allocator of their
+    /// buffer.
+    allocator: WordAllocator,
+}
 
 impl UBig {
     /// Construct from one word.
     pub(crate) fn from_word(word: Word) -> UBig {
-        UBig(Small(word))
+        UBig::from_word_in(word, WordAllocator::global())
+    }
+
+    /// Construct from one word, with memory for larger values from `allocator`.
+    fn from_word_in(word: Word, allocator: WordAllocator) -> UBig {
+        UBig {
+            repr: Small(word),
+            allocator,
+        }
+    }
+
+    /// Construct from a buffer of length at least 2, with no leading zero and compact capacity.
+    fn from_large(buffer: Buffer) -> UBig {
+        UBig {
+            repr: Large(buffer),
+            allocator: WordAllocator::global(),
+        }
+    }
+
+    /// Zero, with memory for larger values from `allocator`.
+    ///
+    /// The number keeps using `allocator` as it is changed in place: by compound assignment such
+    /// as `+=`, `*=` or `<<=`, by [set_bit](UBig::set_bit), [add_mul](UBig::add_mul) and
+    /// `clone_from`, and as the destination of [add_into](UBig::add_into) and the other `_into`
+    /// operations. Clones use the same allocator. Other operations allocate their results from
+    /// the global allocator, or may reuse the memory of numbers passed by value.
+    ///
+    /// Requires the `allocator_api` feature and a nightly compiler. The feature makes every number
+    /// larger, including small ones and those in the global allocator: a `UBig` and its heap
+    /// words each hold an `Option<Arc<dyn Allocator>>`. On 64-bit targets `UBig` grows from 24 to
+    /// 56 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(allocator_api)]
+    /// # use ibig::{ubig, UBig};
+    /// # use std::alloc::System;
+    /// let mut a = UBig::new_in(System);
+    /// a += ubig!(1) << 1000;
+    /// a *= ubig!(1) << 1000;
+    /// assert_eq!(a, ubig!(1) << 2000);
+    /// ```
+    #[cfg(feature = "allocator_api")]
+    pub fn new_in<A: Allocator + Send + Sync + 'static>(allocator: A) -> UBig {
+        UBig::from_word_in(0, WordAllocator::new(allocator))
+    }
+
+    /// The allocator for the memory of the number.
+    pub(crate) fn allocator(&self) -> &WordAllocator {
+        match &self.repr {
+            Small(_) => &self.allocator,
+            Large(buffer) => buffer.allocator(),
+        }
+    }
+
+    /// The same number in memory from `allocator`.
+    pub(crate) fn into_allocator(self, allocator: WordAllocator) -> UBig {
+        if self.allocator().same_as(&allocator) {
+            return self;
+        }
+        match self.repr {
+            Small(word) => UBig::from_word_in(word, allocator),
+            Large(buffer) => {
+                let mut new_buffer = Buffer::allocate_in(buffer.len(), allocator);
+                new_buffer.clone_from(&buffer);
+                UBig::from_large(new_buffer)
+            }
+        }
+    }
+
+    /// Set to `value`, keeping the allocator of `self`.
+    pub(crate) fn assign(&mut self, value: UBig) {
+        let allocator = self.allocator().clone();
+        *self = value.into_allocator(allocator);
+    }
+
+    /// Replace with `f(self)`, keeping the allocator of `self`.
+    pub(crate) fn update<F>(&mut self, f: F)
+    where
+        F: FnOnce(UBig) -> UBig,
+    {
+        let allocator = self.allocator().clone();
+        *self = f(mem::take(self)).into_allocator(allocator);
+    }
+
+    /// Construct from little-endian words without leading zeros.
+    pub(crate) fn from_words(words: &[Word]) -> UBig {
+        match *words {
+            [] => UBig::from_word(0),
+            [word] => UBig::from_word(word),
+            _ => Buffer::from_words(words).into(),
+        }
     }
 
     /// Get the representation of UBig.
     pub(crate) fn repr(&self) -> &Repr {
-        &self.0
+        &self.repr
     }
 
     /// Convert into representation.
     pub(crate) fn into_repr(self) -> Repr {
-        self.0
+        self.repr
     }
 
     /// Length in Words.
@@ -66,6 +170,37 @@ impl UBig {
         }
     }
 
+    /// Take the buffer of `self` for reuse in a result of up to `num_words` words.
+    ///
+    /// `self` is left as 0. The buffer is in memory from the allocator of `self`, and keeps its
+    /// capacity if it is larger than needed. Convert the result back with `from_reused_buffer`.
+    pub(crate) fn take_buffer(&mut self, num_words: usize) -> Buffer {
+        let UBig { repr, allocator } = mem::take(self);
+        match repr {
+            Small(_) => Buffer::allocate_in(num_words, allocator),
+            Large(buffer) => buffer.reuse(num_words),
+        }
+    }
+
+    /// Take the words of `self` in a buffer with capacity for at least `num_words` words.
+    ///
+    /// `self` is left as 0. Reuses the buffer of `self` if it is large, and otherwise allocates
+    /// from the allocator of `self`.
+    pub(crate) fn take_words(&mut self, num_words: usize) -> Buffer {
+        let UBig { repr, allocator } = mem::take(self);
+        match repr {
+            Small(word) => {
+                let mut buffer = Buffer::allocate_in(num_words.max(1), allocator);
+                buffer.push(word);
+                buffer
+            }
+            Large(mut buffer) => {
+                buffer.ensure_capacity(num_words);
+                buffer
+            }
+        }
+    }
+
     /// Representation in Words.
     pub(crate) fn as_words(&self) -> &[Word] {
         match self.repr() {
@@ -109,24 +244,88 @@ impl UBig {
     pub(crate) fn panic_number_too_large() -> ! {
         panic!("number too large, maximum is {} bits", UBig::MAX_BIT_LEN)
     }
+
+    /// Clone, or fail if memory can't be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{error::AllocError, ubig};
+    /// let a = ubig!(1) << 1000;
+    /// assert_eq!(a.try_clone()?, a);
+    /// # Ok::<(), AllocError>(())
+    /// ```
+    pub fn try_clone(&self) -> Result<UBig, AllocError> {
+        match self.repr() {
+            Small(x) => Ok(UBig::from_word_in(*x, self.allocator.clone())),
+            Large(buffer) => Ok(UBig::from_large(buffer.try_clone()?)),
+        }
+    }
+
+    /// Convert a `Buffer` to `UBig`, or fail if it is too large or memory can't be allocated.
+    ///
+    /// Same as `From<Buffer>` otherwise.
+    pub(crate) fn try_from_buffer(mut buffer: Buffer) -> Result<UBig, AllocError> {
+        buffer.pop_leading_zeros();
+
+        match buffer.len() {
+            0 => Ok(UBig::from_word_in(0, buffer.allocator().clone())),
+            1 => Ok(UBig::from_word_in(buffer[0], buffer.allocator().clone())),
+            _ if buffer.len() > UBig::MAX_LEN => Err(CapacityError.into()),
+            _ => {
+                buffer.try_shrink()?;
+                Ok(UBig::from_large(buffer))
+            }
+        }
+    }
+
+    /// Convert a `Buffer` from `take_buffer` to `UBig`, keeping its capacity for the next result.
+    ///
+    /// Same as `From<Buffer>` otherwise.
+    pub(crate) fn from_reused_buffer(mut buffer: Buffer) -> UBig {
+        buffer.pop_leading_zeros();
+
+        match buffer.len() {
+            0 => UBig::from_word_in(0, buffer.allocator().clone()),
+            1 => UBig::from_word_in(buffer[0], buffer.allocator().clone()),
+            _ if buffer.len() > UBig::MAX_LEN => UBig::panic_number_too_large(),
+            _ => UBig::from_large(buffer),
+        }
+    }
 }
 
 impl Clone for UBig {
     fn clone(&self) -> UBig {
         match self.repr() {
-            Small(x) => UBig(Small(*x)),
-            Large(buffer) => UBig(Large(buffer.clone())),
+            Small(x) => UBig::from_word_in(*x, self.allocator.clone()),
+            Large(buffer) => UBig::from_large(buffer.clone()),
         }
     }
 
+    /// Keeps the allocator of `self`.
     fn clone_from(&mut self, source: &UBig) {
-        if let Large(buffer) = &mut self.0 {
-            if let Large(source_buffer) = source.repr() {
+        match source.repr() {
+            Small(x) => self.assign(UBig::from_word(*x)),
+            Large(source_buffer) => {
+                let mut buffer = self.take_buffer(source_buffer.len());
                 buffer.resizing_clone_from(source_buffer);
-                return;
+                *self = UBig::from_large(buffer);
             }
         }
-        *self = source.clone();
+    }
+}
+
+impl PartialEq for UBig {
+    fn eq(&self, other: &UBig) -> bool {
+        self.repr == other.repr
+    }
+}
+
+impl Eq for UBig {}
+
+impl Hash for UBig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.repr.hash(state);
     }
 }
 
@@ -139,12 +338,12 @@ impl From<Buffer> for UBig {
         buffer.pop_leading_zeros();
 
         match buffer.len() {
-            0 => UBig::from_word(0),
-            1 => UBig::from_word(buffer[0]),
+            0 => UBig::from_word_in(0, buffer.allocator().clone()),
+            1 => UBig::from_word_in(buffer[0], buffer.allocator().clone()),
             _ if buffer.len() > UBig::MAX_LEN => UBig::panic_number_too_large(),
             _ => {
                 buffer.shrink();
-                UBig(Large(buffer))
+                UBig::from_large(buffer)
             }
         }
     }
@@ -153,6 +352,7 @@ impl From<Buffer> for UBig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::buffer::INLINE_CAPACITY;
 
     /// Current capacity in Words.
     fn capacity(x: &UBig) -> usize {
@@ -194,7 +394,14 @@ mod tests {
         buf.push(3);
         buf.push(4);
         let num: UBig = buf.into();
-        assert_eq!(capacity(&num), 6);
+        assert_eq!(
+            capacity(&num),
+            if INLINE_CAPACITY == 4 {
+                INLINE_CAPACITY
+            } else {
+                6
+            }
+        );
     }
 
     #[test]
@@ -0,0 +1,373 @@
+//! Borrowed views of big integers.
+
+use crate::{
+    arch::word::Word,
+    cmp,
+    ibig::IBig,
+    math,
+    primitive::WORD_BITS_USIZE,
+    sign::Sign::{self, *},
+    ubig::UBig,
+};
+use core::cmp::Ordering;
+
+/// Borrowed unsigned big integer.
+///
+/// A view of a number stored elsewhere as a little-endian slice of [Word]s, for instance in a
+/// memory-mapped table. Creating it does not allocate. Arithmetic operators produce an owned
+/// [UBig].
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{ubig, UBigRef, Word};
+/// let words: [Word; 3] = [1, 2, 0];
+/// let a = UBigRef::from_words(&words);
+/// let b = UBigRef::from_words(&words[..1]);
+/// assert_eq!(a.bit_len(), Word::MAX.count_ones() as usize + 2);
+/// assert_eq!(a + b, a.to_owned() + ubig!(1));
+/// ```
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct UBigRef<'a>(&'a [Word]);
+
+/// Borrowed signed big integer.
+///
+/// A sign and a [UBigRef] magnitude. Creating it does not allocate. Arithmetic operators produce
+/// an owned [IBig].
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{ibig, IBigRef, Word};
+/// let words: [Word; 1] = [5];
+/// let a = IBigRef::from_words(true, &words);
+/// assert_eq!(a.to_owned(), ibig!(-5));
+/// assert_eq!(a * a, ibig!(25));
+/// ```
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct IBigRef<'a> {
+    sign: Sign,
+    magnitude: UBigRef<'a>,
+}
+
+/// Representation of a [UBigRef], analogous to the representation of [UBig].
+pub(crate) enum RefRepr<'a> {
+    /// A number that fits in a single Word.
+    Small(Word),
+    /// At least 2 words, no leading zero.
+    Large(&'a [Word]),
+}
+
+impl<'a> UBigRef<'a> {
+    /// View little-endian `words` as a number.
+    ///
+    /// Leading zero words are allowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBigRef, Word};
+    /// let words: [Word; 4] = [7, 0, 0, 0];
+    /// assert_eq!(UBigRef::from_words(&words), ubig!(7));
+    /// assert_eq!(UBigRef::from_words(&words).as_words(), &[7]);
+    /// ```
+    pub fn from_words(words: &'a [Word]) -> UBigRef<'a> {
+        let len = words.len() - words.iter().rev().take_while(|word| **word == 0).count();
+        UBigRef(&words[..len])
+    }
+
+    /// The words of the number in little-endian order, without leading zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBigRef};
+    /// let a = ubig!(5);
+    /// assert_eq!(UBigRef::from(&a).as_words(), &[5]);
+    /// assert!(UBigRef::from(&ubig!(0)).as_words().is_empty());
+    /// ```
+    pub fn as_words(&self) -> &'a [Word] {
+        self.0
+    }
+
+    /// Copy into an owned [UBig].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBigRef};
+    /// let a = ubig!(1) << 100;
+    /// assert_eq!(UBigRef::from(&a).to_owned(), a);
+    /// ```
+    pub fn to_owned(self) -> UBig {
+        UBig::from_words(self.0)
+    }
+
+    /// Returns true if the `n`-th bit is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBigRef};
+    /// let a = ubig!(0b10010);
+    /// assert_eq!(UBigRef::from(&a).bit(1), true);
+    /// assert_eq!(UBigRef::from(&a).bit(100), false);
+    /// ```
+    pub fn bit(&self, n: usize) -> bool {
+        let idx = n / WORD_BITS_USIZE;
+        idx < self.0.len() && self.0[idx] & 1 << (n % WORD_BITS_USIZE) != 0
+    }
+
+    /// Bit length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBigRef};
+    /// assert_eq!(UBigRef::from(&ubig!(17)).bit_len(), 5);
+    /// assert_eq!(UBigRef::from(&ubig!(0)).bit_len(), 0);
+    /// ```
+    pub fn bit_len(&self) -> usize {
+        match self.0.last() {
+            None => 0,
+            Some(top) => (self.0.len() - 1) * WORD_BITS_USIZE + math::bit_len(*top) as usize,
+        }
+    }
+
+    /// Returns the number of trailing zeros in the binary representation, or `None` for 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBigRef};
+    /// assert_eq!(UBigRef::from(&ubig!(48)).trailing_zeros(), Some(4));
+    /// assert_eq!(UBigRef::from(&ubig!(0)).trailing_zeros(), None);
+    /// ```
+    pub fn trailing_zeros(&self) -> Option<usize> {
+        if self.0.is_empty() {
+            None
+        } else {
+            Some(UBig::trailing_zeros_large(self.0))
+        }
+    }
+
+    /// True if the number is a power of 2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, UBigRef};
+    /// assert_eq!(UBigRef::from(&ubig!(8)).is_power_of_two(), true);
+    /// assert_eq!(UBigRef::from(&ubig!(0)).is_power_of_two(), false);
+    /// ```
+    pub fn is_power_of_two(&self) -> bool {
+        !self.0.is_empty() && UBig::is_power_of_two_large(self.0)
+    }
+
+    /// Representation as a single word or a large number.
+    pub(crate) fn repr(&self) -> RefRepr<'a> {
+        match *self.0 {
+            [] => RefRepr::Small(0),
+            [word] => RefRepr::Small(word),
+            _ => RefRepr::Large(self.0),
+        }
+    }
+}
+
+impl<'a> IBigRef<'a> {
+    /// View little-endian `words` as the magnitude of a number, negative if `negative` is true.
+    ///
+    /// Leading zero words are allowed. Zero is never negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, IBigRef, Word};
+    /// let words: [Word; 2] = [7, 0];
+    /// assert_eq!(IBigRef::from_words(true, &words), ibig!(-7));
+    /// assert!(!IBigRef::from_words(true, &words[1..]).is_negative());
+    /// ```
+    pub fn from_words(negative: bool, words: &'a [Word]) -> IBigRef<'a> {
+        let sign = if negative { Negative } else { Positive };
+        IBigRef::from_sign_magnitude(sign, UBigRef::from_words(words))
+    }
+
+    pub(crate) fn from_sign_magnitude(mut sign: Sign, magnitude: UBigRef<'a>) -> IBigRef<'a> {
+        if magnitude.0.is_empty() {
+            sign = Positive;
+        }
+        IBigRef { sign, magnitude }
+    }
+
+    pub(crate) fn sign(&self) -> Sign {
+        self.sign
+    }
+
+    /// The absolute value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, ubig, IBigRef};
+    /// let a = ibig!(-5);
+    /// assert_eq!(IBigRef::from(&a).magnitude(), ubig!(5));
+    /// ```
+    pub fn magnitude(&self) -> UBigRef<'a> {
+        self.magnitude
+    }
+
+    /// Is the number negative?
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, IBigRef};
+    /// assert!(IBigRef::from(&ibig!(-5)).is_negative());
+    /// assert!(!IBigRef::from(&ibig!(0)).is_negative());
+    /// ```
+    pub fn is_negative(&self) -> bool {
+        self.sign == Negative
+    }
+
+    /// Copy into an owned [IBig].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, IBigRef};
+    /// let a = ibig!(-1) << 100;
+    /// assert_eq!(IBigRef::from(&a).to_owned(), a);
+    /// ```
+    pub fn to_owned(self) -> IBig {
+        IBig::from_sign_magnitude(self.sign, self.magnitude.to_owned())
+    }
+
+    /// Returns the number of trailing zeros in the two's complement binary representation, or
+    /// `None` for 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, IBigRef};
+    /// assert_eq!(IBigRef::from(&ibig!(-48)).trailing_zeros(), Some(4));
+    /// ```
+    pub fn trailing_zeros(&self) -> Option<usize> {
+        self.magnitude.trailing_zeros()
+    }
+}
+
+impl<'a> From<&'a UBig> for UBigRef<'a> {
+    fn from(x: &'a UBig) -> UBigRef<'a> {
+        UBigRef(x.as_words())
+    }
+}
+
+impl<'a> From<&'a IBig> for IBigRef<'a> {
+    fn from(x: &'a IBig) -> IBigRef<'a> {
+        IBigRef {
+            sign: x.sign(),
+            magnitude: UBigRef::from(x.magnitude()),
+        }
+    }
+}
+
+impl<'a> From<UBigRef<'a>> for IBigRef<'a> {
+    fn from(x: UBigRef<'a>) -> IBigRef<'a> {
+        IBigRef {
+            sign: Positive,
+            magnitude: x,
+        }
+    }
+}
+
+impl From<UBigRef<'_>> for UBig {
+    fn from(x: UBigRef) -> UBig {
+        x.to_owned()
+    }
+}
+
+impl From<IBigRef<'_>> for IBig {
+    fn from(x: IBigRef) -> IBig {
+        x.to_owned()
+    }
+}
+
+impl Ord for UBigRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .len()
+            .cmp(&other.0.len())
+            .then_with(|| cmp::cmp_same_len(self.0, other.0))
+    }
+}
+
+impl PartialOrd for UBigRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IBigRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.sign, other.sign) {
+            (Positive, Positive) => self.magnitude.cmp(&other.magnitude),
+            (Positive, Negative) => Ordering::Greater,
+            (Negative, Positive) => Ordering::Less,
+            (Negative, Negative) => other.magnitude.cmp(&self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for IBigRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<UBig> for UBigRef<'_> {
+    fn eq(&self, other: &UBig) -> bool {
+        *self == UBigRef::from(other)
+    }
+}
+
+impl PartialEq<UBigRef<'_>> for UBig {
+    fn eq(&self, other: &UBigRef) -> bool {
+        UBigRef::from(self) == *other
+    }
+}
+
+impl PartialEq<IBig> for IBigRef<'_> {
+    fn eq(&self, other: &IBig) -> bool {
+        *self == IBigRef::from(other)
+    }
+}
+
+impl PartialEq<IBigRef<'_>> for IBig {
+    fn eq(&self, other: &IBigRef) -> bool {
+        IBigRef::from(self) == *other
+    }
+}
+
+impl PartialOrd<UBig> for UBigRef<'_> {
+    fn partial_cmp(&self, other: &UBig) -> Option<Ordering> {
+        Some(self.cmp(&UBigRef::from(other)))
+    }
+}
+
+impl PartialOrd<UBigRef<'_>> for UBig {
+    fn partial_cmp(&self, other: &UBigRef) -> Option<Ordering> {
+        Some(UBigRef::from(self).cmp(other))
+    }
+}
+
+impl PartialOrd<IBig> for IBigRef<'_> {
+    fn partial_cmp(&self, other: &IBig) -> Option<Ordering> {
+        Some(self.cmp(&IBigRef::from(other)))
+    }
+}
+
+impl PartialOrd<IBigRef<'_>> for IBig {
+    fn partial_cmp(&self, other: &IBigRef) -> Option<Ordering> {
+        Some(IBigRef::from(self).cmp(other))
+    }
+}
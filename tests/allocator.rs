@@ -0,0 +1,210 @@
+#![feature(allocator_api)]
+
+use ibig::{ibig, ubig, IBig, Scratch, UBig};
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Allocator that counts its allocations and the bytes currently allocated from it.
+#[derive(Clone, Default)]
+struct Counting(Arc<Counts>);
+
+#[derive(Default)]
+struct Counts {
+    allocations: AtomicUsize,
+    allocated: AtomicUsize,
+}
+
+impl Counting {
+    fn allocations(&self) -> usize {
+        self.0.allocations.load(Ordering::SeqCst)
+    }
+
+    fn allocated(&self) -> usize {
+        self.0.allocated.load(Ordering::SeqCst)
+    }
+}
+
+unsafe impl Allocator for Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = Global.allocate(layout)?;
+        self.0.allocations.fetch_add(1, Ordering::SeqCst);
+        self.0.allocated.fetch_add(layout.size(), Ordering::SeqCst);
+        Ok(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.allocated.fetch_sub(layout.size(), Ordering::SeqCst);
+        Global.deallocate(ptr, layout);
+    }
+}
+
+#[test]
+fn test_ubig_in_place() {
+    let counting = Counting::default();
+    let mut a = UBig::new_in(counting.clone());
+    assert_eq!(counting.allocated(), 0);
+
+    a += ubig!(1) << 1000;
+    assert!(counting.allocated() > 0);
+    a *= ubig!(3).pow(1000);
+    a <<= 100;
+    a >>= 50;
+    a.set_bit(5000);
+    a.clear_bit(5000);
+    assert_eq!(a, (ubig!(3).pow(1000) << 1050));
+
+    let allocated = counting.allocated();
+    let b = &a + &a;
+    assert_eq!(counting.allocated(), allocated);
+    let c = a.clone();
+    assert!(counting.allocated() > allocated);
+    drop(c);
+    assert_eq!(counting.allocated(), allocated);
+
+    // Small values keep the allocator for when they grow again.
+    a -= a.clone();
+    assert_eq!(a, ubig!(0));
+    assert_eq!(counting.allocated(), 0);
+    a += &b;
+    assert!(counting.allocated() > 0);
+    a /= ubig!(1) << 1000;
+    a %= ubig!(7).pow(200);
+    a.clone_from(&b);
+    assert_eq!(a, b);
+    assert!(counting.allocated() > 0);
+
+    drop(a);
+    assert_eq!(counting.allocated(), 0);
+}
+
+#[test]
+fn test_ubig_into() {
+    let counting = Counting::default();
+    let mut out = UBig::new_in(counting.clone());
+    let x = ubig!(3).pow(1000);
+    let y = ubig!(5).pow(1000);
+
+    let mut scratch = Scratch::new();
+    UBig::mul_into(&mut out, &x, &y, &mut scratch);
+    assert_eq!(out, &x * &y);
+    assert!(counting.allocated() > 0);
+    UBig::add_into(&mut out, &ubig!(2), &ubig!(3));
+    assert_eq!(out, ubig!(5));
+    UBig::shl_into(&mut out, &x, 100);
+    assert_eq!(out, &x << 100);
+    assert!(counting.allocated() > 0);
+    out.add_mul(&x, &y);
+    assert_eq!(out, (&x << 100) + &x * &y);
+
+    let mut r = UBig::new_in(counting.clone());
+    UBig::div_rem_into(&mut out, &mut r, &(&x * &y), &x, &mut scratch);
+    assert_eq!(out, y);
+    assert_eq!(r, ubig!(0));
+
+    drop(out);
+    drop(r);
+    assert_eq!(counting.allocated(), 0);
+}
+
+#[test]
+fn test_ibig_in_place() {
+    let counting = Counting::default();
+    let mut a = IBig::new_in(counting.clone());
+
+    a -= ibig!(1) << 1000;
+    assert!(counting.allocated() > 0);
+    a *= ibig!(-3).pow(1001);
+    a.sub_mul(&ibig!(5), &(ibig!(1) << 2000));
+    assert_eq!(a, (ibig!(3).pow(1001) << 1000) - (ibig!(5) << 2000));
+    let b = a.clone();
+    a.clone_from(&ibig!(-7));
+    a *= &b;
+    assert_eq!(a, &b * ibig!(-7));
+
+    let allocated = counting.allocated();
+    drop(b);
+    assert!(counting.allocated() < allocated);
+    drop(a);
+    assert_eq!(counting.allocated(), 0);
+}
+
+#[test]
+fn test_into_no_allocations_when_warm() {
+    let counting = Counting::default();
+    let mut scratch = Scratch::new_in(counting.clone());
+    let mut product = UBig::new_in(counting.clone());
+    let mut q = UBig::new_in(counting.clone());
+    let mut r = UBig::new_in(counting.clone());
+    let a = ubig!(3).pow(20000);
+    let b = ubig!(7).pow(10000);
+    let c = ubig!(5).pow(5000);
+
+    UBig::mul_into(&mut product, &a, &b, &mut scratch);
+    UBig::div_rem_into(&mut q, &mut r, &product, &c, &mut scratch);
+    let allocations = counting.allocations();
+    assert!(allocations > 0);
+    for _ in 0..5 {
+        UBig::mul_into(&mut product, &a, &b, &mut scratch);
+        UBig::div_rem_into(&mut q, &mut r, &product, &c, &mut scratch);
+    }
+    assert_eq!(counting.allocations(), allocations);
+    assert_eq!(r, &a * &b % &c);
+}
+
+#[test]
+fn test_into_no_allocations_when_results_shrink() {
+    let counting = Counting::default();
+    let mut scratch = Scratch::new_in(counting.clone());
+    let mut out = UBig::new_in(counting.clone());
+    let mut q = UBig::new_in(counting.clone());
+    let mut r = UBig::new_in(counting.clone());
+    let a = ubig!(3).pow(20000);
+    let b = ubig!(7).pow(10000);
+    let c = ubig!(5).pow(100);
+    let d = ubig!(11).pow(5000);
+
+    let mut run = |out: &mut UBig, q: &mut UBig, r: &mut UBig| {
+        UBig::mul_into(out, &a, &b, &mut scratch);
+        UBig::div_rem_into(q, r, out, &c, &mut scratch);
+        UBig::div_rem_into(q, r, out, &d, &mut scratch);
+        UBig::mul_into(out, &c, &c, &mut scratch);
+        assert_eq!(*out, &c * &c);
+        UBig::shl_into(out, &c, 1000);
+        UBig::add_into(out, &c, &b);
+        UBig::sub_into(out, &b, &c);
+        assert_eq!(*out, &b - &c);
+    };
+    run(&mut out, &mut q, &mut r);
+    let allocations = counting.allocations();
+    for _ in 0..5 {
+        run(&mut out, &mut q, &mut r);
+    }
+    assert_eq!(counting.allocations(), allocations);
+    assert_eq!(r, &a * &b % &d);
+}
+
+#[test]
+fn test_add_sub_mul_no_allocations_when_warm() {
+    let counting = Counting::default();
+    let mut scratch = Scratch::new_in(counting.clone());
+    let mut acc = UBig::new_in(counting.clone());
+    acc += ubig!(3).pow(40000);
+    let a = ubig!(5).pow(5000);
+    let b = ubig!(7).pow(5000);
+
+    acc.add_mul_with_scratch(&a, &b, &mut scratch);
+    acc.sub_mul_with_scratch(&a, &b, &mut scratch);
+    let allocations = counting.allocations();
+    for _ in 0..5 {
+        acc.add_mul_with_scratch(&a, &b, &mut scratch);
+        acc.sub_mul_with_scratch(&a, &b, &mut scratch);
+    }
+    assert_eq!(counting.allocations(), allocations);
+    assert_eq!(acc, ubig!(3).pow(40000));
+}
@@ -0,0 +1,125 @@
+use ibig::{ibig, ops::DivRem, ubig, IBig, IBigRef, UBig, UBigRef, Word};
+
+fn test_numbers() -> Vec<UBig> {
+    vec![
+        ubig!(0),
+        ubig!(1),
+        ubig!(12345),
+        UBig::from(Word::MAX),
+        ubig!(1) << 64,
+        ubig!(1) << 500,
+        (ubig!(1) << 500) - ubig!(1),
+        ubig!(3).pow(300),
+        ubig!(7).pow(2000),
+    ]
+}
+
+#[test]
+fn test_from_words() {
+    let words: [Word; 4] = [3, 0, 5, 0];
+    let a = UBigRef::from_words(&words);
+    assert_eq!(a.as_words(), &words[..3]);
+    assert_eq!(
+        a,
+        (ubig!(5) << (2 * Word::MAX.count_ones() as usize)) + ubig!(3)
+    );
+    assert_eq!(UBigRef::from_words(&words[1..2]), ubig!(0));
+    assert_eq!(UBigRef::from_words(&[]), ubig!(0));
+    assert_eq!(IBigRef::from_words(true, &words[..1]), ibig!(-3));
+    assert_eq!(IBigRef::from_words(true, &[0]), ibig!(0));
+}
+
+#[test]
+fn test_ubig_ref_arithmetic() {
+    let numbers = test_numbers();
+    for a in &numbers {
+        let a_ref = UBigRef::from(a);
+        assert_eq!(a_ref.to_owned(), *a);
+        assert_eq!(UBig::from(a_ref), *a);
+        assert_eq!(a_ref.bit_len(), a.bit_len());
+        assert_eq!(a_ref.trailing_zeros(), a.trailing_zeros());
+        assert_eq!(a_ref.is_power_of_two(), a.is_power_of_two());
+        for n in [0, 1, 63, 64, 100, 500, 1000].iter() {
+            assert_eq!(a_ref.bit(*n), a.bit(*n));
+            assert_eq!(a_ref << *n, a << *n);
+            assert_eq!(a_ref >> *n, a >> *n);
+        }
+        for b in &numbers {
+            let b_ref = UBigRef::from(b);
+            assert_eq!(a_ref.cmp(&b_ref), a.cmp(b));
+            assert_eq!(a_ref.partial_cmp(b), a.partial_cmp(b));
+            assert_eq!(a_ref == *b, a == b);
+            assert_eq!(a_ref + b_ref, a + b);
+            assert_eq!(a_ref * b_ref, a * b);
+            if a >= b {
+                assert_eq!(a_ref - b_ref, a - b);
+            }
+            if *b != ubig!(0) {
+                assert_eq!(a_ref / b_ref, a / b);
+                assert_eq!(a_ref % b_ref, a % b);
+                assert_eq!(a_ref.div_rem(b_ref), a.div_rem(b));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ibig_ref_arithmetic() {
+    let mut numbers: Vec<IBig> = test_numbers().into_iter().map(IBig::from).collect();
+    let negated: Vec<IBig> = numbers.iter().map(|x| -x).collect();
+    numbers.extend(negated);
+    for a in &numbers {
+        let a_ref = IBigRef::from(a);
+        assert_eq!(a_ref.to_owned(), *a);
+        assert_eq!(a_ref.is_negative(), *a < ibig!(0));
+        assert_eq!(-a_ref, -a);
+        assert_eq!(a_ref << 100, a << 100);
+        for b in &numbers {
+            let b_ref = IBigRef::from(b);
+            assert_eq!(a_ref.cmp(&b_ref), a.cmp(b));
+            assert_eq!(a_ref.partial_cmp(b), a.partial_cmp(b));
+            assert_eq!(a_ref + b_ref, a + b);
+            assert_eq!(a_ref - b_ref, a - b);
+            assert_eq!(a_ref * b_ref, a * b);
+            if *b != ibig!(0) {
+                assert_eq!(a_ref / b_ref, a / b);
+                assert_eq!(a_ref % b_ref, a % b);
+                assert_eq!(a_ref.div_rem(b_ref), a.div_rem(b));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_format() {
+    for a in &test_numbers() {
+        let a_ref = UBigRef::from(a);
+        assert_eq!(format!("{}", a_ref), format!("{}", a));
+        assert_eq!(format!("{:?}", a_ref), format!("{:?}", a));
+        assert_eq!(format!("{:#x}", a_ref), format!("{:#x}", a));
+        assert_eq!(format!("{:X}", a_ref), format!("{:X}", a));
+        assert_eq!(format!("{:#o}", a_ref), format!("{:#o}", a));
+        assert_eq!(format!("{:b}", a_ref), format!("{:b}", a));
+        assert_eq!(a_ref.in_radix(7).to_string(), a.in_radix(7).to_string());
+        let b = -IBig::from(a);
+        let b_ref = IBigRef::from(&b);
+        assert_eq!(format!("{:>10}", b_ref), format!("{:>10}", b));
+        assert_eq!(format!("{:x}", b_ref), format!("{:x}", b));
+        assert_eq!(b_ref.in_radix(36).to_string(), b.in_radix(36).to_string());
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_sub_overflow() {
+    let a = ubig!(5);
+    let b = ubig!(1) << 100;
+    let _ = UBigRef::from(&a) - UBigRef::from(&b);
+}
+
+#[test]
+#[should_panic]
+fn test_div_by_0() {
+    let a = ubig!(1) << 100;
+    let _ = UBigRef::from(&a) / UBigRef::from_words(&[]);
+}
@@ -0,0 +1,68 @@
+use ibig::{ubig, UBig};
+
+#[test]
+fn test_sub_variants() {
+    let test_cases = [
+        (ubig!(5), ubig!(3)),
+        (ubig!(3), ubig!(5)),
+        (ubig!(7), ubig!(7)),
+        (ubig!(0), ubig!(0)),
+        (ubig!(1) << 200, ubig!(1)),
+        (ubig!(1), ubig!(1) << 200),
+        (ubig!(_0x1234567890abcdef1234567890abcdef), ubig!(1) << 100),
+    ];
+
+    for (a, b) in &test_cases {
+        if a >= b {
+            let diff = a - b;
+            assert_eq!(a.checked_sub(b), Some(diff.clone()));
+            assert_eq!(a.overflowing_sub(b), (diff.clone(), false));
+            assert_eq!(a.saturating_sub(b), diff.clone());
+            assert_eq!(a.abs_diff(b), diff);
+        } else {
+            let diff = b - a;
+            assert_eq!(a.checked_sub(b), None);
+            assert_eq!(a.overflowing_sub(b), (diff.clone(), true));
+            assert_eq!(a.saturating_sub(b), ubig!(0));
+            assert_eq!(a.abs_diff(b), diff);
+        }
+    }
+}
+
+#[test]
+fn test_checked_div_rem() {
+    let a = (ubig!(1) << 200) + ubig!(17);
+    let b = ubig!(1) << 100;
+    assert_eq!(a.checked_div(&b), Some(&a / &b));
+    assert_eq!(a.checked_rem(&b), Some(ubig!(17)));
+    assert_eq!(a.checked_div(&ubig!(0)), None);
+    assert_eq!(a.checked_rem(&ubig!(0)), None);
+    assert_eq!(ubig!(0).checked_div(&ubig!(0)), None);
+    assert_eq!(ubig!(0).checked_rem(&ubig!(5)), Some(ubig!(0)));
+}
+
+#[test]
+fn test_checked_pow() {
+    assert_eq!(ubig!(0).checked_pow(usize::MAX), Some(ubig!(0)));
+    assert_eq!(ubig!(1).checked_pow(usize::MAX), Some(ubig!(1)));
+    assert_eq!(ubig!(5).checked_pow(0), Some(ubig!(1)));
+    assert_eq!(ubig!(123).checked_pow(13), Some(ubig!(123).pow(13)));
+    assert_eq!(ubig!(3).checked_pow(1000), Some(ubig!(3).pow(1000)));
+    assert_eq!(ubig!(2).checked_pow(UBig::MAX_BIT_LEN), None);
+    assert_eq!(ubig!(3).checked_pow(UBig::MAX_BIT_LEN), None);
+    assert_eq!(ubig!(3).checked_pow(usize::MAX), None);
+    assert_eq!(
+        (ubig!(1) << 1000).checked_pow(UBig::MAX_BIT_LEN / 1000 + 1),
+        None
+    );
+}
+
+#[test]
+fn test_checked_shl() {
+    assert_eq!(ubig!(3).checked_shl(0), Some(ubig!(3)));
+    assert_eq!(ubig!(3).checked_shl(100), Some(ubig!(3) << 100));
+    assert_eq!(ubig!(0).checked_shl(usize::MAX), Some(ubig!(0)));
+    assert_eq!(ubig!(1).checked_shl(UBig::MAX_BIT_LEN), None);
+    assert_eq!(ubig!(3).checked_shl(UBig::MAX_BIT_LEN - 1), None);
+    assert_eq!((ubig!(1) << 100).checked_shl(usize::MAX), None);
+}
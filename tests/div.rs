@@ -1,9 +1,15 @@
 use ibig::{
     ibig,
-    ops::{DivEuclid, DivRem, DivRemEuclid, RemEuclid},
-    ubig, IBig,
+    ops::{
+        DivCeil, DivEuclid, DivFloor, DivRem, DivRemEuclid, DivRemFloor, DivRound, RemEuclid,
+        TieBreak,
+    },
+    ubig, Divisor, IBig, UBig,
 };
 
+#[cfg(not(force_bits = "16"))]
+use ibig::{ThresholdConfig, Word};
+
 #[test]
 fn test_div_rem_ubig() {
     let test_cases = [
@@ -141,6 +147,32 @@ fn test_div_rem_ubig() {
     }
 }
 
+#[test]
+#[cfg(not(force_bits = "16"))]
+fn test_div_rem_ubig_huge() {
+    // Divisors and quotients longer than `div_newton`, so that division uses Newton's method.
+    // Numbers this long are not supported with 16-bit words.
+    let bits = (ThresholdConfig::built_in().div_newton + 10) * Word::MAX.count_ones() as usize;
+    let b = ubig!(3).pow(bits * 2 / 3) + ubig!(7);
+    let q = ubig!(7).pow(bits * 2 / 5) - ubig!(1);
+
+    // Balanced.
+    let r = &b - ubig!(1);
+    let a = &q * &b + &r;
+    assert_eq!(&a / &b, q);
+    assert_eq!(&a % &b, r);
+
+    // Quotient shorter than the divisor, and quotient longer than the divisor.
+    for (b, q) in [
+        ((&b << (bits / 2)) + ubig!(5), q.clone()),
+        (b, (&q << (bits / 2)) + ubig!(1)),
+    ] {
+        let r = &b - ubig!(1);
+        let a = &q * &b + &r;
+        assert_eq!(a.div_rem(&b), (q, r));
+    }
+}
+
 #[test]
 #[should_panic]
 fn test_divide_by_0_ubig() {
@@ -227,8 +259,302 @@ fn test_div_rem_euclid_ibig() {
     }
 }
 
+const TIE_BREAKS: [TieBreak; 5] = [
+    TieBreak::Even,
+    TieBreak::AwayFromZero,
+    TieBreak::TowardZero,
+    TieBreak::Up,
+    TieBreak::Down,
+];
+
+fn div_floor_i32(a: i32, b: i32) -> i32 {
+    let q = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+fn div_round_i32(a: i32, b: i32, ties: TieBreak) -> i32 {
+    let floor = div_floor_i32(a, b);
+    let ceil = -div_floor_i32(-a, b);
+    let floor_dist = (a - floor * b).abs();
+    let ceil_dist = (a - ceil * b).abs();
+    if floor_dist != ceil_dist {
+        return if floor_dist < ceil_dist { floor } else { ceil };
+    }
+    match ties {
+        TieBreak::Even => {
+            if floor % 2 == 0 {
+                floor
+            } else {
+                ceil
+            }
+        }
+        TieBreak::AwayFromZero => {
+            if floor.abs() > ceil.abs() {
+                floor
+            } else {
+                ceil
+            }
+        }
+        TieBreak::TowardZero => {
+            if floor.abs() < ceil.abs() {
+                floor
+            } else {
+                ceil
+            }
+        }
+        TieBreak::Up => ceil,
+        TieBreak::Down => floor,
+    }
+}
+
+#[test]
+fn test_div_floor_ceil_round_ubig() {
+    for a in 0..=40 {
+        for b in 1..=20 {
+            let a_big = UBig::from(a as u32);
+            let b_big = UBig::from(b as u32);
+            let floor = UBig::from(div_floor_i32(a, b) as u32);
+            let ceil = UBig::from(-div_floor_i32(-a, b) as u32);
+            let rem = UBig::from((a - div_floor_i32(a, b) * b) as u32);
+
+            assert_eq!(a_big.clone().div_floor(b_big.clone()), floor);
+            assert_eq!((&a_big).div_floor(b_big.clone()), floor);
+            assert_eq!(a_big.clone().div_floor(&b_big), floor);
+            assert_eq!((&a_big).div_floor(&b_big), floor);
+
+            let qr = (floor.clone(), rem.clone());
+            assert_eq!(a_big.clone().div_rem_floor(b_big.clone()), qr);
+            assert_eq!((&a_big).div_rem_floor(b_big.clone()), qr);
+            assert_eq!(a_big.clone().div_rem_floor(&b_big), qr);
+            assert_eq!((&a_big).div_rem_floor(&b_big), qr);
+
+            assert_eq!(a_big.clone().div_ceil(b_big.clone()), ceil);
+            assert_eq!((&a_big).div_ceil(b_big.clone()), ceil);
+            assert_eq!(a_big.clone().div_ceil(&b_big), ceil);
+            assert_eq!((&a_big).div_ceil(&b_big), ceil);
+
+            for ties in &TIE_BREAKS {
+                let round = UBig::from(div_round_i32(a, b, *ties) as u32);
+                assert_eq!(a_big.clone().div_round(b_big.clone(), *ties), round);
+                assert_eq!((&a_big).div_round(b_big.clone(), *ties), round);
+                assert_eq!(a_big.clone().div_round(&b_big, *ties), round);
+                assert_eq!((&a_big).div_round(&b_big, *ties), round);
+            }
+        }
+    }
+
+    let a = ubig!(1) << 1000;
+    let b = (ubig!(1) << 500) + ubig!(1);
+    // a = b * (2^500 - 1) + 1
+    let q = (ubig!(1) << 500) - ubig!(1);
+    assert_eq!((&a).div_floor(&b), q);
+    assert_eq!((&a).div_ceil(&b), &q + ubig!(1));
+    assert_eq!((&a).div_round(&b, TieBreak::Up), q);
+    assert_eq!((&a - ubig!(1)).div_rem_floor(&b), (q, ubig!(0)));
+}
+
+#[test]
+fn test_div_floor_ceil_round_ibig() {
+    for a in -40..=40 {
+        for b in -20..=20 {
+            if b == 0 {
+                continue;
+            }
+            let a_big = IBig::from(a);
+            let b_big = IBig::from(b);
+            let floor = IBig::from(div_floor_i32(a, b));
+            let ceil = IBig::from(-div_floor_i32(-a, b));
+            let rem = IBig::from(a - div_floor_i32(a, b) * b);
+
+            assert_eq!(a_big.clone().div_floor(b_big.clone()), floor);
+            assert_eq!((&a_big).div_floor(b_big.clone()), floor);
+            assert_eq!(a_big.clone().div_floor(&b_big), floor);
+            assert_eq!((&a_big).div_floor(&b_big), floor);
+
+            let qr = (floor.clone(), rem.clone());
+            assert_eq!(a_big.clone().div_rem_floor(b_big.clone()), qr);
+            assert_eq!((&a_big).div_rem_floor(b_big.clone()), qr);
+            assert_eq!(a_big.clone().div_rem_floor(&b_big), qr);
+            assert_eq!((&a_big).div_rem_floor(&b_big), qr);
+
+            assert_eq!(a_big.clone().div_ceil(b_big.clone()), ceil);
+            assert_eq!((&a_big).div_ceil(b_big.clone()), ceil);
+            assert_eq!(a_big.clone().div_ceil(&b_big), ceil);
+            assert_eq!((&a_big).div_ceil(&b_big), ceil);
+
+            for ties in &TIE_BREAKS {
+                let round = IBig::from(div_round_i32(a, b, *ties));
+                assert_eq!(a_big.clone().div_round(b_big.clone(), *ties), round);
+                assert_eq!((&a_big).div_round(b_big.clone(), *ties), round);
+                assert_eq!(a_big.clone().div_round(&b_big, *ties), round);
+                assert_eq!((&a_big).div_round(&b_big, *ties), round);
+            }
+        }
+    }
+}
+
 #[test]
 #[should_panic]
 fn test_divide_by_0_ibig() {
     let _ = ibig!(5) / ibig!(0);
 }
+
+#[test]
+fn test_div_exact_ubig() {
+    let divisors = [
+        ubig!(1),
+        ubig!(3),
+        ubig!(1) << 70,
+        ubig!(_0xffffffffffffffff),
+        ubig!(_0x123456789abcdef0123456789abcdef) << 13,
+        ubig!(7).pow(500),
+        ubig!(5).pow(2000) << 3,
+        ubig!(7).pow(20000) << 5,
+    ];
+    let multipliers = [
+        ubig!(0),
+        ubig!(1),
+        ubig!(2),
+        ubig!(_0xfedcba9876543210),
+        ubig!(11).pow(30),
+        ubig!(13).pow(1000) << 100,
+        ubig!(3).pow(3000),
+    ];
+    for d in &divisors {
+        for m in &multipliers {
+            let a = d * m;
+            assert_eq!(a.div_exact(d), *m);
+            assert!(a.is_divisible_by(d));
+            assert!(!(&a + ubig!(1)).is_divisible_by(d) || *d == ubig!(1));
+            if *m != ubig!(0) {
+                assert!(!(&a + d - ubig!(1)).is_divisible_by(d) || *d == ubig!(1));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_is_divisible_by_ubig() {
+    assert!(ubig!(0).is_divisible_by(&ubig!(0)));
+    assert!(ubig!(0).is_divisible_by(&ubig!(5)));
+    assert!(!ubig!(5).is_divisible_by(&ubig!(0)));
+    assert!(!ubig!(5).is_divisible_by(&(ubig!(1) << 100)));
+    assert!(!(ubig!(1) << 100).is_divisible_by(&(ubig!(1) << 101)));
+    assert!((ubig!(1) << 101).is_divisible_by(&(ubig!(1) << 100)));
+    assert!(!ubig!(3).pow(100).is_divisible_by(&ubig!(3).pow(101)));
+    for a in 0..200u32 {
+        for b in 1..50u32 {
+            assert_eq!(
+                UBig::from(a).is_divisible_by(&UBig::from(b)),
+                a % b == 0,
+                "{} {}",
+                a,
+                b
+            );
+        }
+    }
+}
+
+#[test]
+fn test_is_divisible_by_pow2() {
+    assert!(ubig!(0).is_divisible_by_pow2(0));
+    assert!(ubig!(0).is_divisible_by_pow2(1000));
+    assert!(ubig!(1).is_divisible_by_pow2(0));
+    assert!(!ubig!(1).is_divisible_by_pow2(1));
+    assert!((ubig!(3) << 100).is_divisible_by_pow2(100));
+    assert!(!(ubig!(3) << 100).is_divisible_by_pow2(101));
+    assert!((ibig!(-3) << 100).is_divisible_by_pow2(100));
+    assert!(!(ibig!(-3) << 100).is_divisible_by_pow2(101));
+}
+
+#[test]
+fn test_div_exact_ibig() {
+    let a = ibig!(-3).pow(301);
+    let b = ibig!(3).pow(100);
+    assert_eq!(a.div_exact(&b), ibig!(-3).pow(201));
+    assert_eq!(a.div_exact(&-&b), ibig!(3).pow(201));
+    assert_eq!(ibig!(0).div_exact(&b), ibig!(0));
+    assert!(a.is_divisible_by(&b));
+    assert!(a.is_divisible_by(&-&b));
+    assert!(!(&a + ibig!(1)).is_divisible_by(&b));
+}
+
+#[test]
+#[should_panic]
+fn test_div_exact_by_0_ubig() {
+    let _ = ubig!(5).div_exact(&ubig!(0));
+}
+
+#[test]
+#[should_panic]
+fn test_div_exact_by_0_ibig() {
+    let _ = ibig!(-5).div_exact(&ibig!(0));
+}
+
+#[test]
+fn test_divisor() {
+    let divisors = [
+        ubig!(1),
+        ubig!(7),
+        ubig!(_0xffffffffffffffff),
+        ubig!(_0x123456789abcdef0123456789abcdef),
+        (ubig!(1) << 100) + ubig!(1),
+        ubig!(3).pow(1000),
+        ubig!(1) << 5000,
+    ];
+    let dividends = [
+        ubig!(0),
+        ubig!(5),
+        ubig!(_0xfedcba9876543210),
+        ubig!(_0x123456789abcdef0123456789abcdef),
+        ubig!(11).pow(30),
+        ubig!(13).pow(1000) << 100,
+        ubig!(3).pow(3000) - ubig!(1),
+    ];
+    for d in &divisors {
+        let divisor = Divisor::new(d);
+        assert_eq!(divisor.divisor(), *d);
+        for a in &dividends {
+            let qr = a.div_rem(d);
+            assert_eq!(a / &divisor, qr.0);
+            assert_eq!(a.clone() / &divisor, qr.0);
+            assert_eq!(a % &divisor, qr.1);
+            assert_eq!(a.clone() % &divisor, qr.1);
+            assert_eq!(a.div_rem(&divisor), qr);
+            assert_eq!(a.clone().div_rem(&divisor), qr);
+            let mut x = a.clone();
+            x /= &divisor;
+            assert_eq!(x, qr.0);
+            let mut x = a.clone();
+            x %= &divisor;
+            assert_eq!(x, qr.1);
+
+            for b in &[IBig::from(a.clone()), -IBig::from(a.clone())] {
+                let d_signed = IBig::from(d.clone());
+                let qr = b.div_rem(&d_signed);
+                assert_eq!(b / &divisor, qr.0);
+                assert_eq!(b.clone() / &divisor, qr.0);
+                assert_eq!(b % &divisor, qr.1);
+                assert_eq!(b.clone() % &divisor, qr.1);
+                assert_eq!(b.div_rem(&divisor), qr);
+                assert_eq!(b.clone().div_rem(&divisor), qr);
+                let mut x = b.clone();
+                x /= &divisor;
+                assert_eq!(x, qr.0);
+                let mut x = b.clone();
+                x %= &divisor;
+                assert_eq!(x, qr.1);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_divisor_0() {
+    let _ = Divisor::new(&ubig!(0));
+}
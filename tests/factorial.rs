@@ -0,0 +1,89 @@
+use ibig::{ubig, UBig};
+
+fn product(factors: impl Iterator<Item = usize>) -> UBig {
+    factors.fold(ubig!(1), |acc, x| acc * UBig::from(x))
+}
+
+fn is_prime(n: usize) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n / d * d != n)
+}
+
+#[test]
+fn test_factorial() {
+    for n in 0..300 {
+        assert_eq!(UBig::factorial(n), product(1..=n));
+    }
+    assert_eq!(UBig::factorial(2000), product(1..=2000));
+}
+
+#[test]
+fn test_double_factorial() {
+    for n in 0..300 {
+        assert_eq!(
+            UBig::double_factorial(n),
+            product((1..=n).rev().step_by(2)),
+            "n = {}",
+            n
+        );
+    }
+    assert_eq!(UBig::double_factorial(2001), product((1..=2001).step_by(2)));
+}
+
+#[test]
+fn test_primorial() {
+    for n in 0..300 {
+        assert_eq!(
+            UBig::primorial(n),
+            product((0..=n).filter(|p| is_prime(*p)))
+        );
+    }
+}
+
+#[test]
+fn test_binomial() {
+    for n in 0..100 {
+        for k in 0..=n + 1 {
+            let expected = if k > n {
+                ubig!(0)
+            } else {
+                product(n - k + 1..=n) / product(1..=k)
+            };
+            assert_eq!(UBig::binomial(n, k), expected, "n = {}, k = {}", n, k);
+        }
+    }
+    assert_eq!(
+        UBig::binomial(1000, 500),
+        product(501..=1000) / product(1..=500)
+    );
+    assert_eq!(UBig::binomial(1000, 3), ubig!(166167000));
+    assert_eq!(UBig::binomial(1000, 997), ubig!(166167000));
+    let big = usize::MAX;
+    assert_eq!(UBig::binomial(big, 1), UBig::from(big));
+    assert_eq!(UBig::binomial(big, big - 1), UBig::from(big));
+    assert_eq!(
+        UBig::binomial(big, 2),
+        UBig::from(big) * UBig::from(big - 1) / ubig!(2)
+    );
+}
+
+#[test]
+fn test_multinomial() {
+    assert_eq!(UBig::multinomial(&[]), ubig!(1));
+    assert_eq!(UBig::multinomial(&[0, 0]), ubig!(1));
+    assert_eq!(UBig::multinomial(&[7]), ubig!(1));
+    for a in 0..20 {
+        for b in 0..20 {
+            for c in 0..20 {
+                let expected =
+                    product(1..=a + b + c) / (product(1..=a) * product(1..=b) * product(1..=c));
+                assert_eq!(UBig::multinomial(&[a, b, c]), expected);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_multinomial_overflow() {
+    let _ = UBig::multinomial(&[usize::MAX, 1]);
+}
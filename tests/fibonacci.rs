@@ -0,0 +1,79 @@
+use ibig::{modular::ModuloRing, ubig, UBig};
+
+#[test]
+fn test_fibonacci() {
+    let mut a = ubig!(0);
+    let mut b = ubig!(1);
+    for n in 0..1000 {
+        assert_eq!(UBig::fibonacci(n), a, "n = {}", n);
+        assert_eq!(UBig::fibonacci2(n), (a.clone(), &b - &a), "n = {}", n);
+        let c = &a + &b;
+        a = b;
+        b = c;
+    }
+}
+
+#[test]
+fn test_fibonacci_large() {
+    let n = 20000;
+    let (f, g) = UBig::fibonacci2(n);
+    assert_eq!(UBig::fibonacci(n), f);
+    assert_eq!(UBig::fibonacci(n - 1), g);
+    assert_eq!(UBig::fibonacci(n + 1), &f + &g);
+    // Cassini's identity: F(n-1) F(n+1) - F(n)^2 = (-1)^n
+    assert_eq!(&g * (&f + &g), &f * &f + ubig!(1));
+    assert_eq!(
+        UBig::fibonacci(2 * n + 1),
+        &f * &f + UBig::fibonacci(n + 1).pow(2)
+    );
+}
+
+#[test]
+fn test_lucas() {
+    let mut a = ubig!(2);
+    let mut b = ubig!(1);
+    for n in 0..1000 {
+        assert_eq!(UBig::lucas(n), a, "n = {}", n);
+        let c = &a + &b;
+        a = b;
+        b = c;
+    }
+    let n = 12345;
+    assert_eq!(
+        UBig::lucas(n),
+        UBig::fibonacci(n - 1) + UBig::fibonacci(n + 1)
+    );
+}
+
+#[test]
+fn test_modulo_fibonacci() {
+    let moduli = [
+        ubig!(1),
+        ubig!(2),
+        ubig!(1000000007),
+        ubig!(_0xfffffffffffffffffffffffffffffff1),
+        (ubig!(1) << 300) - ubig!(1),
+    ];
+    for m in &moduli {
+        let ring = ModuloRing::new(m);
+        for n in (0..200).chain([1000, 4095, 4096, 12345].iter().copied()) {
+            assert_eq!(
+                ring.fibonacci(&UBig::from(n)),
+                ring.from(UBig::fibonacci(n)),
+                "n = {}",
+                n
+            );
+            assert_eq!(
+                ring.lucas(&UBig::from(n)),
+                ring.from(UBig::lucas(n)),
+                "n = {}",
+                n
+            );
+        }
+    }
+
+    // The Pisano period of 10 is 60.
+    let ring = ModuloRing::new(&ubig!(10));
+    let n = (ubig!(1) << 200) * ubig!(60) + ubig!(7);
+    assert_eq!(ring.fibonacci(&n), ring.from(13));
+}
@@ -0,0 +1,281 @@
+use core::convert::TryFrom;
+use ibig::{
+    fixed::{FixedModuloRing, Int, UInt, I128, I256, I4096, U128, U256, U4096},
+    ibig,
+    modular::ModuloRing,
+    ops::{DivRem, RemEuclid, UnsignedAbs},
+    ubig, IBig, UBig,
+};
+
+fn big_numbers() -> Vec<UBig> {
+    vec![
+        ubig!(0),
+        ubig!(1),
+        ubig!(2),
+        ubig!(12345),
+        UBig::from(u64::MAX),
+        ubig!(1) << 64,
+        ubig!(1) << 127,
+        (ubig!(1) << 128) - ubig!(1),
+        ubig!(3).pow(60),
+        ubig!(3).pow(150),
+        ubig!(7).pow(700),
+        ubig!(5).pow(1500),
+        (ubig!(1) << 4000) - ubig!(1),
+    ]
+}
+
+/// Test numbers reduced to `WORDS` words, including some that only use part of the width.
+fn uint_numbers<const WORDS: usize>() -> Vec<UInt<WORDS>> {
+    let bits = UInt::<WORDS>::BITS;
+    let mut numbers: Vec<UInt<WORDS>> = big_numbers()
+        .iter()
+        .map(|x| UInt::try_from(x % (ubig!(1) << bits)).unwrap())
+        .collect();
+    numbers.push(UInt::MAX);
+    numbers.push(UInt::try_from(ubig!(1) << (bits / 2)).unwrap());
+    let high_bit = ubig!(1) << (bits - 1);
+    numbers.push(UInt::try_from(&high_bit + ubig!(3).pow(20) % &high_bit).unwrap());
+    numbers
+}
+
+/// Wrap `x` to `BITS` bits, returning whether it was out of range.
+fn wrap_uint<const WORDS: usize>(x: &IBig) -> (UInt<WORDS>, bool) {
+    let modulus = IBig::from(ubig!(1) << UInt::<WORDS>::BITS);
+    let wrapped = x.rem_euclid(&modulus);
+    let result = UInt::try_from(UBig::try_from(&wrapped).unwrap()).unwrap();
+    (result, &wrapped != x)
+}
+
+fn wrap_int<const WORDS: usize>(x: &IBig) -> (Int<WORDS>, bool) {
+    let result = Int::from_bits(wrap_uint::<WORDS>(x).0);
+    (result, &IBig::from(result) != x)
+}
+
+fn shift_amounts(bits: usize) -> Vec<usize> {
+    vec![0, 1, 13, 64, 65, bits - 1]
+        .into_iter()
+        .filter(|n| *n < bits)
+        .collect()
+}
+
+fn check_uint<const WORDS: usize>() {
+    let numbers = uint_numbers::<WORDS>();
+    for a in &numbers {
+        let a_big = UBig::from(a);
+        assert_eq!(UInt::try_from(&a_big).unwrap(), *a);
+        assert_eq!(a.bit_len(), a_big.bit_len());
+        for b in &numbers {
+            let b_big = UBig::from(b);
+            let a_ibig = IBig::from(&a_big);
+            let b_ibig = IBig::from(&b_big);
+            assert_eq!(a.cmp(b), a_big.cmp(&b_big));
+            assert_eq!(a.overflowing_add(*b), wrap_uint(&(&a_ibig + &b_ibig)));
+            assert_eq!(a.overflowing_sub(*b), wrap_uint(&(&a_ibig - &b_ibig)));
+            assert_eq!(a.overflowing_mul(*b), wrap_uint(&(&a_ibig * &b_ibig)));
+            assert_eq!(a.checked_mul(*b).is_none(), a.overflowing_mul(*b).1);
+            if b.is_zero() {
+                assert_eq!(a.checked_div_rem(*b), None);
+            } else {
+                let (q, r) = (&a_big).div_rem(&b_big);
+                assert_eq!(
+                    a.checked_div_rem(*b),
+                    Some((UInt::try_from(q).unwrap(), UInt::try_from(r).unwrap()))
+                );
+            }
+            assert_eq!(UBig::from(*a & *b), &a_big & &b_big);
+            assert_eq!(UBig::from(*a | *b), &a_big | &b_big);
+            assert_eq!(UBig::from(*a ^ *b), &a_big ^ &b_big);
+        }
+        for n in shift_amounts(UInt::<WORDS>::BITS) {
+            assert_eq!(
+                a.checked_shl(n),
+                Some(wrap_uint(&IBig::from(&a_big << n)).0)
+            );
+            assert_eq!(a.checked_shr(n), Some(UInt::try_from(&a_big >> n).unwrap()));
+        }
+        assert_eq!(a.checked_shl(UInt::<WORDS>::BITS), None);
+        assert_eq!(a.wrapping_shr(UInt::<WORDS>::BITS), *a);
+    }
+}
+
+fn check_int<const WORDS: usize>() {
+    let mut numbers: Vec<Int<WORDS>> = uint_numbers::<WORDS>()
+        .into_iter()
+        .map(Int::from_bits)
+        .collect();
+    numbers.extend(vec![Int::MIN, Int::MAX, Int::NEG_ONE]);
+    for a in &numbers {
+        let a_big = IBig::from(a);
+        assert_eq!(Int::try_from(&a_big).unwrap(), *a);
+        assert_eq!(UBig::from(a.unsigned_abs()), (&a_big).unsigned_abs());
+        assert_eq!(a.overflowing_neg(), wrap_int(&-&a_big));
+        for b in &numbers {
+            let b_big = IBig::from(b);
+            assert_eq!(a.cmp(b), a_big.cmp(&b_big));
+            assert_eq!(a.overflowing_add(*b), wrap_int(&(&a_big + &b_big)));
+            assert_eq!(a.overflowing_sub(*b), wrap_int(&(&a_big - &b_big)));
+            assert_eq!(a.overflowing_mul(*b), wrap_int(&(&a_big * &b_big)));
+            if b.is_zero() {
+                assert_eq!(a.checked_div_rem(*b), None);
+            } else {
+                let (q, r) = (&a_big).div_rem(&b_big);
+                let expected = match (Int::try_from(q), Int::try_from(r)) {
+                    (Ok(q), Ok(r)) => Some((q, r)),
+                    _ => None,
+                };
+                assert_eq!(a.checked_div_rem(*b), expected);
+            }
+        }
+        for n in shift_amounts(Int::<WORDS>::BITS) {
+            assert_eq!(a.checked_shl(n), Some(wrap_int(&(&a_big << n)).0));
+            assert_eq!(a.checked_shr(n), Some(Int::try_from(&a_big >> n).unwrap()));
+        }
+    }
+}
+
+fn check_modular<const WORDS: usize>() {
+    let numbers = uint_numbers::<WORDS>();
+    for m in numbers.iter().filter(|m| !m.is_zero()) {
+        let ring = FixedModuloRing::new(m);
+        let big_ring = ModuloRing::new(&UBig::from(m));
+        assert_eq!(ring.modulus(), *m);
+        let reduced: Vec<UInt<WORDS>> = numbers.iter().map(|x| ring.reduce(*x)).collect();
+        for (x, r) in numbers.iter().zip(&reduced) {
+            assert_eq!(big_ring.from(UBig::from(x)).residue(), UBig::from(r));
+        }
+        for a in &reduced {
+            let a_mod = big_ring.from(UBig::from(a));
+            assert_eq!(UBig::from(ring.neg(*a)), (-&a_mod).residue());
+            for b in &reduced {
+                let b_mod = big_ring.from(UBig::from(b));
+                assert_eq!(UBig::from(ring.add(*a, *b)), (&a_mod + &b_mod).residue());
+                assert_eq!(UBig::from(ring.sub(*a, *b)), (&a_mod - &b_mod).residue());
+                assert_eq!(UBig::from(ring.mul(*a, *b)), (&a_mod * &b_mod).residue());
+            }
+        }
+        let a = reduced[reduced.len() - 1];
+        let e = UInt::<8>::try_from(ubig!(3).pow(70)).unwrap();
+        assert_eq!(
+            UBig::from(ring.pow(a, &e)),
+            big_ring.from(UBig::from(a)).pow(&UBig::from(e)).residue()
+        );
+    }
+}
+
+#[test]
+fn test_uint() {
+    check_uint::<1>();
+    check_uint::<2>();
+    check_uint::<4>();
+    check_uint::<40>();
+}
+
+#[test]
+fn test_int() {
+    check_int::<1>();
+    check_int::<2>();
+    check_int::<4>();
+    check_int::<40>();
+}
+
+#[test]
+fn test_modular() {
+    check_modular::<1>();
+    check_modular::<2>();
+    check_modular::<4>();
+    check_modular::<40>();
+    check_modular::<64>();
+}
+
+#[test]
+fn test_aliases() {
+    assert_eq!(U128::BITS, 128);
+    assert_eq!(U256::BITS, 256);
+    assert_eq!(U4096::BITS, 4096);
+    assert_eq!(I128::BITS, 128);
+    assert_eq!(I256::BITS, 256);
+    assert_eq!(I4096::BITS, 4096);
+    assert_eq!(UBig::from(U256::MAX), (ubig!(1) << 256) - ubig!(1));
+    assert_eq!(IBig::from(I128::MIN), -(ibig!(1) << 127));
+}
+
+#[test]
+fn test_conversion_out_of_range() {
+    let bits = UInt::<2>::BITS;
+    assert!(UInt::<2>::try_from(ubig!(1) << bits).is_err());
+    assert!(UInt::<2>::try_from(ubig!(1) << (bits - 1)).is_ok());
+    assert!(Int::<2>::try_from(ibig!(1) << (bits - 1)).is_err());
+    assert!(Int::<2>::try_from(-(ibig!(1) << (bits - 1))).is_ok());
+    assert!(Int::<2>::try_from(-(ibig!(1) << (bits - 1)) - ibig!(1)).is_err());
+    assert_eq!(u8::try_from(UInt::<2>::try_from(255u8).unwrap()), Ok(255));
+    assert!(u8::try_from(UInt::<2>::MAX).is_err());
+    assert_eq!(i8::try_from(Int::<2>::try_from(-128).unwrap()), Ok(-128));
+}
+
+#[test]
+fn test_pow() {
+    let bits = UInt::<4>::BITS;
+    // The largest power of 3 that fits.
+    let mut max_exp = 0;
+    while ubig!(3).pow(max_exp + 1).bit_len() <= bits {
+        max_exp += 1;
+    }
+    let three = UInt::<4>::try_from(3u8).unwrap();
+    assert_eq!(
+        UBig::from(three.checked_pow(max_exp).unwrap()),
+        ubig!(3).pow(max_exp)
+    );
+    assert_eq!(three.checked_pow(max_exp + 1), None);
+    assert_eq!(
+        UBig::from(three.wrapping_pow(1000)),
+        ubig!(3).pow(1000) % (ubig!(1) << bits)
+    );
+}
+
+#[test]
+fn test_format() {
+    let a = UInt::<2>::try_from(ubig!(0xabcdef)).unwrap();
+    assert_eq!(format!("{} {:x} {:?}", a, a, a), "11259375 abcdef 11259375");
+    let b = Int::<2>::try_from(-1000).unwrap();
+    assert_eq!(format!("{} {:?}", b, b), "-1000 -1000");
+    let ring = FixedModuloRing::new(&a);
+    assert_eq!(format!("{}", ring), "mod 11259375");
+}
+
+#[test]
+#[should_panic]
+fn test_add_overflow() {
+    let _ = UInt::<2>::MAX + UInt::ONE;
+}
+
+#[test]
+#[should_panic]
+fn test_sub_overflow() {
+    let _ = Int::<2>::MIN - Int::ONE;
+}
+
+#[test]
+#[should_panic]
+fn test_div_by_0() {
+    let _ = UInt::<2>::ONE / UInt::ZERO;
+}
+
+#[test]
+#[should_panic]
+fn test_shl_overflow() {
+    let _ = UInt::<2>::ONE << UInt::<2>::BITS;
+}
+
+#[test]
+#[should_panic]
+fn test_modulo_ring_0() {
+    let _ = FixedModuloRing::new(&UInt::<2>::ZERO);
+}
+
+#[test]
+#[should_panic]
+fn test_modular_not_reduced() {
+    let ring = FixedModuloRing::new(&UInt::<2>::try_from(100u8).unwrap());
+    let _ = ring.add(UInt::try_from(100u8).unwrap(), UInt::ZERO);
+}
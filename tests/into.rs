@@ -0,0 +1,117 @@
+use ibig::{ops::DivRem, ubig, Scratch, UBig};
+
+fn test_numbers() -> Vec<UBig> {
+    vec![
+        ubig!(0),
+        ubig!(1),
+        ubig!(12345),
+        UBig::from(u64::MAX),
+        ubig!(1) << 64,
+        ubig!(1) << 500,
+        (ubig!(1) << 500) - ubig!(1),
+        ubig!(3).pow(300),
+        ubig!(7).pow(2000),
+    ]
+}
+
+#[test]
+fn test_add_sub_into() {
+    let numbers = test_numbers();
+    let mut out = ubig!(1) << 10000;
+    for a in &numbers {
+        for b in &numbers {
+            UBig::add_into(&mut out, a, b);
+            assert_eq!(out, a + b);
+            if a >= b {
+                UBig::sub_into(&mut out, a, b);
+                assert_eq!(out, a - b);
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_sub_into_overflow() {
+    let mut out = UBig::default();
+    UBig::sub_into(&mut out, &(ubig!(1) << 200), &(ubig!(1) << 201));
+}
+
+#[test]
+fn test_mul_into() {
+    let numbers = test_numbers();
+    let (mut out, mut scratch) = (UBig::default(), Scratch::new());
+    for a in &numbers {
+        for b in &numbers {
+            UBig::mul_into(&mut out, a, b, &mut scratch);
+            assert_eq!(out, a * b);
+        }
+    }
+}
+
+#[test]
+fn test_div_rem_into() {
+    let numbers = test_numbers();
+    let (mut q, mut r) = (ubig!(5), ubig!(1) << 1000);
+    let mut scratch = Scratch::new();
+    for a in &numbers {
+        for b in numbers.iter().filter(|b| **b != ubig!(0)) {
+            UBig::div_rem_into(&mut q, &mut r, a, b, &mut scratch);
+            assert_eq!((&q, &r), (&(a / b), &(a % b)));
+        }
+    }
+    let a = ubig!(3).pow(40000);
+    let b = ubig!(7).pow(10000);
+    UBig::div_rem_into(&mut q, &mut r, &a, &b, &mut scratch);
+    assert_eq!((q, r), a.div_rem(&b));
+}
+
+#[test]
+#[should_panic]
+fn test_div_rem_into_by_0() {
+    let (mut q, mut r) = (UBig::default(), UBig::default());
+    UBig::div_rem_into(&mut q, &mut r, &ubig!(5), &ubig!(0), &mut Scratch::new());
+}
+
+#[test]
+fn test_mul_div_rem_into_reuse_scratch() {
+    let a = ubig!(3).pow(20000);
+    let b = ubig!(7).pow(10000);
+    let c = ubig!(5).pow(5000);
+    let (mut product, mut q, mut r) = (UBig::default(), UBig::default(), UBig::default());
+    let mut scratch = Scratch::new();
+    UBig::mul_into(&mut product, &a, &b, &mut scratch);
+    UBig::div_rem_into(&mut q, &mut r, &product, &c, &mut scratch);
+    let capacity = scratch.capacity();
+    assert!(capacity > 0);
+    for _ in 0..5 {
+        UBig::mul_into(&mut product, &a, &b, &mut scratch);
+        UBig::div_rem_into(&mut q, &mut r, &product, &c, &mut scratch);
+        assert_eq!((&q, &r), (&(&a * &b / &c), &(&a * &b % &c)));
+        assert_eq!(scratch.capacity(), capacity);
+    }
+}
+
+#[test]
+fn test_shl_into() {
+    let numbers = test_numbers();
+    let mut out = UBig::default();
+    for a in &numbers {
+        for shift in &[0, 1, 63, 64, 65, 200, 1000] {
+            UBig::shl_into(&mut out, a, *shift);
+            assert_eq!(out, a << *shift);
+        }
+    }
+}
+
+#[test]
+fn test_into_iterated() {
+    // Fibonacci numbers computed with three reused variables.
+    let (mut a, mut b, mut c) = (ubig!(0), ubig!(1), UBig::default());
+    for _ in 0..1000 {
+        UBig::add_into(&mut c, &a, &b);
+        std::mem::swap(&mut a, &mut b);
+        std::mem::swap(&mut b, &mut c);
+    }
+    assert_eq!(a, UBig::fibonacci(1000));
+}
@@ -0,0 +1,123 @@
+use ibig::{ibig, ubig, UBig};
+
+fn ilog_naive(x: &UBig, base: &UBig) -> Option<usize> {
+    if *x == ubig!(0) {
+        return None;
+    }
+    let mut power = base.clone();
+    let mut log = 0;
+    while power <= *x {
+        power *= base;
+        log += 1;
+    }
+    Some(log)
+}
+
+#[test]
+fn test_ilog() {
+    let bases = [
+        ubig!(2),
+        ubig!(3),
+        ubig!(7),
+        ubig!(10),
+        ubig!(16),
+        ubig!(36),
+        ubig!(37),
+        ubig!(1000),
+        ubig!(_0x100000000000000000001),
+    ];
+    let mut values = vec![ubig!(0), ubig!(1), ubig!(2), ubig!(999), ubig!(1000)];
+    for base in &bases {
+        for exp in &[1, 2, 5, 63, 64, 65, 200] {
+            let power = base.pow(*exp);
+            values.push(&power - ubig!(1));
+            values.push(power.clone());
+            values.push(power + ubig!(1));
+        }
+    }
+    for base in &bases {
+        for x in &values {
+            assert_eq!(x.ilog(base), ilog_naive(x, base), "{}, {}", x, base);
+        }
+    }
+}
+
+#[test]
+fn test_ilog_large() {
+    for base in &[ubig!(3), ubig!(10), ubig!(35), ubig!(12345)] {
+        let power = base.pow(3000);
+        assert_eq!((&power - ubig!(1)).ilog(base), Some(2999));
+        assert_eq!(power.ilog(base), Some(3000));
+        assert_eq!((&power * base - ubig!(1)).ilog(base), Some(3000));
+    }
+}
+
+#[test]
+fn test_ilog2_ilog10() {
+    assert_eq!(ubig!(0).ilog2(), None);
+    assert_eq!(ubig!(0).ilog10(), None);
+    for k in 0..500 {
+        let power = ubig!(10).pow(k);
+        assert_eq!(power.ilog10(), Some(k));
+        assert_eq!((&power - ubig!(1)).ilog10(), k.checked_sub(1));
+        let power = ubig!(1) << k;
+        assert_eq!(power.ilog2(), Some(k));
+        assert_eq!((&power - ubig!(1)).ilog2(), k.checked_sub(1));
+    }
+}
+
+#[test]
+fn test_ilog_ibig() {
+    assert_eq!(ibig!(100).ilog(&ubig!(10)), Some(2));
+    assert_eq!(ibig!(100).ilog10(), Some(2));
+    assert_eq!(ibig!(100).ilog2(), Some(6));
+    assert_eq!(ibig!(0).ilog(&ubig!(10)), None);
+    assert_eq!(ibig!(0).ilog10(), None);
+    assert_eq!(ibig!(0).ilog2(), None);
+    assert_eq!(ibig!(-100).ilog(&ubig!(10)), None);
+    assert_eq!(ibig!(-100).ilog10(), None);
+    assert_eq!(ibig!(-100).ilog2(), None);
+}
+
+#[test]
+#[should_panic]
+fn test_ilog_base_1() {
+    let _ = ubig!(5).ilog(&ubig!(1));
+}
+
+#[test]
+#[should_panic]
+fn test_ilog_ibig_base_0() {
+    let _ = ibig!(-5).ilog(&ubig!(0));
+}
+
+#[test]
+fn test_log2_f64() {
+    assert_eq!(ubig!(0).log2_f64(), f64::NEG_INFINITY);
+    for x in &[1u64, 2, 3, 10, 1000, 123456789, u64::MAX] {
+        let expected = (*x as f64).log2();
+        assert!((UBig::from(*x).log2_f64() - expected).abs() < 1e-12);
+    }
+    let x = ubig!(5).pow(20000);
+    let expected = 20000.0 * 5f64.log2();
+    assert!((x.log2_f64() - expected).abs() / expected < 1e-14);
+
+    assert_eq!(ibig!(0).log2_f64(), f64::NEG_INFINITY);
+    assert_eq!(ibig!(8).log2_f64(), 3.0);
+    assert!(ibig!(-8).log2_f64().is_nan());
+}
+
+#[test]
+fn test_ln_f64() {
+    assert_eq!(ubig!(0).ln_f64(), f64::NEG_INFINITY);
+    for x in &[1u64, 2, 3, 10, 1000, 123456789, u64::MAX] {
+        let expected = (*x as f64).ln();
+        assert!((UBig::from(*x).ln_f64() - expected).abs() < 1e-12);
+    }
+    let x = ubig!(7).pow(20000);
+    let expected = 20000.0 * 7f64.ln();
+    assert!((x.ln_f64() - expected).abs() / expected < 1e-14);
+
+    assert_eq!(ibig!(0).ln_f64(), f64::NEG_INFINITY);
+    assert!(ibig!(-8).ln_f64().is_nan());
+}
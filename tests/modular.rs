@@ -244,6 +244,12 @@ fn test_pow() {
     let ring = ModuloRing::new(&prime);
     // Fermat theorem: a^(p-1) = 1
     assert_eq!(ring.from(13).pow(&(prime - ubig!(1))), ring.from(1));
+
+    // Large enough for squaring with Toom-3.
+    let ring = ModuloRing::new(&((ubig!(1) << 16000) + ubig!(7)));
+    let x = ring.from(ubig!(3).pow(10000));
+    assert_eq!(x.pow(&ubig!(2)), ring.from(ubig!(3).pow(20000)));
+    assert_eq!(x.pow(&ubig!(3)), ring.from(ubig!(3).pow(30000)));
 }
 
 #[test]
@@ -285,3 +291,69 @@ fn test_format() {
         "C9F2C9CD04674EDEA3FFFFFFF (mod C9F2C9CD04674EDEA40000000)"
     );
 }
+
+#[test]
+fn test_order() {
+    let ring = ModuloRing::new(&ubig!(1));
+    assert_eq!(ring.from(0).order(), Some(ubig!(1)));
+
+    let ring = ModuloRing::new(&ubig!(10));
+    assert_eq!(ring.from(1).order(), Some(ubig!(1)));
+    assert_eq!(ring.from(3).order(), Some(ubig!(4)));
+    assert_eq!(ring.from(9).order(), Some(ubig!(2)));
+    assert_eq!(ring.from(0).order(), None);
+    assert_eq!(ring.from(4).order(), None);
+
+    // Mersenne prime: 2 has order 127.
+    let p = ubig!(2).pow(127) - ubig!(1);
+    let ring = ModuloRing::new(&p);
+    assert_eq!(ring.from(2).order(), Some(ubig!(127)));
+    assert_eq!(ring.from(-1).order(), Some(ubig!(2)));
+
+    let ring = ModuloRing::new(&(ubig!(1) << 100));
+    assert_eq!(ring.from(5).order(), Some(ubig!(1) << 98));
+    assert_eq!(ring.from(6).order(), None);
+}
+
+#[test]
+fn test_order_with_factors() {
+    let ring = ModuloRing::new(&ubig!(274177));
+    let factors = [(ubig!(2), 8), (ubig!(3), 2), (ubig!(7), 1), (ubig!(17), 1)];
+    assert_eq!(ring.from(2).order_with_factors(&factors), Some(ubig!(128)));
+    assert_eq!(
+        ring.from(5).order_with_factors(&factors),
+        Some(ubig!(274176))
+    );
+    // Not a multiple of the order.
+    assert_eq!(ring.from(5).order_with_factors(&[(ubig!(2), 8)]), None);
+}
+
+#[test]
+fn test_primitive_root() {
+    let test_cases = [
+        (ubig!(1), Some(1)),
+        (ubig!(2), Some(1)),
+        (ubig!(4), Some(3)),
+        (ubig!(7), Some(3)),
+        (ubig!(8), None),
+        (ubig!(12), None),
+        (ubig!(15), None),
+        (ubig!(18), Some(5)),
+        (ubig!(25), Some(2)),
+        (ubig!(41), Some(6)),
+        (ubig!(998244353), Some(3)),
+        (ubig!(0xbe00000000000001), Some(3)),
+        (ubig!(3).pow(40), Some(2)),
+    ];
+
+    for (n, root) in &test_cases {
+        let ring = ModuloRing::new(n);
+        assert_eq!(ring.primitive_root(), root.map(|r| ring.from(r)));
+    }
+
+    // 2^127 - 1
+    let p = ubig!(2).pow(127) - ubig!(1);
+    let ring = ModuloRing::new(&p);
+    let root = ring.primitive_root().unwrap();
+    assert_eq!(root.order(), Some(&p - ubig!(1)));
+}
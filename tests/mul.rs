@@ -2,7 +2,11 @@ use core::{
     fmt::Debug,
     ops::{Mul, MulAssign},
 };
-use ibig::{ibig, ubig};
+use ibig::{dot, ibig, ops::DivRem, product_tree, remainder_tree, ubig, IBig, Scratch, UBig, Word};
+use std::panic::{self, AssertUnwindSafe};
+
+/// Bits in a `Word`, for lengths around the thresholds, which are in `Word`s.
+const WORD_BITS: usize = Word::BITS as usize;
 
 fn test_mul<'a, T>(a: &'a T, b: &'a T, c: &'a T)
 where
@@ -78,3 +82,226 @@ fn test_mul_ibig() {
         test_mul(b, a, c);
     }
 }
+
+#[test]
+fn test_square_ubig() {
+    // Lengths around the thresholds between multiplication algorithms.
+    let lengths = [1, 2, 3, 10, 24, 25, 26, 100, 191, 192, 193, 194, 500, 1500];
+    for len in lengths.iter() {
+        let bits = WORD_BITS * len;
+        let all_ones = (ubig!(1) << bits) - ubig!(1);
+        let mixed = ubig!(3).pow(bits * 10 / 16) % (ubig!(1) << bits);
+        let high_low = (ubig!(1) << (bits - 1)) + ubig!(1);
+        for a in [all_ones, mixed, high_low].iter() {
+            // a * (a + 1) - a doesn't square.
+            let expected = a * (a + ubig!(1)) - a;
+            assert_eq!(a * a, expected);
+            assert_eq!(a.clone() * a.clone(), expected);
+            assert_eq!(a.pow(2), expected);
+        }
+    }
+}
+
+#[test]
+fn test_mul_large_tiers() {
+    // Lengths in words around the thresholds for Toom-4 and Toom-8.
+    let p = ubig!(18446744073709551557);
+    for len in [7000, 7001, 10000, 10001].iter() {
+        let bits = WORD_BITS * len;
+        if 2 * bits > UBig::MAX_BIT_LEN {
+            continue;
+        }
+        let a = ubig!(3).pow(bits * 10 / 16) % (ubig!(1) << bits);
+        let b = (ubig!(1) << bits) - ubig!(1) - ubig!(5).pow(bits / 3);
+        let c = &a * &b;
+        assert_eq!(&c % &p, (&a % &p) * (&b % &p) % &p);
+        assert_eq!(c.div_rem(&b), (a.clone(), ubig!(0)));
+        let sqr = &a * &a;
+        assert_eq!(&sqr % &p, (&a % &p) * (&a % &p) % &p);
+        assert_eq!(sqr, &a * (&a + ubig!(1)) - &a);
+    }
+}
+
+#[test]
+fn test_mul_ntt() {
+    // Lengths in words above the threshold for number-theoretic multiplication, balanced and
+    // unbalanced.
+    let p = ubig!(18446744073709551557);
+    for (a_len, b_len) in [(10001, 10001), (12000, 10500), (150000, 10001)].iter() {
+        let (a_bits, b_bits) = (WORD_BITS * a_len, WORD_BITS * b_len);
+        if a_bits + b_bits > UBig::MAX_BIT_LEN {
+            continue;
+        }
+        let a = (ubig!(1) << a_bits) - ubig!(1) - ubig!(3).pow(a_bits * 10 / 16);
+        let b = (ubig!(1) << b_bits) - ubig!(1);
+        let c = &a * &b;
+        assert_eq!(&c % &p, (&a % &p) * (&b % &p) % &p);
+        assert_eq!(c, (&a << b_bits) - &a);
+        let sqr = &b * &b;
+        assert_eq!(sqr, (&b << b_bits) - &b);
+    }
+}
+
+#[test]
+fn test_add_sub_mul() {
+    let numbers = [
+        ubig!(0),
+        ubig!(1),
+        ubig!(12345),
+        UBig::from(u64::MAX),
+        ubig!(1) << 64,
+        (ubig!(1) << 500) - ubig!(1),
+        ubig!(3).pow(300),
+        ubig!(7).pow(2000),
+        ubig!(5).pow(10000),
+    ];
+    let mut scratch = Scratch::new();
+    for acc in numbers.iter() {
+        for a in numbers.iter() {
+            for b in numbers.iter() {
+                let product = a * b;
+                let mut x = acc.clone();
+                x.add_mul(a, b);
+                assert_eq!(x, acc + &product);
+                x.sub_mul_with_scratch(a, b, &mut scratch);
+                assert_eq!(&x, acc);
+                if *acc >= product {
+                    let mut x = acc.clone();
+                    x.sub_mul(a, b);
+                    assert_eq!(x, acc - &product);
+                    x.add_mul_with_scratch(a, b, &mut scratch);
+                    assert_eq!(&x, acc);
+                }
+
+                for acc_sign in [1, -1].iter() {
+                    for a_sign in [1, -1].iter() {
+                        let acc = IBig::from(acc.clone()) * IBig::from(*acc_sign);
+                        let a = IBig::from(a.clone()) * IBig::from(*a_sign);
+                        let b = IBig::from(b.clone());
+                        let product = &a * &b;
+                        let mut x = acc.clone();
+                        x.add_mul(&a, &b);
+                        assert_eq!(x, &acc + &product);
+                        x.sub_mul_with_scratch(&a, &b, &mut scratch);
+                        assert_eq!(x, acc);
+                        x.sub_mul(&a, &b);
+                        assert_eq!(x, &acc - &product);
+                        x.add_mul_with_scratch(&a, &b, &mut scratch);
+                        assert_eq!(x, acc);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_sub_mul_overflow() {
+    let mut x = ubig!(1) << 200;
+    x.sub_mul(&(ubig!(1) << 100), &(ubig!(1) << 101));
+}
+
+#[test]
+#[should_panic(expected = "UBig subtraction overflow")]
+fn test_sub_mul_overflow_keeps_value() {
+    let cases = [
+        (ubig!(41), ubig!(6), ubig!(7)),
+        (
+            (ubig!(1) << 200) - ubig!(1),
+            ubig!(1) << 100,
+            ubig!(1) << 100,
+        ),
+        (ubig!(3).pow(1000), ubig!(2).pow(800), ubig!(5).pow(500)),
+    ];
+    let mut scratch = Scratch::new();
+    let mut panic = None;
+    for (acc, a, b) in cases.iter() {
+        let mut x = acc.clone();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            x.sub_mul_with_scratch(a, b, &mut scratch)
+        }));
+        assert_eq!(&x, acc);
+        panic = Some(result.unwrap_err());
+    }
+    panic::resume_unwind(panic.unwrap());
+}
+
+#[test]
+fn test_dot() {
+    assert_eq!(dot(&[], &[]), ibig!(0));
+    let a: Vec<IBig> = (0..20).map(|i| ibig!(-3).pow(i * 50)).collect();
+    let b: Vec<IBig> = (0..20)
+        .map(|i| ibig!(7).pow(1000 - i * 50) - ibig!(1))
+        .collect();
+    let expected = a
+        .iter()
+        .zip(b.iter())
+        .fold(ibig!(0), |sum, (x, y)| sum + x * y);
+    assert_eq!(dot(&a, &b), expected);
+}
+
+#[test]
+#[should_panic]
+fn test_dot_different_lengths() {
+    let _ = dot(&[ibig!(1)], &[]);
+}
+
+#[test]
+fn test_sum_product() {
+    let empty: [UBig; 0] = [];
+    assert_eq!(empty.iter().sum::<UBig>(), ubig!(0));
+    assert_eq!(empty.iter().product::<UBig>(), ubig!(1));
+    let empty: [IBig; 0] = [];
+    assert_eq!(empty.iter().sum::<IBig>(), ibig!(0));
+    assert_eq!(empty.iter().product::<IBig>(), ibig!(1));
+
+    let a: Vec<UBig> = (1..=300u32).map(|i| UBig::from(i).pow(20)).collect();
+    let sum = a.iter().fold(ubig!(0), |sum, x| sum + x);
+    let product = a.iter().fold(ubig!(1), |product, x| product * x);
+    assert_eq!(a.iter().sum::<UBig>(), sum);
+    assert_eq!(a.clone().into_iter().sum::<UBig>(), sum);
+    assert_eq!(a.iter().product::<UBig>(), product);
+    assert_eq!(a.into_iter().product::<UBig>(), product);
+
+    let b: Vec<IBig> = (1..=301i32).map(|i| IBig::from(-i).pow(21)).collect();
+    let sum = b.iter().fold(ibig!(0), |sum, x| sum + x);
+    let product = b.iter().fold(ibig!(1), |product, x| product * x);
+    assert!(product < ibig!(0));
+    assert_eq!(b.iter().sum::<IBig>(), sum);
+    assert_eq!(b.clone().into_iter().sum::<IBig>(), sum);
+    assert_eq!(b.iter().product::<IBig>(), product);
+    assert_eq!(b.into_iter().product::<IBig>(), product);
+
+    assert_eq!(
+        [ibig!(-2), ibig!(0), ibig!(-3)].iter().product::<IBig>(),
+        ibig!(0)
+    );
+}
+
+#[test]
+fn test_product_remainder_tree() {
+    assert!(product_tree(&[]).is_empty());
+    assert!(remainder_tree(&ubig!(5), &[]).is_empty());
+
+    for len in [1, 2, 3, 7, 8, 100].iter() {
+        let moduli: Vec<UBig> = (0..*len)
+            .map(|i| ubig!(3).pow(i * 7 + 1) + ubig!(2))
+            .collect();
+        let tree = product_tree(&moduli);
+        assert_eq!(tree[0], moduli);
+        assert_eq!(tree.last().unwrap().len(), 1);
+        assert_eq!(tree.last().unwrap()[0], moduli.iter().product::<UBig>());
+        for x in [ubig!(0), ubig!(1000), ubig!(5).pow(5000)].iter() {
+            let expected: Vec<UBig> = moduli.iter().map(|m| x % m).collect();
+            assert_eq!(remainder_tree(x, &tree), expected);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_remainder_tree_zero_modulus() {
+    let tree = product_tree(&[ubig!(3), ubig!(0)]);
+    let _ = remainder_tree(&ubig!(5), &tree);
+}
@@ -0,0 +1,54 @@
+use ibig::{ops::DivRem, ubig, UBig};
+use std::thread;
+
+fn large_numbers(bits: usize) -> (UBig, UBig) {
+    let a = ubig!(3).pow(bits * 10 / 16) % (ubig!(1) << bits);
+    let b = (ubig!(1) << (bits / 2 + 7)) - ubig!(1) - ubig!(5).pow(bits / 7);
+    (a, b)
+}
+
+#[test]
+fn test_mul_div_parallel() {
+    let p = ubig!(18446744073709551557);
+    for bits in [200_000, 1_000_000].iter() {
+        let (a, b) = large_numbers(*bits);
+        let c = &a * &b;
+        assert_eq!(&c % &p, (&a % &p) * (&b % &p) % &p);
+        assert_eq!((&c).div_rem(&b), (a.clone(), ubig!(0)));
+        assert_eq!((&c + ubig!(5)).div_rem(&a), (b.clone(), ubig!(5)));
+        let sqr = &a * &a;
+        assert_eq!(sqr, &a * (&a + ubig!(1)) - &a);
+    }
+}
+
+#[test]
+fn test_radix_conversion_parallel() {
+    let (a, _) = large_numbers(300_000);
+    assert_eq!(a.to_string().parse::<UBig>().unwrap(), a);
+    let base_36 = a.in_radix(36).to_string();
+    assert_eq!(format!("{:#}", a.in_radix(36)), base_36.to_uppercase());
+    assert_eq!(UBig::from_str_radix(&base_36, 36).unwrap(), a);
+
+    let k = 50_000;
+    assert_eq!(
+        "9".repeat(k).parse::<UBig>().unwrap(),
+        ubig!(10).pow(k) - ubig!(1)
+    );
+    assert_eq!((ubig!(10).pow(k) - ubig!(1)).to_string(), "9".repeat(k));
+    assert_eq!(ubig!(10).pow(k).to_string(), format!("1{}", "0".repeat(k)));
+}
+
+#[test]
+fn test_concurrent_callers() {
+    let (a, b) = large_numbers(400_000);
+    let expected = &a * &b;
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let (a, b) = (a.clone(), b.clone());
+            thread::spawn(move || &a * &b)
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+}
@@ -0,0 +1,44 @@
+//! Results of parallel execution compared with the serial path.
+//!
+//! This is a separate test binary because it changes the thresholds of the whole process.
+
+use ibig::{ops::DivRem, ubig, ThresholdConfig, UBig};
+
+fn large_number(bits: usize, seed: u32) -> UBig {
+    (ubig!(3).pow(bits * 10 / 16) + UBig::from(seed)) % (ubig!(1) << bits)
+}
+
+/// Products and squares of a and b, and a division and radix conversions of c.
+fn compute(a: &UBig, b: &UBig, c: &UBig) -> (Vec<UBig>, String, String) {
+    let b_half = b >> (b.bit_len() / 2);
+    let (q, r) = (c * c).div_rem(&(c >> 1000));
+    let decimal = c.to_string();
+    let base_36 = c.in_radix(36).to_string();
+    let numbers = vec![
+        a * b,
+        a * &b_half,
+        a * a,
+        q,
+        r,
+        decimal.parse().unwrap(),
+        UBig::from_str_radix(&base_36, 36).unwrap(),
+    ];
+    (numbers, decimal, base_36)
+}
+
+#[test]
+fn test_parallel_same_as_serial() {
+    // Long enough for Karatsuba, Toom-Cook and the number-theoretic transform to run in parallel.
+    let a = large_number(3_000_000, 1);
+    let b = large_number(2_500_000, 2);
+    let c = large_number(300_000, 3);
+    let parallel = compute(&a, &b, &c);
+
+    let mut config = ThresholdConfig::built_in();
+    config.parallel = usize::MAX;
+    config.set_current_for_tuning();
+    let serial = compute(&a, &b, &c);
+    ThresholdConfig::built_in().set_current_for_tuning();
+
+    assert!(parallel == serial);
+}
@@ -0,0 +1,74 @@
+use ibig::{modular::ModuloRing, ops::DivRem, ubig, Scratch, UBig};
+
+#[test]
+fn test_mul_with_scratch() {
+    let test_cases = [
+        (ubig!(0), ubig!(5)),
+        (ubig!(3), ubig!(1) << 200),
+        (ubig!(1) << 200, ubig!(7)),
+        (ubig!(3).pow(500), ubig!(5).pow(400)),
+        (ubig!(3).pow(20000), ubig!(7).pow(10000)),
+        (ubig!(3).pow(50), ubig!(7).pow(40)),
+    ];
+    let mut scratch = Scratch::new();
+    for (a, b) in &test_cases {
+        assert_eq!(a.mul_with_scratch(b, &mut scratch), a * b);
+        assert_eq!(b.mul_with_scratch(a, &mut scratch), a * b);
+    }
+}
+
+#[test]
+fn test_div_rem_with_scratch() {
+    let test_cases = [
+        (ubig!(100), ubig!(7)),
+        (ubig!(5), ubig!(1) << 200),
+        (ubig!(1) << 200, ubig!(7)),
+        (ubig!(1) << 200, ubig!(3).pow(100)),
+        (ubig!(3).pow(40000), ubig!(7).pow(10000)),
+        (ubig!(3).pow(500), ubig!(5).pow(100)),
+    ];
+    let mut scratch = Scratch::new();
+    for (a, b) in &test_cases {
+        assert_eq!(a.div_rem_with_scratch(b, &mut scratch), a.div_rem(b));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_div_rem_with_scratch_by_0() {
+    let _ = ubig!(5).div_rem_with_scratch(&ubig!(0), &mut Scratch::new());
+}
+
+#[test]
+fn test_pow_with_scratch() {
+    let mut scratch = Scratch::new();
+    let moduli = [ubig!(100), ubig!(2).pow(607) - ubig!(1), ubig!(3).pow(1000)];
+    for m in &moduli {
+        let ring = ModuloRing::new(m);
+        for (a, e) in &[
+            (ubig!(0), ubig!(0)),
+            (ubig!(5), ubig!(1)),
+            (ubig!(123), ubig!(456)),
+            (ubig!(3).pow(100), ubig!(7).pow(300)),
+        ] {
+            let a = ring.from(a);
+            assert_eq!(a.pow_with_scratch(e, &mut scratch), a.pow(e));
+        }
+    }
+}
+
+#[test]
+fn test_scratch_reuse() {
+    let mut scratch = Scratch::default();
+    assert_eq!(scratch.capacity(), 0);
+    let a: UBig = ubig!(3).pow(20000);
+    let b: UBig = ubig!(5).pow(10000);
+    let _ = a.mul_with_scratch(&b, &mut scratch);
+    let capacity = scratch.capacity();
+    assert!(capacity > 0);
+    // Smaller operations don't grow the memory.
+    let _ = b.mul_with_scratch(&b, &mut scratch);
+    let _ = a.div_rem_with_scratch(&b, &mut scratch);
+    assert_eq!(scratch.capacity(), capacity);
+    assert_eq!(format!("{:?}", Scratch::new()), "Scratch { capacity: 0 }");
+}
@@ -0,0 +1,87 @@
+use ibig::{ubig, UBig};
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[test]
+fn test_euler_phi() {
+    let test_cases = [
+        (ubig!(1), ubig!(1)),
+        (ubig!(2), ubig!(1)),
+        (ubig!(9), ubig!(6)),
+        (ubig!(36), ubig!(12)),
+        (ubig!(97), ubig!(96)),
+        (ubig!(1) << 100, ubig!(1) << 99),
+        (ubig!(3).pow(50), ubig!(2) * ubig!(3).pow(49)),
+        // 2^64 + 1 = 274177 * 67280421310721
+        (
+            (ubig!(1) << 64) + ubig!(1),
+            ubig!(274176) * ubig!(67280421310720),
+        ),
+    ];
+
+    for (n, phi) in &test_cases {
+        assert_eq!(n.euler_phi(), *phi);
+    }
+
+    for n in 1..200u32 {
+        let phi = (1..=n).filter(|k| gcd(*k, n) == 1).count();
+        assert_eq!(UBig::from(n).euler_phi(), UBig::from(phi));
+    }
+}
+
+#[test]
+fn test_carmichael_lambda() {
+    let test_cases = [
+        (ubig!(1), ubig!(1)),
+        (ubig!(2), ubig!(1)),
+        (ubig!(4), ubig!(2)),
+        (ubig!(8), ubig!(2)),
+        (ubig!(36), ubig!(6)),
+        (ubig!(561), ubig!(80)),
+        (ubig!(1) << 100, ubig!(1) << 98),
+        ((ubig!(1) << 64) + ubig!(1), ubig!(72057331223781120)),
+    ];
+
+    for (n, lambda) in &test_cases {
+        assert_eq!(n.carmichael_lambda(), *lambda);
+    }
+
+    for n in 1..200u32 {
+        let lambda = carmichael_lambda_naive(n);
+        assert_eq!(UBig::from(n).carmichael_lambda(), UBig::from(lambda));
+    }
+}
+
+/// Smallest m > 0 with a^m = 1 (mod n) for all a coprime to n.
+fn carmichael_lambda_naive(n: u32) -> u32 {
+    let units: Vec<u32> = (1..=n).filter(|k| gcd(*k, n) == 1).collect();
+    (1..)
+        .find(|m| {
+            units.iter().all(|a| {
+                let mut x = 1 % n;
+                for _ in 0..*m {
+                    x = x * a % n;
+                }
+                x == 1 % n
+            })
+        })
+        .unwrap()
+}
+
+#[test]
+#[should_panic]
+fn test_euler_phi_zero() {
+    let _ = ubig!(0).euler_phi();
+}
+
+#[test]
+#[should_panic]
+fn test_carmichael_lambda_zero() {
+    let _ = ubig!(0).carmichael_lambda();
+}
@@ -0,0 +1,101 @@
+use ibig::{
+    error::{AllocError, CapacityError, ParseError, TryParseError},
+    ibig, ubig, IBig, UBig, Word,
+};
+
+#[test]
+fn test_try_clone() {
+    let a = ubig!(_0x123456789abcdef0123456789abcdef) << 1000;
+    assert_eq!(a.try_clone(), Ok(a.clone()));
+    assert_eq!(ubig!(5).try_clone(), Ok(ubig!(5)));
+    let b = -IBig::from(a);
+    assert_eq!(b.try_clone(), Ok(b.clone()));
+}
+
+#[test]
+fn test_try_mul() {
+    let test_cases = [
+        (ubig!(0), ubig!(0)),
+        (ubig!(3), ubig!(4)),
+        (UBig::from(Word::MAX), UBig::from(Word::MAX)),
+        (ubig!(0), ubig!(1) << 200),
+        (ubig!(1) << 200, ubig!(7)),
+        (ubig!(3).pow(500), ubig!(5).pow(400)),
+        (ubig!(3).pow(20000), ubig!(7).pow(10000)),
+    ];
+    for (a, b) in &test_cases {
+        assert_eq!(a.try_mul(b), Ok(a * b));
+        assert_eq!(b.try_mul(a), Ok(a * b));
+    }
+    assert_eq!(ibig!(-3).try_mul(&ibig!(-4)), Ok(ibig!(12)));
+}
+
+#[test]
+fn test_try_pow() {
+    assert_eq!(ubig!(0).try_pow(0), Ok(ubig!(1)));
+    assert_eq!(ubig!(2).try_pow(100), Ok(ubig!(1) << 100));
+    assert_eq!(ubig!(123).try_pow(13), Ok(ubig!(123).pow(13)));
+    assert_eq!(ibig!(-3).try_pow(5), Ok(ibig!(-243)));
+    assert_eq!(
+        ubig!(3).try_pow(usize::MAX),
+        Err(AllocError::Capacity(CapacityError))
+    );
+    assert_eq!(
+        ubig!(2).try_pow(UBig::MAX_BIT_LEN),
+        Err(AllocError::Capacity(CapacityError))
+    );
+}
+
+#[test]
+fn test_try_shl() {
+    assert_eq!(ubig!(0).try_shl(usize::MAX), Ok(ubig!(0)));
+    assert_eq!(ubig!(3).try_shl(1), Ok(ubig!(6)));
+    assert_eq!(ubig!(3).try_shl(1000), Ok(ubig!(3) << 1000));
+    assert_eq!((ubig!(3) << 1000).try_shl(7), Ok(ubig!(3) << 1007));
+    assert_eq!(ibig!(-3).try_shl(1000), Ok(ibig!(-3) << 1000));
+    assert_eq!(
+        ubig!(1).try_shl(UBig::MAX_BIT_LEN),
+        Err(AllocError::Capacity(CapacityError))
+    );
+    assert_eq!(
+        ubig!(1).try_shl(usize::MAX),
+        Err(AllocError::Capacity(CapacityError))
+    );
+}
+
+#[test]
+fn test_try_shl_one_bit_too_large() {
+    // Rejected before allocating MAX_LEN words, on every target.
+    assert_eq!(
+        ubig!(2).try_shl(UBig::MAX_BIT_LEN - 1),
+        Err(AllocError::Capacity(CapacityError))
+    );
+    assert_eq!(
+        (ubig!(1) << 100).try_shl(UBig::MAX_BIT_LEN - 100),
+        Err(AllocError::Capacity(CapacityError))
+    );
+    assert_eq!(
+        ibig!(-3).try_shl(UBig::MAX_BIT_LEN - 1),
+        Err(AllocError::Capacity(CapacityError))
+    );
+}
+
+#[test]
+fn test_try_from_str_radix() {
+    assert_eq!(UBig::try_from_str_radix("+7ab", 32), Ok(ubig!(7499)));
+    assert_eq!(IBig::try_from_str_radix("-7ab", 32), Ok(ibig!(-7499)));
+    assert_eq!(
+        UBig::try_from_str_radix("", 10),
+        Err(TryParseError::Parse(ParseError::NoDigits))
+    );
+    assert_eq!(
+        UBig::try_from_str_radix("12a", 10),
+        Err(TryParseError::Parse(ParseError::InvalidDigit))
+    );
+
+    let a = ubig!(3).pow(20000);
+    let dec = a.to_string();
+    assert_eq!(UBig::try_from_str_radix(&dec, 10), Ok(a.clone()));
+    let hex = a.in_radix(16).to_string();
+    assert_eq!(UBig::try_from_str_radix(&hex, 16), Ok(a));
+}